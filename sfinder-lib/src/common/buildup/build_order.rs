@@ -0,0 +1,158 @@
+//! Porting note: validates an unordered set of operations, unlike parse_to_operations which expects
+//! the operations to be in a valid order already. The order is searched with build_up, and is
+//! returned as the indexes of the operations.
+
+use super::build_up;
+use crate::{
+    common::datastore::mino_operation_with_key::MinoOperationWithKey,
+    sfinder_core::{
+        action::reachable::reachable::Reachable, field::field::Field,
+        mino::mino_factory::MinoFactory,
+    },
+};
+
+pub struct BuildOrderValidator<'a, T: MinoOperationWithKey> {
+    mino_factory: &'a MinoFactory,
+    field: &'a dyn Field,
+    operations: &'a [T],
+    height: u8,
+    reachable: &'a mut dyn Reachable,
+}
+
+impl<'a, T: MinoOperationWithKey> BuildOrderValidator<'a, T> {
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        field: &'a dyn Field,
+        operations: &'a [T],
+        height: u8,
+        reachable: &'a mut dyn Reachable,
+    ) -> Self {
+        Self {
+            mino_factory,
+            field,
+            operations,
+            height,
+            reachable,
+        }
+    }
+
+    // Returns one order that all operations can be placed in
+    pub fn find_order(&mut self) -> Option<Vec<usize>> {
+        build_up::find_build_order(
+            self.mino_factory,
+            self.field,
+            self.operations,
+            self.height,
+            self.reachable,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{datastore::operation::Operation, parser::operation_with_key_interpreter},
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{
+            action::reachable::{harddrop_reachable::HarddropReachable, reachable_facade},
+            field::field_factory,
+            mino::{mino_shifter::MinoShifter, piece::Piece},
+        },
+    };
+
+    // L and Z are placed after the I clears the third row
+    const OPERATIONS: &str = "J,0,1,0,0,3;I,0,1,2,0,4;L,L,3,1,4,11;Z,0,1,1,4,10";
+
+    fn field() -> Box<dyn Field> {
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX",
+        );
+        field
+    }
+
+    #[test]
+    fn find_orders() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        // given in an order that cannot be built
+        let mut operations =
+            operation_with_key_interpreter::parse_to_vec(OPERATIONS, &mino_factory);
+        operations.reverse();
+        let field = field();
+
+        let mut validator = BuildOrderValidator::new(
+            &mino_factory,
+            field.as_ref(),
+            &operations,
+            4,
+            reachable.as_mut(),
+        );
+
+        let order = validator.find_order().unwrap();
+        let pieces = order
+            .iter()
+            .map(|&index| operations[index].get_piece())
+            .collect::<Vec<_>>();
+        assert_eq!(&pieces[..2], &[Piece::J, Piece::I]);
+    }
+
+    #[test]
+    fn never_placed() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        // without the I, the third row is never cleared
+        let operations = operation_with_key_interpreter::parse_to_vec(
+            "J,0,1,0,0,3;L,L,3,1,4,11;Z,0,1,1,4,10",
+            &mino_factory,
+        );
+        let field = field();
+
+        let mut validator = BuildOrderValidator::new(
+            &mino_factory,
+            field.as_ref(),
+            &operations,
+            4,
+            reachable.as_mut(),
+        );
+        assert!(validator.find_order().is_none());
+    }
+
+    #[test]
+    fn harddrop() {
+        let mino_factory = MinoFactory::new();
+        let mut reachable = HarddropReachable::new(4);
+        // the I is placed on the O
+        let field = field_factory::create_field(4);
+        let operations =
+            operation_with_key_interpreter::parse_to_vec("I,0,1,2,0,4;O,0,0,0,0,3", &mino_factory);
+
+        let mut validator = BuildOrderValidator::new(
+            &mino_factory,
+            field.as_ref(),
+            &operations,
+            4,
+            &mut reachable,
+        );
+        assert_eq!(validator.find_order(), Some(vec![1, 0]));
+    }
+}
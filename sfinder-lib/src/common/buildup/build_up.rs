@@ -0,0 +1,482 @@
+//! Porting note: replaces BuildUp.
+//! The field is kept without clearing rows, and each operation is merged into it with the rows of
+//! its need_deleted_key inserted back. The rows are cleared only to check if a mino can be placed.
+
+use crate::{
+    common::datastore::mino_operation_with_key::MinoOperationWithKey,
+    searcher::common::hold_choices,
+    sfinder_core::{
+        action::reachable::reachable::Reachable,
+        field::{field::Field, key_operators},
+        mino::{mino::Mino, mino_factory::MinoFactory, piece::Piece},
+    },
+};
+use std::ptr;
+
+// Called with the placed operation and the field after it is placed
+type BuildNext<'a, T> = dyn FnMut(&T, &dyn Field, &mut Vec<&T>, &mut dyn Reachable) -> bool + 'a;
+
+// The field after the filled rows are cleared, with the position of the operation in it
+pub struct ClearedPosition {
+    pub field: Box<dyn Field>,
+    pub mino: &'static Mino,
+    pub x: u8,
+    pub y: u8,
+    // The height of the field after the rows are cleared
+    pub max_y: u8,
+}
+
+// Returns None if the rows that the operation needs to be deleted are not cleared
+pub fn to_cleared_position(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operation: &impl MinoOperationWithKey,
+    height: u8,
+) -> Option<ClearedPosition> {
+    let mut freeze = field.prune(height);
+    let delete_key = freeze.clear_filled_rows_return_key();
+
+    // 必要なラインが消去されていない
+    let need_deleted_key = operation.get_need_deleted_key();
+    if delete_key & need_deleted_key != need_deleted_key {
+        return None;
+    }
+
+    // 既に消去されたライン分だけ、下にずらす
+    let lower_y = key_operators::bit_to_y_from_key(key_operators::extract_lower_bit(
+        operation.get_using_key(),
+    ));
+    let deleted_lines =
+        (key_operators::get_mask_for_key_below_y(lower_y) & delete_key).count_ones() as u8;
+
+    let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+    Some(ClearedPosition {
+        field: freeze,
+        mino,
+        x: operation.get_x(),
+        y: ((lower_y - deleted_lines) as i8 - mino.get_min_y()) as u8,
+        max_y: height - delete_key.count_ones() as u8,
+    })
+}
+
+// Whether the operation can be placed on the field, after the filled rows are cleared
+pub fn can_put_operation(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operation: &impl MinoOperationWithKey,
+    height: u8,
+    reachable: &mut dyn Reachable,
+) -> bool {
+    let Some(ClearedPosition {
+        field,
+        mino,
+        x,
+        y,
+        max_y,
+    }) = to_cleared_position(mino_factory, field, operation, height)
+    else {
+        return false;
+    };
+
+    field.can_put(mino, x, y)
+        && field.is_on_ground(mino, x, y)
+        && reachable.checks(field.as_ref(), mino, x, y, max_y)
+}
+
+// Tries to place each remaining operation of the piece (any piece if None),
+// and calls `next` with the field after the placement.
+#[allow(clippy::too_many_arguments)]
+fn build_piece<T: MinoOperationWithKey>(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    remaining: &mut Vec<&T>,
+    piece: Option<Piece>,
+    height: u8,
+    reachable: &mut dyn Reachable,
+    next: &mut BuildNext<'_, T>,
+) -> bool {
+    for index in 0..remaining.len() {
+        let operation = remaining[index];
+        if piece.is_some_and(|piece| operation.get_piece() != piece)
+            || !can_put_operation(mino_factory, field, operation, height, reachable)
+        {
+            continue;
+        }
+
+        let mut next_field = field.prune(height);
+        next_field.merge(operation.create_mino_field(height).as_ref());
+
+        remaining.remove(index);
+        let is_found = next(operation, next_field.as_ref(), remaining, reachable);
+        remaining.insert(index, operation);
+
+        if is_found {
+            return true;
+        }
+    }
+
+    false
+}
+
+// Returns the indexes of the operations in an order that they can be placed in
+pub fn find_build_order<T: MinoOperationWithKey>(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[T],
+    height: u8,
+    reachable: &mut dyn Reachable,
+) -> Option<Vec<usize>> {
+    fn build<T: MinoOperationWithKey>(
+        mino_factory: &MinoFactory,
+        operations: &[T],
+        field: &dyn Field,
+        remaining: &mut Vec<&T>,
+        height: u8,
+        reachable: &mut dyn Reachable,
+        order: &mut Vec<usize>,
+    ) -> bool {
+        if remaining.is_empty() {
+            return true;
+        }
+
+        build_piece(
+            mino_factory,
+            field,
+            remaining,
+            None,
+            height,
+            reachable,
+            &mut |placed, field, remaining, reachable| {
+                let index = operations
+                    .iter()
+                    .position(|operation| ptr::eq(operation, placed))
+                    .unwrap();
+                order.push(index);
+                let is_found = build(
+                    mino_factory,
+                    operations,
+                    field,
+                    remaining,
+                    height,
+                    reachable,
+                    order,
+                );
+                if !is_found {
+                    order.pop();
+                }
+                is_found
+            },
+        )
+    }
+
+    let mut order = Vec::with_capacity(operations.len());
+    build(
+        mino_factory,
+        operations,
+        field,
+        &mut operations.iter().collect(),
+        height,
+        reachable,
+        &mut order,
+    )
+    .then_some(order)
+}
+
+// Whether the operations can be placed in some order
+#[cfg(test)]
+pub fn cans_build<T: MinoOperationWithKey>(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[T],
+    height: u8,
+    reachable: &mut dyn Reachable,
+) -> bool {
+    find_build_order(mino_factory, field, operations, height, reachable).is_some()
+}
+
+// Whether the operations can be placed in the order of the pieces.
+// The pieces after all operations are placed are ignored.
+pub fn exists_valid_by_order<T: MinoOperationWithKey>(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[T],
+    pieces: &[Piece],
+    height: u8,
+    reachable: &mut dyn Reachable,
+) -> bool {
+    fn build<T: MinoOperationWithKey>(
+        mino_factory: &MinoFactory,
+        field: &dyn Field,
+        remaining: &mut Vec<&T>,
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn Reachable,
+    ) -> bool {
+        if remaining.is_empty() {
+            return true;
+        }
+        let Some((&piece, rest)) = pieces.split_first() else {
+            return false;
+        };
+
+        build_piece(
+            mino_factory,
+            field,
+            remaining,
+            Some(piece),
+            height,
+            reachable,
+            &mut |_, field, remaining, reachable| {
+                build(mino_factory, field, remaining, rest, height, reachable)
+            },
+        )
+    }
+
+    build(
+        mino_factory,
+        field,
+        &mut operations.iter().collect(),
+        pieces,
+        height,
+        reachable,
+    )
+}
+
+// Whether the operations can be placed in an order that can be made from the pieces with hold
+pub fn exists_valid_by_order_with_hold<T: MinoOperationWithKey>(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[T],
+    pieces: &[Piece],
+    height: u8,
+    reachable: &mut dyn Reachable,
+) -> bool {
+    #[allow(clippy::too_many_arguments)]
+    fn build<T: MinoOperationWithKey>(
+        mino_factory: &MinoFactory,
+        field: &dyn Field,
+        remaining: &mut Vec<&T>,
+        hold: Option<Piece>,
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn Reachable,
+    ) -> bool {
+        if remaining.is_empty() {
+            return true;
+        }
+
+        hold_choices::get_choices_from_slice(hold, pieces, true)
+            .into_iter()
+            .any(|(piece, next_hold, next_pieces)| {
+                build_piece(
+                    mino_factory,
+                    field,
+                    remaining,
+                    Some(piece),
+                    height,
+                    reachable,
+                    &mut |_, field, remaining, reachable| {
+                        build(
+                            mino_factory,
+                            field,
+                            remaining,
+                            next_hold,
+                            next_pieces,
+                            height,
+                            reachable,
+                        )
+                    },
+                )
+            })
+    }
+
+    build(
+        mino_factory,
+        field,
+        &mut operations.iter().collect(),
+        None,
+        pieces,
+        height,
+        reachable,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use crate::{
+        common::{
+            datastore::{operations::Operations, simple_operation::SimpleOperation},
+            parser::{operation_transform, operation_with_key_interpreter},
+        },
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{
+            action::reachable::reachable_facade, field::field_factory,
+            mino::mino_shifter::MinoShifter,
+        },
+    };
+
+    // L and Z are placed after the I clears the third row
+    const OPERATIONS: &str = "J,0,1,0,0,3;I,0,1,2,0,4;L,L,3,1,4,11;Z,0,1,1,4,10";
+
+    fn field() -> Box<dyn Field> {
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX",
+        );
+        field
+    }
+
+    #[test]
+    fn build() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        let operations = operation_with_key_interpreter::parse_to_vec(OPERATIONS, &mino_factory);
+        let field = field();
+
+        assert!(cans_build(
+            &mino_factory,
+            field.as_ref(),
+            &operations,
+            4,
+            reachable.as_mut()
+        ));
+
+        // the order of the operations is not used
+        let mut reversed = operations.clone();
+        reversed.reverse();
+        assert!(cans_build(
+            &mino_factory,
+            field.as_ref(),
+            &reversed,
+            4,
+            reachable.as_mut()
+        ));
+    }
+
+    #[test]
+    fn by_order() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        let operations = operation_with_key_interpreter::parse_to_vec(OPERATIONS, &mino_factory);
+        let field = field();
+
+        let mut exists = |pieces: &[Piece], is_using_hold| {
+            if is_using_hold {
+                exists_valid_by_order_with_hold(
+                    &mino_factory,
+                    field.as_ref(),
+                    &operations,
+                    pieces,
+                    4,
+                    reachable.as_mut(),
+                )
+            } else {
+                exists_valid_by_order(
+                    &mino_factory,
+                    field.as_ref(),
+                    &operations,
+                    pieces,
+                    4,
+                    reachable.as_mut(),
+                )
+            }
+        };
+
+        assert!(exists(&pieces("JILZ"), false));
+        assert!(exists(&pieces("JIZL"), false));
+        // the I floats before the J is placed, and the L needs the third row to be cleared
+        assert!(!exists(&pieces("IJLZ"), false));
+        assert!(!exists(&pieces("JLIZ"), false));
+        assert!(!exists(&pieces("JIL"), false));
+
+        assert!(exists(&pieces("IJLZ"), true));
+        assert!(exists(&pieces("JLIZ"), true));
+        assert!(!exists(&pieces("LZJI"), true));
+    }
+
+    #[test]
+    fn needs_deleted_rows() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        let operations = operation_with_key_interpreter::parse_to_vec(OPERATIONS, &mino_factory);
+        let field = field();
+
+        // the Z cannot be placed before the third row is cleared
+        assert!(!cans_build(
+            &mino_factory,
+            field.as_ref(),
+            &operations[3..],
+            4,
+            reachable.as_mut()
+        ));
+    }
+
+    #[test]
+    fn above_24_rows() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let height = 30;
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            height,
+        );
+        let field = field_factory::create_extra_large_field_with_marks(
+            String::new() + "XXXXXX____" + &"XXXXXXXXX_".repeat(3) + &"XXXXXXXX_X".repeat(22),
+        );
+        let operations = operation_transform::parse_to_operations_with_key(
+            &field,
+            &"I,0,7,25;I,L,9,23"
+                .parse::<Operations<SimpleOperation>>()
+                .unwrap(),
+            &mino_factory,
+            height,
+        );
+
+        assert!(cans_build(
+            &mino_factory,
+            &field,
+            &operations,
+            height,
+            reachable.as_mut()
+        ));
+
+        // the 25th row is not cleared without the first I
+        assert!(!cans_build(
+            &mino_factory,
+            &field,
+            &operations[1..],
+            height,
+            reachable.as_mut()
+        ));
+    }
+}
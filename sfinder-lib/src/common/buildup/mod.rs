@@ -0,0 +1,2 @@
+pub mod build_order;
+pub mod build_up;
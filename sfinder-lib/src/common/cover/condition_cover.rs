@@ -0,0 +1,463 @@
+//! Porting note: replaces NormalCover, TSpinCover, B2BContinuousCover and the other covers with conditions.
+//! The conditions are checked along each order, with the rows cleared by each placement and the
+//! spin detected by SpinChecker, which is only the T-spin unless the spin rule is AllSpin.
+
+use super::{
+    cover::{self, Cover},
+    reachable::reachable_for_cover::ReachableForCover,
+};
+use crate::{
+    common::{
+        buildup::build_up::ClearedPosition,
+        datastore::{
+            full_operation_with_key::FullOperationWithKey,
+            simple_mino_operation::SimpleMinoOperation,
+        },
+        spin_checker::{SpinChecker, SpinRule},
+    },
+    searcher::{
+        common::transposition_table::TranspositionTable,
+        spins::spin::{Spin, TSpins},
+    },
+    sfinder_core::{
+        action::reachable::reachable_facade,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::{mino_rotation::MinoRotation, mino_rotation_detail::MinoRotationDetail},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverCondition {
+    // 条件なし
+    Normal,
+    // min_cleared_rows以上のラインを消去するTスピンを1回以上する
+    // Porting note: any spin is counted when the spin rule is AllSpin
+    TSpin {
+        min_cleared_rows: u8,
+        regular_only: bool,
+    },
+    // ラインを消去するときは、すべてテトリスかTスピン
+    B2BContinuous,
+    // 合計でmin_cleared_rows以上のラインを消去する
+    ClearedRows {
+        min_cleared_rows: u8,
+    },
+    // テトリスを1回以上する
+    Tetris,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Progress {
+    cleared_rows: u8,
+    is_satisfied: bool,
+}
+
+impl From<Progress> for u64 {
+    fn from(progress: Progress) -> Self {
+        (progress.cleared_rows as u64) << 1 | progress.is_satisfied as u64
+    }
+}
+
+impl CoverCondition {
+    fn needs_spin(self) -> bool {
+        matches!(self, Self::TSpin { .. } | Self::B2BContinuous)
+    }
+
+    fn init(self) -> Progress {
+        Progress {
+            cleared_rows: 0,
+            is_satisfied: matches!(self, Self::Normal | Self::B2BContinuous),
+        }
+    }
+
+    // Returns None if the placement breaks the condition
+    fn next(self, progress: Progress, cleared_rows: u8, spin: Option<&Spin>) -> Option<Progress> {
+        let mut next = Progress {
+            cleared_rows: progress.cleared_rows + cleared_rows,
+            is_satisfied: progress.is_satisfied,
+        };
+
+        match self {
+            Self::Normal => {}
+            Self::TSpin {
+                min_cleared_rows,
+                regular_only,
+            } => {
+                next.is_satisfied |= cleared_rows >= min_cleared_rows
+                    && spin.is_some_and(|spin| !regular_only || spin.spin == TSpins::Regular);
+            }
+            Self::B2BContinuous => {
+                if cleared_rows != 0 && cleared_rows != 4 && spin.is_none() {
+                    return None;
+                }
+            }
+            Self::ClearedRows { min_cleared_rows } => {
+                next.is_satisfied = min_cleared_rows <= next.cleared_rows;
+            }
+            Self::Tetris => {
+                next.is_satisfied |= cleared_rows == 4;
+            }
+        }
+
+        Some(next)
+    }
+}
+
+pub struct ConditionCover<'a> {
+    mino_factory: &'a MinoFactory,
+    spin_checker: SpinChecker<'a>,
+    condition: CoverCondition,
+    // The states that are known to fail in the current search
+    table: TranspositionTable<()>,
+}
+
+impl<'a> ConditionCover<'a> {
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_y: u8,
+        use_180_rotation: bool,
+        condition: CoverCondition,
+        spin_rule: SpinRule,
+    ) -> Self {
+        Self {
+            mino_factory,
+            spin_checker: SpinChecker::new(
+                mino_factory,
+                MinoRotationDetail::new(mino_factory, mino_rotation),
+                reachable_facade::create_locked(
+                    mino_factory,
+                    mino_shifter,
+                    mino_rotation,
+                    max_y,
+                    use_180_rotation,
+                ),
+                use_180_rotation,
+                spin_rule,
+            ),
+            condition,
+            table: TranspositionTable::default(),
+        }
+    }
+
+    fn search(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn ReachableForCover,
+        is_using_hold: bool,
+    ) -> bool {
+        let condition = self.condition;
+        let spin_checker = &mut self.spin_checker;
+
+        cover::exists_valid_order(
+            self.mino_factory,
+            field,
+            operations,
+            pieces,
+            height,
+            reachable,
+            is_using_hold,
+            condition.init(),
+            &mut |progress, position: &ClearedPosition| {
+                let ClearedPosition {
+                    field,
+                    mino,
+                    x,
+                    y,
+                    max_y,
+                } = position;
+
+                let mut freeze = field.prune(*max_y);
+                freeze.put(mino, *x, *y);
+                let cleared_rows = freeze.clear_filled_rows() as u8;
+
+                let spin = (condition.needs_spin()
+                    && spin_checker.is_spin_target(mino.get_piece()))
+                .then(|| {
+                    spin_checker.check(
+                        field.as_ref(),
+                        &SimpleMinoOperation::new(mino, *x, *y),
+                        *max_y,
+                        cleared_rows,
+                    )
+                })
+                .flatten();
+
+                condition.next(progress, cleared_rows, spin.as_ref())
+            },
+            &|progress| progress.is_satisfied,
+            &mut self.table,
+        )
+    }
+}
+
+impl Cover for ConditionCover<'_> {
+    fn can_build(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn ReachableForCover,
+    ) -> bool {
+        self.search(field, operations, pieces, height, reachable, false)
+    }
+
+    fn can_build_with_hold(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn ReachableForCover,
+    ) -> bool {
+        self.search(field, operations, pieces, height, reachable, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::cover::reachable::reachable_for_cover::ReachableForCoverWrapper,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{field::field_factory, srs::rotate::Rotate},
+    };
+
+    // Returns whether each condition is satisfied by placing the operations in the order of the pieces
+    fn check(
+        marks: String,
+        operations: &[(Piece, Rotate, u8, u8)],
+        pieces: &[Piece],
+        conditions: &[CoverCondition],
+    ) -> Vec<bool> {
+        check_with_spin_rule(marks, operations, pieces, conditions, 4, SpinRule::TSpin)
+    }
+
+    fn check_with_spin_rule(
+        marks: String,
+        operations: &[(Piece, Rotate, u8, u8)],
+        pieces: &[Piece],
+        conditions: &[CoverCondition],
+        height: u8,
+        spin_rule: SpinRule,
+    ) -> Vec<bool> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = ReachableForCoverWrapper(reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            height,
+        ));
+
+        let field = field_factory::create_field_with_marks(marks);
+        // (piece, rotate, x, the lowest y of the blocks)
+        let operations = operations
+            .iter()
+            .map(|&(piece, rotate, x, lower_y)| {
+                let mino = mino_factory.get(piece, rotate);
+                let y = (lower_y as i8 - mino.get_min_y()) as u8;
+                FullOperationWithKey::create(mino, x, y, 0, height)
+            })
+            .collect::<Vec<_>>();
+
+        conditions
+            .iter()
+            .map(|&condition| {
+                ConditionCover::new(
+                    &mino_factory,
+                    &mino_shifter,
+                    mino_rotation.as_ref(),
+                    height,
+                    false,
+                    condition,
+                    spin_rule,
+                )
+                .can_build(
+                    field.as_ref(),
+                    &operations,
+                    pieces,
+                    height,
+                    &mut reachable,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tetris() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "_XXXXXXXXX"
+            + "_XXXXXXXXX"
+            + "_XXXXXXXXX"
+            + "_XXXXXXXXX";
+
+        let result = check(
+            marks,
+            &[(Piece::I, Rotate::Left, 0, 0)],
+            &[Piece::I],
+            &[
+                CoverCondition::Normal,
+                CoverCondition::Tetris,
+                CoverCondition::ClearedRows {
+                    min_cleared_rows: 4,
+                },
+                CoverCondition::ClearedRows {
+                    min_cleared_rows: 5,
+                },
+                CoverCondition::B2BContinuous,
+                CoverCondition::TSpin {
+                    min_cleared_rows: 1,
+                    regular_only: false,
+                },
+            ],
+        );
+        assert_eq!(result, vec![true, true, true, false, true, false]);
+    }
+
+    #[test]
+    fn t_spin_double() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XX________"
+            + "X___XXXXXX"
+            + "XX_XXXXXXX";
+
+        let result = check(
+            marks,
+            &[(Piece::T, Rotate::Reverse, 2, 0)],
+            &[Piece::T],
+            &[
+                CoverCondition::Normal,
+                CoverCondition::TSpin {
+                    min_cleared_rows: 2,
+                    regular_only: true,
+                },
+                CoverCondition::TSpin {
+                    min_cleared_rows: 3,
+                    regular_only: false,
+                },
+                CoverCondition::B2BContinuous,
+                CoverCondition::Tetris,
+            ],
+        );
+        assert_eq!(result, vec![true, true, false, true, false]);
+    }
+
+    #[test]
+    fn all_spin() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXX___XXXX"
+            + "XXXXX__XXX"
+            + "XXXX__XXXX";
+
+        let conditions = [
+            CoverCondition::B2BContinuous,
+            CoverCondition::TSpin {
+                min_cleared_rows: 1,
+                regular_only: false,
+            },
+            CoverCondition::TSpin {
+                min_cleared_rows: 1,
+                regular_only: true,
+            },
+        ];
+        let operations = [(Piece::Z, Rotate::Spawn, 5, 1)];
+
+        let result = check_with_spin_rule(
+            marks.clone(),
+            &operations,
+            &[Piece::Z],
+            &conditions,
+            6,
+            SpinRule::TSpin,
+        );
+        assert_eq!(result, vec![false, false, false]);
+
+        // the Z spin is a mini
+        let result = check_with_spin_rule(
+            marks,
+            &operations,
+            &[Piece::Z],
+            &conditions,
+            6,
+            SpinRule::AllSpin,
+        );
+        assert_eq!(result, vec![true, true, false]);
+    }
+
+    #[test]
+    fn b2b_broken() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____";
+
+        // the I clears a single, and the O is placed over it
+        let result = check(
+            marks,
+            &[
+                (Piece::I, Rotate::Spawn, 7, 0),
+                (Piece::O, Rotate::Spawn, 0, 0),
+            ],
+            &[Piece::I, Piece::O],
+            &[
+                CoverCondition::Normal,
+                CoverCondition::B2BContinuous,
+                CoverCondition::ClearedRows {
+                    min_cleared_rows: 1,
+                },
+            ],
+        );
+        assert_eq!(result, vec![true, false, true]);
+    }
+
+    #[test]
+    fn reuse_cover() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = ReachableForCoverWrapper(reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        ));
+        let mut cover = ConditionCover::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+            false,
+            CoverCondition::Normal,
+            SpinRule::TSpin,
+        );
+
+        let field = field_factory::create_field_with_marks("XXXXXX____".to_owned());
+        let operations = [
+            FullOperationWithKey::create(mino_factory.get(Piece::I, Rotate::Spawn), 7, 0, 0, 4),
+            FullOperationWithKey::create(mino_factory.get(Piece::O, Rotate::Spawn), 0, 0, 0, 4),
+        ];
+
+        // 前のミノ列で失敗した状態は、次のミノ列では使われない
+        for (pieces, expected) in [
+            ([Piece::O, Piece::I], false),
+            ([Piece::I, Piece::O], true),
+            ([Piece::O, Piece::I], false),
+        ] {
+            assert_eq!(
+                cover.can_build(field.as_ref(), &operations, &pieces, 4, &mut reachable),
+                expected,
+                "{pieces:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Porting note: the orders are searched in the same way for all covers like BuildUp, keeping the
+//! field without clearing rows. The covers only differ in which placements they allow, which is
+//! decided by `on_place` with a state carried along the order.
+//! The states that fail are kept in a transposition table, with the state of the cover as the extra
+//! of the key. The field is not cleared, so it also decides the remaining operations.
+
+use super::reachable::reachable_for_cover::ReachableForCover;
+use crate::{
+    common::{
+        buildup::build_up::{self, ClearedPosition},
+        datastore::{
+            full_operation_with_key::FullOperationWithKey,
+            mino_operation_with_key::MinoOperationWithKey, operation::Operation,
+            piece_counter::PieceCounter,
+        },
+    },
+    searcher::common::{
+        hold_choices,
+        transposition_table::{StateKey, TranspositionTable},
+    },
+    sfinder_core::{
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+
+pub trait Cover {
+    // ミノ列の順番で、すべての操作を置くことができるとき true を返却
+    // reachableには、そのミノを含めた残りの置くミノの数が渡される
+    fn can_build(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn ReachableForCover,
+    ) -> bool;
+
+    // ホールドを使って、すべての操作を置くことができるとき true を返却
+    fn can_build_with_hold(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+        height: u8,
+        reachable: &mut dyn ReachableForCover,
+    ) -> bool;
+}
+
+// The state after the placement, or None if the placement is not allowed.
+// The position has the field before the placement, with the filled rows cleared.
+pub type OnPlace<'a, S> = dyn FnMut(S, &ClearedPosition) -> Option<S> + 'a;
+
+struct Search<'a, 'r, S> {
+    mino_factory: &'a MinoFactory,
+    height: u8,
+    reachable: &'r mut dyn ReachableForCover,
+    is_using_hold: bool,
+    on_place: &'r mut OnPlace<'a, S>,
+    is_satisfied: &'r dyn Fn(S) -> bool,
+    table: &'r mut TranspositionTable<()>,
+}
+
+impl<S: Copy + Into<u64>> Search<'_, '_, S> {
+    fn build(
+        &mut self,
+        field: &dyn Field,
+        remaining: &mut Vec<&FullOperationWithKey>,
+        hold: Option<Piece>,
+        pieces: &[Piece],
+        state: S,
+    ) -> bool {
+        if remaining.is_empty() {
+            return (self.is_satisfied)(state);
+        }
+
+        // The remaining pieces are a suffix of the sequence, so their counts decide the state
+        let key = StateKey::new(field, PieceCounter::from(pieces.iter().copied()), hold)
+            .with_extra(state.into());
+        if self.table.get(&key).is_some() {
+            return false;
+        }
+
+        let is_found = hold_choices::get_choices_from_slice(hold, pieces, self.is_using_hold)
+            .into_iter()
+            .any(|(piece, next_hold, next_pieces)| {
+                self.build_piece(field, remaining, piece, next_hold, next_pieces, state)
+            });
+
+        if !is_found {
+            self.table.insert(key, remaining.len() as u8, ());
+        }
+        is_found
+    }
+
+    fn build_piece(
+        &mut self,
+        field: &dyn Field,
+        remaining: &mut Vec<&FullOperationWithKey>,
+        piece: Piece,
+        hold: Option<Piece>,
+        pieces: &[Piece],
+        state: S,
+    ) -> bool {
+        // このミノを含めて、残りの置くミノの数
+        let remaining_depth = remaining.len() as u8;
+
+        for index in 0..remaining.len() {
+            let operation = remaining[index];
+            if operation.get_piece() != piece {
+                continue;
+            }
+
+            let Some(position) =
+                build_up::to_cleared_position(self.mino_factory, field, operation, self.height)
+            else {
+                continue;
+            };
+
+            let ClearedPosition {
+                field: ref freeze,
+                mino,
+                x,
+                y,
+                max_y,
+            } = position;
+            if !freeze.can_put(mino, x, y)
+                || !freeze.is_on_ground(mino, x, y)
+                || !self
+                    .reachable
+                    .checks(freeze.as_ref(), mino, x, y, max_y, remaining_depth)
+            {
+                continue;
+            }
+
+            let Some(next_state) = (self.on_place)(state, &position) else {
+                continue;
+            };
+
+            let mut next_field = field.prune(self.height);
+            next_field.merge(operation.create_mino_field(self.height).as_ref());
+
+            remaining.remove(index);
+            let is_found = self.build(next_field.as_ref(), remaining, hold, pieces, next_state);
+            remaining.insert(index, operation);
+
+            if is_found {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+// Whether all operations can be placed in the order of the pieces, where each placement is allowed
+// by on_place, and the state after the last placement satisfies is_satisfied.
+// The state is converted to the extra of the keys in the table, so it should be identified by the value.
+#[allow(clippy::too_many_arguments)]
+pub fn exists_valid_order<'a, S: Copy + Into<u64>>(
+    mino_factory: &'a MinoFactory,
+    field: &dyn Field,
+    operations: &[FullOperationWithKey],
+    pieces: &[Piece],
+    height: u8,
+    reachable: &mut dyn ReachableForCover,
+    is_using_hold: bool,
+    init_state: S,
+    on_place: &mut OnPlace<'a, S>,
+    is_satisfied: &dyn Fn(S) -> bool,
+    table: &mut TranspositionTable<()>,
+) -> bool {
+    // 別のミノ列や操作では、同じ状態でも結果が違う
+    table.new_search();
+
+    Search {
+        mino_factory,
+        height,
+        reachable,
+        is_using_hold,
+        on_place,
+        is_satisfied,
+        table,
+    }
+    .build(
+        field.prune(height).as_ref(),
+        &mut operations.iter().collect(),
+        None,
+        pieces,
+        init_state,
+    )
+}
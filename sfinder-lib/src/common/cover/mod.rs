@@ -1 +1,3 @@
+pub mod condition_cover;
+pub mod cover;
 pub mod reachable;
@@ -28,3 +28,20 @@ impl ReachableForCover for dyn Reachable {
         <dyn Reachable>::checks(self, field, mino, x, y, valid_height)
     }
 }
+
+// Porting note: a boxed reachable cannot be coerced into ReachableForCover, so it is wrapped instead
+pub struct ReachableForCoverWrapper<'a>(pub Box<dyn Reachable + 'a>);
+
+impl ReachableForCover for ReachableForCoverWrapper<'_> {
+    fn checks(
+        &mut self,
+        field: &dyn Field,
+        mino: &'static Mino,
+        x: u8,
+        y: u8,
+        valid_height: u8,
+        _remaining_depth: u8,
+    ) -> bool {
+        self.0.checks(field, mino, x, y, valid_height)
+    }
+}
@@ -1,3 +1,4 @@
 pub mod array_memory;
 pub mod field_memory;
 pub mod memory;
+pub mod row_memory;
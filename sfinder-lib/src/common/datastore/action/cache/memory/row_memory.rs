@@ -0,0 +1,28 @@
+use super::memory::Memory;
+
+// Porting note: stores each row as a bitmask, for fields wider than FIELD_WIDTH
+pub struct RowMemory {
+    data: Vec<u64>,
+}
+
+impl RowMemory {
+    pub fn new(height: u8) -> Self {
+        Self {
+            data: vec![0; height as _],
+        }
+    }
+}
+
+impl Memory for RowMemory {
+    fn get(&self, x: u8, y: u8) -> bool {
+        self.data[y as usize] & (1 << x) != 0
+    }
+
+    fn set(&mut self, x: u8, y: u8) {
+        self.data[y as usize] |= 1 << x;
+    }
+
+    fn clear(&mut self) {
+        self.data.fill(0);
+    }
+}
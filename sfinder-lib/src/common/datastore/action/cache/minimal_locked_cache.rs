@@ -1,5 +1,6 @@
 use crate::sfinder_core::{
     field::{
+        field_constants::FIELD_WIDTH,
         large_field::{LargeField, MAX_FIELD_HEIGHT as LARGE_MAX},
         middle_field::{MiddleField, MAX_FIELD_HEIGHT as MIDDLE_MAX},
         small_field::{SmallField, MAX_FIELD_HEIGHT as SMALL_MAX},
@@ -7,21 +8,31 @@ use crate::sfinder_core::{
     srs::rotate::Rotate,
 };
 
-use super::memory::{array_memory::ArrayMemory, field_memory::FieldMemory, memory::Memory};
+use super::memory::{
+    array_memory::ArrayMemory, field_memory::FieldMemory, memory::Memory, row_memory::RowMemory,
+};
 
 pub struct MinimalLockedCache {
     visited_map: [Box<dyn Memory>; Rotate::get_size()],
+    height: u8,
+    width: u8,
 }
 
 impl MinimalLockedCache {
     pub fn new(height: u8) -> Self {
         Self {
-            visited_map: std::array::from_fn(|_| Self::create_memory(height)),
+            visited_map: std::array::from_fn(|_| Self::create_memory(FIELD_WIDTH, height)),
+            height,
+            width: FIELD_WIDTH,
         }
     }
 
     // TODO: I think this is the only place FieldMemory is created, so the Field implementations have to be imported here
-    fn create_memory(height: u8) -> Box<dyn Memory> {
+    fn create_memory(width: u8, height: u8) -> Box<dyn Memory> {
+        if FIELD_WIDTH < width {
+            return Box::new(RowMemory::new(height));
+        }
+
         match height {
             ..=SMALL_MAX => Box::new(FieldMemory::<SmallField>::new()),
             ..=MIDDLE_MAX => Box::new(FieldMemory::<MiddleField>::new()),
@@ -36,6 +47,16 @@ impl MinimalLockedCache {
         }
     }
 
+    // Clears the cache, and recreates the memory if the width of the field has changed
+    pub fn clear_with_width(&mut self, width: u8) {
+        if self.width == width {
+            self.clear();
+        } else {
+            self.visited_map = std::array::from_fn(|_| Self::create_memory(width, self.height));
+            self.width = width;
+        }
+    }
+
     pub fn visit(&mut self, x: u8, y: u8, rotate: Rotate) {
         self.visited_map[rotate as usize].set(x, y);
     }
@@ -48,7 +69,6 @@ impl MinimalLockedCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sfinder_core::field::field_constants::FIELD_WIDTH;
 
     fn assert_cache(height: u8) {
         let mut cache = MinimalLockedCache::new(height);
@@ -1,6 +1,11 @@
 use super::action::action::Action;
 use crate::sfinder_core::{
-    field::field_constants::FIELD_WIDTH, mino::piece::Piece, srs::rotate::Rotate,
+    field::{
+        extra_large_field::MAX_FIELD_HEIGHT, field_constants::FIELD_WIDTH,
+        variable_width_field::MAX_FIELD_WIDTH,
+    },
+    mino::piece::Piece,
+    srs::rotate::Rotate,
 };
 use std::fmt::Display;
 
@@ -16,10 +21,12 @@ pub trait Operation: Action + std::fmt::Debug {
         result
     }
 
+    // Porting note: x and y are encoded with the max width and height of all fields,
+    // so that the keys are unique in the fields wider than FIELD_WIDTH or higher than 24 rows
     fn to_unique_key(&self) -> u64 {
-        self.get_piece() as u64 * 4 * 24 * FIELD_WIDTH as u64
-            + self.get_rotate() as u64 * 24 * FIELD_WIDTH as u64
-            + u64::from(self.get_y()) * FIELD_WIDTH as u64
+        let piece_rotate =
+            self.get_piece() as u64 * Rotate::get_size() as u64 + self.get_rotate() as u64;
+        (piece_rotate * MAX_FIELD_HEIGHT as u64 + u64::from(self.get_y())) * MAX_FIELD_WIDTH as u64
             + u64::from(self.get_x())
     }
 }
@@ -1,7 +1,7 @@
 use crate::{extras::hash_code::HashCode, sfinder_core::mino::piece::Piece};
 
 // TODO: derive Copy?
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PieceCounter(u64);
 
 const SLIDE_MASK: [u64; Piece::get_size()] = [
@@ -1,6 +1,8 @@
+pub mod buildup;
 pub mod cover;
 pub mod datastore;
 pub mod generator;
 pub mod parser;
+pub mod pattern;
 pub mod spin_checker;
 pub mod tetfu;
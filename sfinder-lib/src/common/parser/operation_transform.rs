@@ -70,6 +70,7 @@ pub fn to_full_operation_with_key(
 
 // List<Operation>に変換する。正しく組み立てられるかはチェックしない
 // operationWithKeysは組み立てられる順番に並んでいること
+// Porting note: the order can be found with BuildOrderValidator
 // 初めにライン消去を行う
 pub fn parse_to_operations(
     field_origin: &dyn Field,
@@ -156,7 +157,9 @@ pub fn get_max_y(mino_factory: &MinoFactory, operations_list: &[impl Operation])
 mod tests {
     use super::*;
     use crate::{
-        common::parser::operation_with_key_interpreter,
+        common::{
+            datastore::operation_with_key::OperationWithKey, parser::operation_with_key_interpreter,
+        },
         sfinder_core::{mino::piece::Piece, srs::rotate::Rotate},
     };
 
@@ -326,6 +329,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn above_24_rows() {
+        let mino_factory = MinoFactory::new();
+        let height = 30;
+
+        // the second I is placed across the 25th row after it is cleared
+        let field = field_factory::create_extra_large_field_with_marks(
+            String::new() + "XXXXXX____" + &"XXXXXXXXX_".repeat(3) + &"XXXXXXXX_X".repeat(22),
+        );
+        let operations = "I,0,7,25;I,L,9,23"
+            .parse::<Operations<SimpleOperation>>()
+            .unwrap();
+        let operations_with_key =
+            parse_to_operations_with_key(&field, &operations, &mino_factory, height);
+
+        assert_eq!(
+            operations_with_key[1].get_need_deleted_key(),
+            key_operators::get_bit_key(25)
+        );
+        assert_eq!(
+            operations_with_key[1].get_using_key(),
+            key_operators::get_bit_keys(&[22, 23, 24, 26])
+        );
+
+        let expected = field_factory::create_extra_large_field_with_marks(
+            String::new() + "_________X" + &"XXXXXXXXXX".repeat(4) + &"XXXXXXXX_X".repeat(22),
+        );
+        assert_operations(
+            &mino_factory,
+            &field,
+            &operations,
+            &operations_with_key,
+            &expected,
+            height,
+        );
+        assert_eq!(
+            parse_to_operations(&field, &operations_with_key, height),
+            operations
+        );
+    }
+
     #[test]
     fn test_parse_to_operations() {
         let mino_factory = MinoFactory::new();
@@ -50,7 +50,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        common::datastore::operation_with_key::OperationWithKey,
+        common::{buildup::build_up, datastore::operation_with_key::OperationWithKey},
         entry::common::kicks::factory::srs_mino_rotation_factory,
         sfinder_core::{
             action::reachable::reachable_facade,
@@ -61,7 +61,6 @@ mod tests {
     };
 
     #[test]
-    #[ignore = "BuildUp::cansBuild is not implemented"]
     fn parse_to_operation_with_key() {
         #[rustfmt::skip]
         let init_field = field_factory::create_field_with_marks(
@@ -85,7 +84,13 @@ mod tests {
                 mino_rotation.as_ref(),
                 8,
             );
-            todo!("BuildUp::cansBuild");
+            assert!(build_up::cans_build(
+                &mino_factory,
+                init_field.as_ref(),
+                &operation_with_keys,
+                4,
+                reachable.as_mut()
+            ));
         }
 
         let line = full_operation_with_key_to_string(&operation_with_keys);
@@ -0,0 +1,2 @@
+pub mod pattern_element;
+pub mod pattern_generator;
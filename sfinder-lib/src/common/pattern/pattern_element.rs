@@ -0,0 +1,103 @@
+use crate::sfinder_core::mino::piece::Piece;
+
+// Porting note: replaces SingleElement, WildcardElement, BracketElement and their permutation variants,
+// as all of them take an ordered permutation of `pop_count` pieces from a set of pieces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternElement {
+    pieces: Vec<Piece>,
+    pop_count: usize,
+}
+
+impl PatternElement {
+    // Panics if pop_count is larger than the number of pieces, or the pieces are not unique.
+    pub fn new(pieces: Vec<Piece>, pop_count: usize) -> Self {
+        assert!(pop_count <= pieces.len());
+        assert!(pieces
+            .iter()
+            .enumerate()
+            .all(|(index, piece)| !pieces[..index].contains(piece)));
+
+        Self { pieces, pop_count }
+    }
+
+    pub fn get_pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    pub fn get_pop_count(&self) -> usize {
+        self.pop_count
+    }
+
+    // The radix of each popped piece when the permutations are numbered in lexicographic order
+    pub(super) fn radixes(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.pop_count).map(|index| (self.pieces.len() - index) as u64)
+    }
+
+    // Returns None if the number of permutations does not fit in u64
+    pub fn get_permutation_count(&self) -> Option<u64> {
+        self.radixes()
+            .try_fold(1u64, |acc, radix| acc.checked_mul(radix))
+    }
+
+    // Each digit is the index among the pieces that are not popped yet, i.e. the lehmer code.
+    pub(super) fn decode_permutation(&self, digits: &[u64], pieces: &mut Vec<Piece>) {
+        let mut left = self.pieces.clone();
+        for &digit in digits {
+            pieces.push(left.remove(digit as usize));
+        }
+    }
+
+    // Whether the pieces are one of the permutations
+    pub fn contains(&self, pieces: &[Piece]) -> bool {
+        pieces.len() == self.pop_count
+            && pieces.iter().enumerate().all(|(index, piece)| {
+                self.pieces.contains(piece) && !pieces[..index].contains(piece)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_count() {
+        let all = Piece::value_list().to_vec();
+        assert_eq!(
+            PatternElement::new(all.clone(), 1).get_permutation_count(),
+            Some(7)
+        );
+        assert_eq!(
+            PatternElement::new(all.clone(), 3).get_permutation_count(),
+            Some(210)
+        );
+        assert_eq!(
+            PatternElement::new(all, 7).get_permutation_count(),
+            Some(5040)
+        );
+        assert_eq!(
+            PatternElement::new(vec![Piece::S, Piece::Z], 0).get_permutation_count(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn decode_permutation() {
+        let element = PatternElement::new(vec![Piece::S, Piece::Z, Piece::T], 2);
+
+        let mut pieces = Vec::new();
+        element.decode_permutation(&[2, 0], &mut pieces);
+        assert_eq!(pieces, vec![Piece::T, Piece::S]);
+        assert!(element.contains(&pieces));
+    }
+
+    #[test]
+    fn contains() {
+        let element = PatternElement::new(vec![Piece::S, Piece::Z, Piece::T], 2);
+
+        assert!(element.contains(&[Piece::Z, Piece::T]));
+        assert!(!element.contains(&[Piece::Z, Piece::Z]));
+        assert!(!element.contains(&[Piece::Z, Piece::I]));
+        assert!(!element.contains(&[Piece::Z]));
+    }
+}
@@ -0,0 +1,844 @@
+//! Porting note: replaces PatternGenerator, LoadedPatternGenerator and PiecesGenerator.
+//!
+//! Each line of the pattern is a list of elements separated by `,`:
+//! - `T`: the piece
+//! - `*`: any piece
+//! - `[SZT]`: one of the pieces, `[^T]`: any piece except the pieces
+//! - an element followed by `p3` takes 3 distinct pieces in any order, and `!` takes all of them
+//!
+//! `#` starts a comment until the end of the line. Blank lines are ignored.
+
+use super::pattern_element::PatternElement;
+use crate::sfinder_core::mino::piece::Piece;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    ops::Range,
+    str::FromStr,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct PatternError {
+    // zero-indexed
+    line: usize,
+    // zero-indexed, counted in chars
+    column: usize,
+    message: String,
+}
+
+impl PatternError {
+    fn new(line: usize, column: usize, message: String) -> Self {
+        Self {
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message,
+            self.line + 1,
+            self.column + 1
+        )
+    }
+}
+
+struct LineParser {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+}
+
+impl LineParser {
+    fn new(line: usize, str: &str) -> Self {
+        Self {
+            chars: str.chars().collect(),
+            index: 0,
+            line,
+        }
+    }
+
+    fn error(&self, column: usize, message: String) -> PatternError {
+        PatternError::new(self.line, column, message)
+    }
+
+    // Skips whitespace and returns the next char with its column
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self
+            .chars
+            .get(self.index)
+            .is_some_and(|c| c.is_whitespace())
+        {
+            self.index += 1;
+        }
+        self.chars.get(self.index).map(|&c| (self.index, c))
+    }
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        let next = self.peek();
+        self.index += 1;
+        next
+    }
+
+    fn parse_piece(&self, column: usize, c: char) -> Result<Piece, PatternError> {
+        c.encode_utf8(&mut [0; 4])
+            .parse()
+            .map_err(|message| self.error(column, message))
+    }
+
+    // Returns None if the line is blank
+    fn parse(mut self) -> Result<Option<Vec<PatternElement>>, PatternError> {
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+
+        let mut elements = Vec::new();
+        loop {
+            elements.push(self.parse_element()?);
+
+            match self.next() {
+                None => return Ok(Some(elements)),
+                Some((_, ',')) => {}
+                Some((column, c)) => {
+                    return Err(self.error(column, format!("Expected ',' but found '{c}'")))
+                }
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<PatternElement, PatternError> {
+        let pieces = match self.next() {
+            None => return Err(self.error(self.chars.len(), "Expected a piece".to_owned())),
+            Some((_, '*')) => Piece::value_list().to_vec(),
+            Some((column, '[')) => self.parse_bracket(column)?,
+            Some((column, c)) => vec![self.parse_piece(column, c)?],
+        };
+
+        let pop_count = match self.peek() {
+            Some((_, '!')) => {
+                self.index += 1;
+                pieces.len()
+            }
+            Some((column, 'p')) => {
+                self.index += 1;
+                let digits = self.chars[self.index..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>();
+                self.index += digits.len();
+
+                match digits.parse() {
+                    Ok(pop_count) if (1..=pieces.len()).contains(&pop_count) => pop_count,
+                    _ => {
+                        return Err(self.error(
+                            column,
+                            format!(
+                                "Permutation count should be between 1 and {}: p{digits}",
+                                pieces.len()
+                            ),
+                        ))
+                    }
+                }
+            }
+            _ => 1,
+        };
+
+        Ok(PatternElement::new(pieces, pop_count))
+    }
+
+    fn parse_bracket(&mut self, open_column: usize) -> Result<Vec<Piece>, PatternError> {
+        let is_negated = self.peek().is_some_and(|(_, c)| c == '^');
+        if is_negated {
+            self.index += 1;
+        }
+
+        let mut pieces = Vec::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error(open_column, "Bracket is not closed".to_owned())),
+                Some((_, ']')) => break,
+                Some((column, c)) => {
+                    let piece = self.parse_piece(column, c)?;
+                    if pieces.contains(&piece) {
+                        return Err(self.error(column, format!("Duplicate piece in bracket: {c}")));
+                    }
+                    pieces.push(piece);
+                }
+            }
+        }
+
+        if is_negated {
+            pieces = Piece::value_list()
+                .iter()
+                .filter(|piece| !pieces.contains(piece))
+                .copied()
+                .collect();
+        }
+
+        if pieces.is_empty() {
+            return Err(self.error(open_column, "Bracket has no pieces".to_owned()));
+        }
+
+        Ok(pieces)
+    }
+}
+
+struct PatternLine {
+    elements: Vec<PatternElement>,
+    // the radix of each piece in the sequence, the first piece being the most significant
+    radixes: Vec<u64>,
+    count: u64,
+}
+
+impl PatternLine {
+    fn new(elements: Vec<PatternElement>) -> Option<Self> {
+        let radixes = elements
+            .iter()
+            .flat_map(|element| element.radixes())
+            .collect::<Vec<_>>();
+        let count = elements.iter().try_fold(1u64, |acc, element| {
+            acc.checked_mul(element.get_permutation_count()?)
+        })?;
+
+        Some(Self {
+            elements,
+            radixes,
+            count,
+        })
+    }
+
+    fn get_depth(&self) -> usize {
+        self.radixes.len()
+    }
+
+    // Sequences are numbered in lexicographic order of the permutations of each element
+    fn get(&self, mut index: u64) -> Vec<Piece> {
+        let mut digits = vec![0; self.radixes.len()];
+        for (digit, radix) in digits.iter_mut().zip(&self.radixes).rev() {
+            *digit = index % radix;
+            index /= radix;
+        }
+
+        let mut pieces = Vec::with_capacity(digits.len());
+        let mut offset = 0;
+        for element in &self.elements {
+            let pop_count = element.get_pop_count();
+            element.decode_permutation(&digits[offset..offset + pop_count], &mut pieces);
+            offset += pop_count;
+        }
+
+        pieces
+    }
+
+    fn contains(&self, pieces: &[Piece]) -> bool {
+        if pieces.len() != self.get_depth() {
+            return false;
+        }
+
+        let mut offset = 0;
+        self.elements.iter().all(|element| {
+            let pop_count = element.get_pop_count();
+            offset += pop_count;
+            element.contains(&pieces[offset - pop_count..offset])
+        })
+    }
+}
+
+pub struct PatternGenerator {
+    lines: Vec<PatternLine>,
+    depth: usize,
+}
+
+impl PatternGenerator {
+    // The number of pieces in each sequence
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    // Porting note: replaces blocksStream
+    // Sequences that appear in more than one line are only returned once.
+    pub fn iter(&self) -> PatternIterator<'_> {
+        PatternIterator {
+            lines: &self.lines,
+            line: 0,
+            index: 0,
+            remaining: None,
+        }
+    }
+
+    // Same as iter, but only visits the sequences whose index (see get_with_duplicates) is in the range.
+    // Ranges that split 0..count_with_duplicates() split iter() without overlaps.
+    pub fn iter_range(&self, range: Range<u64>) -> PatternIterator<'_> {
+        let (line, index) = self.locate(range.start).unwrap_or((self.lines.len(), 0));
+
+        PatternIterator {
+            lines: &self.lines,
+            line,
+            index,
+            remaining: Some(range.end.saturating_sub(range.start)),
+        }
+    }
+
+    // Returns the line and the index in the line
+    fn locate(&self, mut index: u64) -> Option<(usize, u64)> {
+        for (line_index, line) in self.lines.iter().enumerate() {
+            if index < line.count {
+                return Some((line_index, index));
+            }
+            index -= line.count;
+        }
+        None
+    }
+
+    // The number of sequences of all lines, including the sequences that appear in more than one line.
+    // Returns None if the count does not fit in u64.
+    pub fn count_with_duplicates(&self) -> Option<u64> {
+        self.lines
+            .iter()
+            .try_fold(0u64, |acc, line| acc.checked_add(line.count))
+    }
+
+    // The number of sequences returned by iter().
+    // Returns None if the count does not fit in u64.
+    pub fn count(&self) -> Option<u64> {
+        if let [line] = self.lines.as_slice() {
+            return Some(line.count);
+        }
+
+        Self::count_completions(&self.lines, &[], |state| state.iter().any(Option::is_some))
+    }
+
+    // The number of sequences of the last line that are not in the other lines, starting with the prefix.
+    // Returns None if the count does not fit in u64.
+    fn count_new(lines: &[PatternLine], prefix: &[Piece]) -> Option<u64> {
+        Self::count_completions(lines, prefix, |state| match state.split_last() {
+            Some((last, previous)) => last.is_some() && previous.iter().all(Option::is_none),
+            None => false,
+        })
+    }
+
+    // The number of sequences that start with the prefix and are in the lines chosen by is_counted,
+    // which is given whether the sequence is in each line.
+    // Returns None if the count does not fit in u64.
+    fn count_completions(
+        lines: &[PatternLine],
+        prefix: &[Piece],
+        is_counted: impl Fn(&[Option<u8>]) -> bool,
+    ) -> Option<u64> {
+        // 各行について、現在の要素で既に使われたミノを状態として数え上げる
+        // None は、その行にはもう含まれないことを表す
+        let constraints = lines
+            .iter()
+            .map(|line| {
+                line.elements
+                    .iter()
+                    .flat_map(|element| {
+                        let mask = element
+                            .get_pieces()
+                            .iter()
+                            .fold(0u8, |acc, &piece| acc | 1 << piece as u8);
+                        (0..element.get_pop_count()).map(move |index| (index == 0, mask))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let depth = constraints.first().map_or(0, Vec::len);
+
+        let next_state = |state: &[Option<u8>], position: usize, piece: Piece| {
+            let bit = 1 << piece as u8;
+            state
+                .iter()
+                .zip(&constraints)
+                .map(|(used, constraint)| {
+                    let (is_first, allowed) = constraint[position];
+                    let used = (*used).map(|used| if is_first { 0 } else { used })?;
+                    (allowed & bit != 0 && used & bit == 0).then_some(used | bit)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut state = vec![Some(0u8); lines.len()];
+        for (position, &piece) in prefix.iter().enumerate() {
+            state = next_state(&state, position, piece);
+        }
+
+        let mut states = HashMap::from([(state, 1u64)]);
+        for position in prefix.len()..depth {
+            let mut next_states = HashMap::new();
+
+            for (state, count) in states {
+                for &piece in Piece::value_list() {
+                    let next_state = next_state(&state, position, piece);
+                    if next_state.iter().all(Option::is_none) {
+                        continue;
+                    }
+
+                    let next_count = next_states.entry(next_state).or_insert(0u64);
+                    *next_count = next_count.checked_add(count)?;
+                }
+            }
+
+            states = next_states;
+        }
+
+        states
+            .iter()
+            .filter(|(state, _)| is_counted(state))
+            .try_fold(0u64, |acc, (_, &count)| acc.checked_add(count))
+    }
+
+    // Returns the sequence at the index of iter(), i.e. get(n) == iter().nth(n).
+    // Returns None if the index is out of range, or if the number of sequences does not fit in u64.
+    pub fn get(&self, mut index: u64) -> Option<Vec<Piece>> {
+        for (line_index, line) in self.lines.iter().enumerate() {
+            let lines = &self.lines[..=line_index];
+            let new_count = Self::count_new(lines, &[])?;
+            if new_count <= index {
+                index -= new_count;
+                continue;
+            }
+
+            // 各位置で、行の順番にミノを試して、インデックスを含むミノを選ぶ
+            let mut pieces = Vec::with_capacity(self.depth);
+            for element in &line.elements {
+                let offset = pieces.len();
+                for _ in 0..element.get_pop_count() {
+                    for &piece in element.get_pieces() {
+                        if pieces[offset..].contains(&piece) {
+                            continue;
+                        }
+
+                        pieces.push(piece);
+                        let count = Self::count_new(lines, &pieces)?;
+                        if index < count {
+                            break;
+                        }
+                        index -= count;
+                        pieces.pop();
+                    }
+                }
+            }
+
+            return Some(pieces);
+        }
+
+        None
+    }
+
+    // Returns the sequence at the index, counting the sequences of each line in order,
+    // including the sequences that appear in more than one line.
+    pub fn get_with_duplicates(&self, index: u64) -> Option<Vec<Piece>> {
+        self.locate(index)
+            .map(|(line, index)| self.lines[line].get(index))
+    }
+
+    // Whether the sequence at the index is the first appearance of the sequence
+    fn is_first_appearance(&self, index: u64) -> bool {
+        self.locate(index).is_some_and(|(line, index)| {
+            let pieces = self.lines[line].get(index);
+            !self.lines[..line].iter().any(|line| line.contains(&pieces))
+        })
+    }
+
+    // Returns `size` distinct sequences chosen uniformly at random, in the order of iter().
+    // Returns all sequences if there are no more than `size` of them.
+    // Panics if the number of sequences does not fit in u64.
+    pub fn sample(&self, size: usize, seed: u64) -> Vec<Vec<Piece>> {
+        let mut rngs = StdRng::seed_from_u64(seed);
+
+        let count = self.count().expect("Too many sequences to sample");
+        if count <= size as u64 {
+            return self.iter().collect();
+        }
+
+        if count < 2 * size as u64 {
+            // 選択サンプリング
+            let mut left = count;
+            let mut needed = size as u64;
+            return self
+                .iter()
+                .filter(|_| {
+                    let is_selected = rngs.gen_range(0..left) < needed;
+                    left -= 1;
+                    if is_selected {
+                        needed -= 1;
+                    }
+                    is_selected
+                })
+                .collect();
+        }
+
+        // Each sequence is only accepted at its first appearance, so all sequences are equally likely
+        let total = self
+            .count_with_duplicates()
+            .expect("Too many sequences to sample");
+        let mut indices = BTreeSet::new();
+        while indices.len() < size {
+            let index = rngs.gen_range(0..total);
+            if !indices.contains(&index) && self.is_first_appearance(index) {
+                indices.insert(index);
+            }
+        }
+
+        indices
+            .into_iter()
+            .map(|index| self.get_with_duplicates(index).unwrap())
+            .collect()
+    }
+}
+
+impl FromStr for PatternGenerator {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines: Vec<PatternLine> = Vec::new();
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line = line.split_once('#').map_or(line, |(line, _)| line);
+            let Some(elements) = LineParser::new(line_index, line).parse()? else {
+                continue;
+            };
+
+            let line = PatternLine::new(elements).ok_or(PatternError::new(
+                line_index,
+                0,
+                "Too many sequences in the line".to_owned(),
+            ))?;
+
+            if let Some(first) = lines.first() {
+                if first.get_depth() != line.get_depth() {
+                    return Err(PatternError::new(
+                        line_index,
+                        0,
+                        format!(
+                            "All lines should have the same number of pieces: expected {}, but {}",
+                            first.get_depth(),
+                            line.get_depth()
+                        ),
+                    ));
+                }
+            }
+
+            lines.push(line);
+        }
+
+        let depth = lines
+            .first()
+            .ok_or(PatternError::new(0, 0, "Pattern is empty".to_owned()))?
+            .get_depth();
+
+        Ok(Self { lines, depth })
+    }
+}
+
+pub struct PatternIterator<'a> {
+    lines: &'a [PatternLine],
+    line: usize,
+    index: u64,
+    // The number of indices left to visit, None if unbounded
+    remaining: Option<u64>,
+}
+
+impl Iterator for PatternIterator<'_> {
+    type Item = Vec<Piece>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(line) = self.lines.get(self.line) {
+            if line.count <= self.index {
+                self.line += 1;
+                self.index = 0;
+                continue;
+            }
+
+            if let Some(remaining) = &mut self.remaining {
+                if *remaining == 0 {
+                    return None;
+                }
+                *remaining -= 1;
+            }
+
+            let pieces = line.get(self.index);
+            self.index += 1;
+
+            // 前の行で既に出現している場合はスキップ
+            if !self.lines[..self.line]
+                .iter()
+                .any(|line| line.contains(&pieces))
+            {
+                return Some(pieces);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use std::collections::HashSet;
+
+    fn parse(pattern: &str) -> Vec<Vec<Piece>> {
+        pattern
+            .parse::<PatternGenerator>()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    fn error(pattern: &str) -> (usize, usize) {
+        let error = pattern.parse::<PatternGenerator>().err().unwrap();
+        (error.get_line(), error.get_column())
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(parse("T, I ,O"), vec![pieces("TIO")]);
+    }
+
+    #[test]
+    fn wildcard() {
+        let sequences = parse("*");
+        assert_eq!(sequences.len(), 7);
+        assert_eq!(
+            sequences.concat(),
+            Piece::value_list().to_vec(),
+            "should be in the order of value_list"
+        );
+    }
+
+    #[test]
+    fn permutation() {
+        let generator = "*p3".parse::<PatternGenerator>().unwrap();
+        assert_eq!(generator.get_depth(), 3);
+
+        let sequences = generator.iter().collect::<Vec<_>>();
+        assert_eq!(sequences.len(), 210);
+        assert_eq!(sequences.iter().collect::<HashSet<_>>().len(), 210);
+        assert!(sequences.iter().all(|sequence| {
+            sequence[0] != sequence[1] && sequence[1] != sequence[2] && sequence[0] != sequence[2]
+        }));
+
+        assert_eq!(parse("*!").len(), 5040);
+    }
+
+    #[test]
+    fn bracket() {
+        assert_eq!(
+            parse("[SZT]p2"),
+            ["SZ", "ST", "ZS", "ZT", "TS", "TZ"].map(pieces)
+        );
+        assert_eq!(parse("[IO]!"), ["IO", "OI"].map(pieces));
+        assert_eq!(parse("[^TIOLJ]"), ["S", "Z"].map(pieces));
+    }
+
+    #[test]
+    fn concatenate() {
+        assert_eq!(
+            parse("T,[SZ],[IO]"),
+            ["TSI", "TSO", "TZI", "TZO"].map(pieces)
+        );
+        assert_eq!(parse("*p7,*p2").len(), 5040 * 42);
+    }
+
+    #[test]
+    fn lines_are_deduplicated() {
+        let sequences = parse("T,*\n\n# comment\n*,T # T first");
+
+        assert_eq!(sequences.len(), 13);
+        assert_eq!(sequences.iter().collect::<HashSet<_>>().len(), 13);
+        assert_eq!(
+            sequences
+                .iter()
+                .filter(|sequence| **sequence == pieces("TT"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let generator = "*!,*!,*!".parse::<PatternGenerator>().unwrap();
+        assert_eq!(
+            generator.iter().next(),
+            Some(pieces("TILJSZOTILJSZOTILJSZO"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("T,X"), (0, 2));
+        assert_eq!(error("T\n[SZ"), (1, 0));
+        assert_eq!(error("[SZ]p3"), (0, 4));
+        assert_eq!(error("*p0"), (0, 1));
+        assert_eq!(error("*p"), (0, 1));
+        assert_eq!(error("[SS]"), (0, 2));
+        assert_eq!(error("[^TILJSZO]"), (0, 0));
+        assert_eq!(error("T,"), (0, 2));
+        assert_eq!(error("T I"), (0, 2));
+        assert_eq!(error("T\n*p2"), (1, 0));
+        assert_eq!(error("# only comments"), (0, 0));
+    }
+
+    #[test]
+    fn error_message() {
+        let error = "T,X".parse::<PatternGenerator>().err().unwrap();
+        assert_eq!(error.to_string(), "Invalid piece: X (line 1, column 3)");
+    }
+
+    #[test]
+    fn count() {
+        let generator = "*p7,*p7".parse::<PatternGenerator>().unwrap();
+        assert_eq!(generator.count(), Some(5040 * 5040));
+        assert_eq!(generator.count_with_duplicates(), Some(5040 * 5040));
+
+        let generator = "T,*\n*,T".parse::<PatternGenerator>().unwrap();
+        assert_eq!(generator.count(), Some(13));
+        assert_eq!(generator.count_with_duplicates(), Some(14));
+    }
+
+    #[test]
+    fn count_matches_iter() {
+        for pattern in [
+            "*p2,*\n*,*p2",
+            "[SZ]p2,*p2\n*p3,T\n[^O]p4",
+            "*!\n[TIL]!,[JSZO]!\nT,*p6",
+            "I,*p3\n*p4\n*p4",
+        ] {
+            let generator = pattern.parse::<PatternGenerator>().unwrap();
+            assert_eq!(
+                generator.count(),
+                Some(generator.iter().count() as u64),
+                "{pattern}"
+            );
+        }
+    }
+
+    #[test]
+    fn count_overflow() {
+        let generator = "*!,".repeat(20) + "*!";
+        let generator = generator.parse::<PatternGenerator>();
+        assert!(generator.is_err());
+
+        let generator = ("*!,".repeat(4) + "*!\n").repeat(6);
+        let generator = generator.parse::<PatternGenerator>().unwrap();
+        assert_eq!(generator.count_with_duplicates(), None);
+    }
+
+    #[test]
+    fn get_with_duplicates() {
+        let generator = "[SZ]p2\n*p2".parse::<PatternGenerator>().unwrap();
+
+        assert_eq!(generator.get_with_duplicates(0), Some(pieces("SZ")));
+        assert_eq!(generator.get_with_duplicates(1), Some(pieces("ZS")));
+        assert_eq!(generator.get_with_duplicates(2), Some(pieces("TI")));
+        assert_eq!(generator.get_with_duplicates(43), Some(pieces("OZ")));
+        assert_eq!(generator.get_with_duplicates(44), None);
+
+        let generator = "*p7,*p7".parse::<PatternGenerator>().unwrap();
+        assert_eq!(
+            generator.get_with_duplicates(5040 * 5040 - 1),
+            Some(pieces("OZSJLITOZSJLIT"))
+        );
+    }
+
+    #[test]
+    fn get() {
+        for pattern in [
+            "[SZ]p2\n*p2",
+            "*p2,*\n*,*p2",
+            "[SZ]p2,*p2\n*p3,T\n[^O]p4",
+            "T,*p2\n*p2,T\n*,T,*",
+            "I,*p3\n*p4\n*p4",
+        ] {
+            let generator = pattern.parse::<PatternGenerator>().unwrap();
+            let sequences = generator.iter().collect::<Vec<_>>();
+
+            for (index, sequence) in sequences.iter().enumerate() {
+                assert_eq!(
+                    generator.get(index as u64).as_ref(),
+                    Some(sequence),
+                    "{pattern}"
+                );
+            }
+            assert_eq!(generator.get(sequences.len() as u64), None, "{pattern}");
+        }
+
+        let generator = "*p7,*p7".parse::<PatternGenerator>().unwrap();
+        assert_eq!(
+            generator.get(5040 * 5040 - 1),
+            Some(pieces("OZSJLITOZSJLIT"))
+        );
+    }
+
+    #[test]
+    fn iter_range() {
+        let generator = "T,*p2\n*p2,T\n*,T,*".parse::<PatternGenerator>().unwrap();
+        let total = generator.count_with_duplicates().unwrap();
+
+        let mut sequences = Vec::new();
+        for start in (0..total).step_by(10) {
+            sequences.extend(generator.iter_range(start..total.min(start + 10)));
+        }
+
+        assert_eq!(sequences, generator.iter().collect::<Vec<_>>());
+        assert_eq!(generator.iter_range(total..total + 10).count(), 0);
+    }
+
+    #[test]
+    fn sample() {
+        for pattern in ["*p7,*p7", "*p4\nT,*p3", "[SZT]!"] {
+            let generator = pattern.parse::<PatternGenerator>().unwrap();
+            let count = generator.count().unwrap();
+
+            for size in [0, 2, 4, 10, 100] {
+                let sample = generator.sample(size, 42);
+
+                assert_eq!(sample.len() as u64, count.min(size as u64), "{pattern}");
+                assert_eq!(sample, generator.sample(size, 42));
+                assert_eq!(sample.iter().collect::<HashSet<_>>().len(), sample.len());
+                assert!(sample
+                    .iter()
+                    .all(|pieces| generator.lines.iter().any(|line| line.contains(pieces))));
+            }
+        }
+    }
+
+    #[test]
+    fn sample_is_uniform() {
+        // T first sequences appear in both lines, but should not be chosen more often
+        let generator = "*p2\nT,*".parse::<PatternGenerator>().unwrap();
+        let count = generator.count().unwrap();
+        assert_eq!(count, 43);
+
+        let mut t_first = 0;
+        for seed in 0..1000 {
+            let sample = generator.sample(1, seed);
+            if sample[0][0] == Piece::T {
+                t_first += 1;
+            }
+        }
+
+        // expected 1000 * 7 / 43 = 163
+        assert!((100..230).contains(&t_first), "{t_first}");
+    }
+}
@@ -1,20 +1,51 @@
 use crate::{
-    searcher::spins::{spin::Spin, spin_commons},
+    searcher::spins::{
+        spin::{Spin, TSpinNames, TSpins},
+        spin_commons,
+    },
     sfinder_core::{
         action::{common::can_put_mino_in_field, reachable::reachable::ILockedReachable},
         field::field::Field,
         mino::{mino::Mino, mino_factory::MinoFactory, piece::Piece},
-        srs::{mino_rotation_detail::MinoRotationDetail, rotate_direction::RotateDirection},
+        srs::{
+            mino_rotation_detail::MinoRotationDetail, rotate::Rotate,
+            rotate_direction::RotateDirection,
+        },
     },
 };
 
 use super::datastore::operation::Operation;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpinRule {
+    // Tだけがスピンになる
+    #[default]
+    TSpin,
+    // Tは3つの角、それ以外のミノは回転後に上下左右へ移動できないとき、スピンになる
+    // Porting note: the spins of the other pieces are regarded as Mini without names
+    AllSpin,
+}
+
+impl FromStr for SpinRule {
+    type Err = String;
+
+    // e.g. tspin, allspin
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "tspin" | "t-spin" => Ok(Self::TSpin),
+            "allspin" | "all-spin" => Ok(Self::AllSpin),
+            _ => Err(format!("Unsupported spin rule: {value}")),
+        }
+    }
+}
 
 pub struct SpinChecker<'a> {
     mino_factory: &'a MinoFactory,
     mino_rotation_detail: MinoRotationDetail<'a>,
     locked_reachable: Box<dyn ILockedReachable + 'a>,
     search_rotations: Vec<RotateDirection>,
+    spin_rule: SpinRule,
 }
 
 impl<'a> SpinChecker<'a> {
@@ -23,6 +54,7 @@ impl<'a> SpinChecker<'a> {
         mino_rotation_detail: MinoRotationDetail<'a>,
         locked_reachable: Box<dyn ILockedReachable + 'a>,
         use_180_rotation: bool,
+        spin_rule: SpinRule,
     ) -> Self {
         Self {
             mino_factory,
@@ -34,11 +66,17 @@ impl<'a> SpinChecker<'a> {
                 RotateDirection::values_no_180()
             })
             .to_vec(),
+            spin_rule,
         }
     }
 }
 
 impl SpinChecker<'_> {
+    // Whether the piece can be a spin under the rule
+    pub fn is_spin_target(&self, piece: Piece) -> bool {
+        piece == Piece::T || self.spin_rule == SpinRule::AllSpin
+    }
+
     // TODO: this is only mutable becuase of locked_reachable
     pub fn check(
         &mut self,
@@ -47,41 +85,89 @@ impl SpinChecker<'_> {
         field_height: u8,
         cleared_rows: u8,
     ) -> Option<Spin> {
+        let piece = operation.get_piece();
         let rotate = operation.get_rotate();
         let x = operation.get_x();
         let y = operation.get_y();
 
-        if !spin_commons::can_t_spin(field, x, y) {
+        let is_spin_form = match (piece, self.spin_rule) {
+            (Piece::T, _) => spin_commons::can_t_spin(field, x, y),
+            (_, SpinRule::AllSpin) => {
+                spin_commons::is_immobile(field, self.mino_factory.get(piece, rotate), x, y)
+            }
+            (_, SpinRule::TSpin) => false,
+        };
+        if !is_spin_form {
             return None;
         }
 
-        self.search_rotations
-            // TODO: cloning is only necessary because self is mutable
-            .clone()
+        // Porting note: the other pieces can be rotated into the same blocks with the other rotates
+        let targets = if piece == Piece::T {
+            vec![(rotate, x, y)]
+        } else {
+            self.get_congruent_positions(piece, rotate, x, y)
+        };
+
+        // TODO: cloning is only necessary because self is mutable
+        let search_rotations = self.search_rotations.clone();
+        targets
             .into_iter()
-            .flat_map(|direction| {
-                let before_direction = direction.reverse();
-
-                let before = self
-                    .mino_factory
-                    .get(Piece::T, rotate.apply(before_direction));
-
-                self.get_spins(
-                    field,
-                    operation,
-                    before,
-                    direction,
-                    field_height,
-                    cleared_rows,
-                )
+            .flat_map(|(rotate, x, y)| {
+                search_rotations
+                    .iter()
+                    .flat_map(|&direction| {
+                        let before_direction = direction.reverse();
+
+                        let before = self.mino_factory.get(piece, rotate.apply(before_direction));
+
+                        self.get_spins(field, x, y, before, direction, field_height, cleared_rows)
+                    })
+                    .collect::<Vec<_>>()
             })
             .max()
     }
 
+    // 同じブロックを占める、すべての向きの位置
+    fn get_congruent_positions(
+        &self,
+        piece: Piece,
+        rotate: Rotate,
+        x: u8,
+        y: u8,
+    ) -> Vec<(Rotate, u8, u8)> {
+        let mino = self.mino_factory.get(piece, rotate);
+        let mut blocks = mino
+            .get_positions()
+            .iter()
+            .map(|position| (x as i8 + position.x, y as i8 + position.y))
+            .collect::<Vec<_>>();
+        blocks.sort_unstable();
+
+        Rotate::value_list()
+            .iter()
+            .filter_map(|&other_rotate| {
+                let other = self.mino_factory.get(piece, other_rotate);
+                let other_x = x as i8 + mino.get_min_x() - other.get_min_x();
+                let other_y = y as i8 + mino.get_min_y() - other.get_min_y();
+
+                let mut other_blocks = other
+                    .get_positions()
+                    .iter()
+                    .map(|position| (other_x + position.x, other_y + position.y))
+                    .collect::<Vec<_>>();
+                other_blocks.sort_unstable();
+
+                (blocks == other_blocks).then_some((other_rotate, other_x as u8, other_y as u8))
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn get_spins(
         &mut self,
         field_without_t: &dyn Field,
-        operation: &dyn Operation,
+        to_x: u8,
+        to_y: u8,
         before: &'static Mino,
         direction: RotateDirection,
         max_height: u8,
@@ -92,8 +178,8 @@ impl SpinChecker<'_> {
             .filter_map(|coord| {
                 // TODO: this filtering should be done in can_put_mino_in_field instead to avoid comparing twice
                 Some((
-                    u8::try_from(operation.get_x() as i8 - coord.x).ok()?,
-                    u8::try_from(operation.get_y() as i8 - coord.y).ok()?,
+                    u8::try_from(to_x as i8 - coord.x).ok()?,
+                    u8::try_from(to_y as i8 - coord.y).ok()?,
                 ))
             })
             .filter(|(before_x, before_y)| {
@@ -107,8 +193,8 @@ impl SpinChecker<'_> {
             })
             .filter(|(before_x, before_y, spin_result)| {
                 // 回転後に元の場所に戻る
-                spin_result.x == operation.get_x()
-                    && spin_result.y == operation.get_y()
+                spin_result.x == to_x
+                    && spin_result.y == to_y
                     // 回転前の位置に移動できる
                     && self.locked_reachable.checks(
                         field_without_t,
@@ -119,7 +205,11 @@ impl SpinChecker<'_> {
                     )
             })
             .map(|(_, _, spin_result)| {
-                spin_commons::get_spins(field_without_t, &spin_result, cleared_rows)
+                if before.get_piece() == Piece::T {
+                    spin_commons::get_spins(field_without_t, &spin_result, cleared_rows)
+                } else {
+                    Spin::new(TSpins::Mini, TSpinNames::NoName, cleared_rows)
+                }
             })
             .collect()
     }
@@ -152,8 +242,13 @@ mod tests {
             mino_rotation.as_ref(),
             max_y,
         );
-        let mut spin_checker =
-            SpinChecker::new(&mino_factory, mino_rotation_detail, locked_reachable, false);
+        let mut spin_checker = SpinChecker::new(
+            &mino_factory,
+            mino_rotation_detail,
+            locked_reachable,
+            false,
+            SpinRule::TSpin,
+        );
 
         #[rustfmt::skip]
         let field = field_factory::create_field_with_marks(
@@ -188,8 +283,13 @@ mod tests {
             mino_rotation.as_ref(),
             max_y,
         );
-        let mut spin_checker =
-            SpinChecker::new(&mino_factory, mino_rotation_detail, locked_reachable, false);
+        let mut spin_checker = SpinChecker::new(
+            &mino_factory,
+            mino_rotation_detail,
+            locked_reachable,
+            false,
+            SpinRule::TSpin,
+        );
 
         let field = field_factory::create_field_with_marks(
             String::new()
@@ -208,4 +308,63 @@ mod tests {
         assert_eq!(spin.spin, TSpins::Mini);
         assert_eq!(spin.cleared_rows, ClearedRows::Single);
     }
+
+    #[test]
+    fn all_spin() {
+        let max_y = 8;
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let create = |spin_rule| {
+            SpinChecker::new(
+                &mino_factory,
+                MinoRotationDetail::new(&mino_factory, mino_rotation.as_ref()),
+                reachable_facade::create_90_locked(
+                    &mino_factory,
+                    &mino_shifter,
+                    mino_rotation.as_ref(),
+                    max_y,
+                ),
+                false,
+                spin_rule,
+            )
+        };
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXX___XXXX"
+                + "XXXXX__XXX"
+                + "XXXX__XXXX"
+        );
+
+        // the Z is rotated into the same blocks as the spawn
+        let operation = SimpleOperation::new(Piece::Z, Rotate::Spawn, 5, 1);
+        let spin = create(SpinRule::AllSpin)
+            .check(field.as_ref(), &operation, max_y, 1)
+            .unwrap();
+        assert_eq!(spin.spin, TSpins::Mini);
+        assert_eq!(spin.cleared_rows, ClearedRows::Single);
+
+        assert!(create(SpinRule::TSpin)
+            .check(field.as_ref(), &operation, max_y, 1)
+            .is_none());
+
+        // the O can move up
+        let operation = SimpleOperation::new(Piece::O, Rotate::Spawn, 3, 2);
+        assert!(create(SpinRule::AllSpin)
+            .check(field.as_ref(), &operation, max_y, 0)
+            .is_none());
+
+        assert!(create(SpinRule::AllSpin).is_spin_target(Piece::Z));
+        assert!(!create(SpinRule::TSpin).is_spin_target(Piece::Z));
+        assert!(create(SpinRule::TSpin).is_spin_target(Piece::T));
+    }
+
+    #[test]
+    fn parse_spin_rule() {
+        assert_eq!("tspin".parse(), Ok(SpinRule::TSpin));
+        assert_eq!("all-spin".parse(), Ok(SpinRule::AllSpin));
+        assert!("spin".parse::<SpinRule>().is_err());
+    }
 }
@@ -2,7 +2,7 @@ use crate::sfinder_core::mino::piece::Piece;
 use std::fmt::Display;
 
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColorType {
     Empty = 0,
     I,
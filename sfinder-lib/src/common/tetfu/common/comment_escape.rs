@@ -0,0 +1,97 @@
+//! Porting note: fumen stores comments after passing them through JavaScript's `escape`,
+//! so the same transformation is reimplemented here instead of relying on percent-encoding crates.
+
+const UNESCAPED_SYMBOLS: &[u8] = b"@*_+-./";
+
+// Porting note: replaces the escape call used in Tetfu
+pub fn escape(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+
+    for code in str.encode_utf16() {
+        match u8::try_from(code) {
+            Ok(byte) if byte.is_ascii_alphanumeric() || UNESCAPED_SYMBOLS.contains(&byte) => {
+                escaped.push(byte as char)
+            }
+            Ok(byte) => escaped.push_str(&format!("%{byte:02X}")),
+            Err(_) => escaped.push_str(&format!("%u{code:04X}")),
+        }
+    }
+
+    escaped
+}
+
+// Porting note: replaces the unescape call used in Tetfu
+// Malformed escape sequences are kept as is, similar to unescape in JavaScript.
+pub fn unescape(str: &str) -> String {
+    let chars = str.chars().collect::<Vec<_>>();
+    let mut codes = Vec::with_capacity(chars.len());
+
+    let parse_hex = |digits: &[char]| -> Option<u16> {
+        digits
+            .iter()
+            .try_fold(0, |acc, c| Some(acc * 16 + c.to_digit(16)? as u16))
+    };
+
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] == '%' {
+            if chars.get(index + 1) == Some(&'u') {
+                if let Some(code) = chars.get(index + 2..index + 6).and_then(parse_hex) {
+                    codes.push(code);
+                    index += 6;
+                    continue;
+                }
+            } else if let Some(code) = chars.get(index + 1..index + 3).and_then(parse_hex) {
+                codes.push(code);
+                index += 3;
+                continue;
+            }
+        }
+
+        let mut buf = [0; 2];
+        codes.extend_from_slice(chars[index].encode_utf16(&mut buf));
+        index += 1;
+    }
+
+    String::from_utf16_lossy(&codes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_lib::randoms;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn escape_ascii() {
+        assert_eq!(escape("abcXYZ019"), "abcXYZ019");
+        assert_eq!(escape("@*_+-./"), "@*_+-./");
+        assert_eq!(escape("#Q=[](T)IO"), "%23Q%3D%5B%5D%28T%29IO");
+        assert_eq!(escape("a b"), "a%20b");
+    }
+
+    #[test]
+    fn escape_multibyte() {
+        assert_eq!(escape("テト譜"), "%u30C6%u30C8%u8B5C");
+        assert_eq!(escape("¥"), "%A5");
+    }
+
+    #[test]
+    fn unescape_malformed() {
+        assert_eq!(unescape("100%"), "100%");
+        assert_eq!(unescape("%zz%u12"), "%zz%u12");
+    }
+
+    #[test]
+    fn random() {
+        let mut rngs = thread_rng();
+        for _ in 0..1000 {
+            let size = rngs.gen_range(0..100);
+            let str = (0..size)
+                .map(|_| randoms::gen_char(&mut rngs, size))
+                .collect::<String>();
+
+            assert_eq!(unescape(&escape(&str)), str);
+        }
+    }
+}
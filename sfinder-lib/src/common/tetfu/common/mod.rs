@@ -1 +1,2 @@
 pub mod color_type;
+pub mod comment_escape;
@@ -33,7 +33,7 @@ impl ColoredField for ArrayColoredField {
         self.field[y as usize][x as usize]
     }
 
-    fn put_mino(&mut self, mino: Mino, x: u8, y: u8) {
+    fn put_mino(&mut self, mino: &Mino, x: u8, y: u8) {
         let color: ColorType = mino.get_piece().into();
         for positions in mino.get_positions() {
             self.set_color(
@@ -50,8 +50,7 @@ impl ColoredField for ArrayColoredField {
 
     fn clear_filled_rows(&mut self) {
         let length = self.field.len();
-        self.field
-            .retain(|row| row.iter().any(|color| *color == ColorType::Empty));
+        self.field.retain(|row| row.contains(&ColorType::Empty));
         for _ in 0..length - self.field.len() {
             self.field.push([ColorType::Empty; FIELD_WIDTH as usize]);
         }
@@ -8,7 +8,7 @@ pub trait ColoredField {
     // Porting note: getBlockNumber is dropped in favor of converting yourself
     fn get_color(&self, x: u8, y: u8) -> ColorType;
 
-    fn put_mino(&mut self, mino: Mino, x: u8, y: u8);
+    fn put_mino(&mut self, mino: &Mino, x: u8, y: u8);
 
     // Porting note: putBlockNumber is dropped in favor of converting yourself
     fn set_color(&mut self, x: u8, y: u8, color: ColorType);
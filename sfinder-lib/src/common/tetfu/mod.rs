@@ -1,2 +1,5 @@
 pub mod common;
 pub mod field;
+pub mod quiz;
+pub mod tetfu;
+pub mod tetfu_page;
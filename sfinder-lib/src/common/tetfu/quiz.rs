@@ -0,0 +1,318 @@
+//! Porting note: the quiz syntax of the fumen comment, `#Q=[hold](current)next`.
+//! Advancing the quiz follows the behaviour of the fumen editor.
+
+use super::tetfu_page::TetfuPage;
+use crate::{common::datastore::operation::Operation, sfinder_core::mino::piece::Piece};
+use std::{fmt::Display, str::FromStr};
+
+const QUIZ_PREFIX: &str = "#Q=";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quiz {
+    hold: Option<Piece>,
+    current: Option<Piece>,
+    next: Vec<Piece>,
+    // The normal comment after ';', kept as is
+    comment: String,
+}
+
+pub fn is_quiz(comment: &str) -> bool {
+    comment.starts_with(QUIZ_PREFIX)
+}
+
+fn parse_piece(c: char) -> Result<Piece, String> {
+    c.encode_utf8(&mut [0; 4]).parse()
+}
+
+// Returns the piece inside the brackets, and the rest of the body
+fn parse_bracket<'a>(
+    s: &str,
+    body: &'a str,
+    open: char,
+    close: char,
+) -> Result<(Option<Piece>, &'a str), String> {
+    let (inner, rest) = body
+        .strip_prefix(open)
+        .and_then(|body| body.split_once(close))
+        .ok_or(format!("Quiz should contain {open}{close}: {s}"))?;
+
+    let mut chars = inner.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Ok((None, rest)),
+        (Some(c), None) => Ok((Some(parse_piece(c)?), rest)),
+        _ => Err(format!(
+            "Quiz should have at most 1 piece in {open}{close}: {s}"
+        )),
+    }
+}
+
+impl Quiz {
+    pub fn new(hold: Option<Piece>, current: Option<Piece>, next: Vec<Piece>) -> Self {
+        let mut quiz = Self {
+            hold,
+            current,
+            next,
+            comment: String::new(),
+        };
+        quiz.normalize();
+        quiz
+    }
+
+    // ホールドだけ残っているときは、ホールドを現在のミノとして扱う
+    fn normalize(&mut self) {
+        if self.current.is_none() {
+            self.current = self.hold.take();
+        }
+    }
+
+    pub fn get_hold(&self) -> Option<Piece> {
+        self.hold
+    }
+
+    pub fn get_current(&self) -> Option<Piece> {
+        self.current
+    }
+
+    pub fn get_next(&self) -> &[Piece] {
+        &self.next
+    }
+
+    pub fn get_comment(&self) -> &str {
+        &self.comment
+    }
+
+    // The pieces in the order they can be used: hold, current and then next.
+    pub fn get_pieces(&self) -> Vec<Piece> {
+        self.get_hold()
+            .into_iter()
+            .chain(self.get_current())
+            .chain(self.get_next().iter().copied())
+            .collect()
+    }
+
+    fn poll_next(&mut self) -> Option<Piece> {
+        (!self.next.is_empty()).then(|| self.next.remove(0))
+    }
+
+    // Uses the piece either directly, by swapping with the hold, or by holding the current piece.
+    pub fn advance(&mut self, used: Piece) -> Result<(), String> {
+        if self.current == Some(used) {
+            self.current = self.poll_next();
+        } else if self.hold == Some(used) {
+            self.hold = self.current;
+            self.current = self.poll_next();
+        } else if self.next.first() == Some(&used) && self.hold.is_none() {
+            self.poll_next();
+            self.hold = self.current;
+            self.current = self.poll_next();
+        } else {
+            return Err(format!("Cannot use {used} in quiz: {self}"));
+        }
+
+        self.normalize();
+        Ok(())
+    }
+
+    // Returns the quiz of the next page. The quiz is left as is if the page does not lock a piece,
+    // or if the piece cannot be used, the same as the fumen editor.
+    pub fn advance_with_page(&self, page: &TetfuPage) -> Quiz {
+        let mut quiz = self.clone();
+        if let Some(operation) = page.get_operation().filter(|_| page.is_lock()) {
+            if quiz.advance(operation.get_piece()).is_err() {
+                return self.clone();
+            }
+        }
+        quiz
+    }
+}
+
+impl FromStr for Quiz {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = s
+            .strip_prefix(QUIZ_PREFIX)
+            .ok_or(format!("Quiz should start with {QUIZ_PREFIX}: {s}"))?;
+        let (body, comment) = body.split_once(';').unwrap_or((body, ""));
+
+        let (hold, body) = parse_bracket(s, body, '[', ']')?;
+        let (current, body) = parse_bracket(s, body, '(', ')')?;
+        let next = body.chars().map(parse_piece).collect::<Result<_, _>>()?;
+
+        let mut quiz = Quiz::new(hold, current, next);
+        quiz.comment = comment.to_owned();
+        Ok(quiz)
+    }
+}
+
+impl Display for Quiz {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let to_str =
+            |piece: Option<Piece>| piece.map(|piece| piece.to_string()).unwrap_or_default();
+
+        write!(
+            f,
+            "{QUIZ_PREFIX}[{}]({}){}",
+            to_str(self.hold),
+            to_str(self.current),
+            self.next
+                .iter()
+                .map(|piece| piece.to_string())
+                .collect::<String>()
+        )?;
+
+        if !self.comment.is_empty() {
+            write!(f, ";{}", self.comment)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the quiz shown on the page, advanced by the pieces locked on the previous pages.
+// Returns None if the comment of the page is not a valid quiz, or if there is no such page.
+pub fn get_quiz_at(pages: &[TetfuPage], index: usize) -> Option<Quiz> {
+    let mut quiz: Option<Quiz> = None;
+    let mut prev_comment: Option<&str> = None;
+
+    for (page_index, page) in pages.get(..=index)?.iter().enumerate() {
+        // comments are carried over from the previous page until a new one is set
+        if prev_comment != Some(page.get_comment()) {
+            quiz = page.get_comment().parse().ok();
+            prev_comment = Some(page.get_comment());
+        }
+
+        if page_index < index {
+            quiz = quiz.map(|quiz| quiz.advance_with_page(page));
+        }
+    }
+
+    quiz
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{
+            datastore::simple_operation::SimpleOperation,
+            tetfu::{field::colored_field_factory, tetfu},
+        },
+        sfinder_core::{mino::mino_factory::MinoFactory, srs::rotate::Rotate},
+    };
+
+    fn quiz(s: &str) -> Quiz {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let quiz = quiz("#Q=[I](T)SZO");
+        assert_eq!(quiz.get_hold(), Some(Piece::I));
+        assert_eq!(quiz.get_current(), Some(Piece::T));
+        assert_eq!(quiz.get_next(), &[Piece::S, Piece::Z, Piece::O]);
+        assert_eq!(
+            quiz.get_pieces(),
+            vec![Piece::I, Piece::T, Piece::S, Piece::Z, Piece::O]
+        );
+    }
+
+    #[test]
+    fn parse_empty_hold() {
+        let quiz = quiz("#Q=[](L)JI;comment");
+        assert_eq!(quiz.get_hold(), None);
+        assert_eq!(quiz.get_pieces(), vec![Piece::L, Piece::J, Piece::I]);
+        assert_eq!(quiz.get_comment(), "comment");
+        assert_eq!(quiz.to_string(), "#Q=[](L)JI;comment");
+    }
+
+    #[test]
+    fn parse_only_hold() {
+        // the hold piece becomes the current piece
+        let quiz = quiz("#Q=[T]()");
+        assert_eq!(quiz.get_hold(), None);
+        assert_eq!(quiz.get_current(), Some(Piece::T));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in ["#Q=(T)", "#Q=[](T", "#Q=[IO](T)", "#Q=[](T)X", "(T)SZ"] {
+            assert!(s.parse::<Quiz>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn advance_direct() {
+        let mut quiz = quiz("#Q=[I](T)SZ");
+        quiz.advance(Piece::T).unwrap();
+        assert_eq!(quiz.to_string(), "#Q=[I](S)Z");
+    }
+
+    #[test]
+    fn advance_swap() {
+        let mut quiz = quiz("#Q=[I](T)SZ");
+        quiz.advance(Piece::I).unwrap();
+        assert_eq!(quiz.to_string(), "#Q=[T](S)Z");
+    }
+
+    #[test]
+    fn advance_stock() {
+        let mut quiz = quiz("#Q=[](T)SZ");
+        quiz.advance(Piece::S).unwrap();
+        assert_eq!(quiz.to_string(), "#Q=[T](Z)");
+    }
+
+    #[test]
+    fn advance_until_empty() {
+        let mut quiz = quiz("#Q=[](T)S");
+        quiz.advance(Piece::S).unwrap();
+        quiz.advance(Piece::T).unwrap();
+        assert_eq!(quiz.to_string(), "#Q=[]()");
+        assert!(quiz.get_pieces().is_empty());
+        assert!(quiz.advance(Piece::T).is_err());
+    }
+
+    #[test]
+    fn advance_invalid() {
+        let mut quiz = quiz("#Q=[I](T)SZ");
+        // the next piece cannot be used when the hold is occupied
+        assert!(quiz.advance(Piece::S).is_err());
+        assert_eq!(quiz.to_string(), "#Q=[I](T)SZ");
+    }
+
+    #[test]
+    fn quiz_at_pages() {
+        let mino_factory = MinoFactory::new();
+        let field = colored_field_factory::create_field(tetfu::FIELD_MAX_HEIGHT);
+        let page = |operation, comment: &str| {
+            TetfuPage::new(
+                field.clone(),
+                operation,
+                comment.to_owned(),
+                true,
+                false,
+                false,
+            )
+        };
+        let pages = [
+            page(
+                Some(SimpleOperation::new(Piece::I, Rotate::Spawn, 4, 0)),
+                "#Q=[](T)IO",
+            ),
+            page(
+                Some(SimpleOperation::new(Piece::T, Rotate::Spawn, 1, 1)),
+                "#Q=[](T)IO",
+            ),
+            page(None, "#Q=[](T)IO"),
+        ];
+
+        // round trip through fumen to get the comments as they are decoded
+        let data = tetfu::encode(&mino_factory, &pages).unwrap();
+        let pages = tetfu::decode(&mino_factory, &data).unwrap();
+
+        assert_eq!(get_quiz_at(&pages, 0), Some(quiz("#Q=[](T)IO")));
+        assert_eq!(get_quiz_at(&pages, 1), Some(quiz("#Q=[T](O)")));
+        assert_eq!(get_quiz_at(&pages, 2), Some(quiz("#Q=[](O)")));
+        assert_eq!(get_quiz_at(&pages, 3), None);
+    }
+}
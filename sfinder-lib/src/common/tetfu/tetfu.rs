@@ -0,0 +1,983 @@
+//! Porting note: replaces Tetfu. Only the v115 format is supported.
+
+use super::{
+    common::{color_type::ColorType, comment_escape},
+    field::{
+        array_colored_field::ArrayColoredField, colored_field::ColoredField, colored_field_factory,
+    },
+    tetfu_page::TetfuPage,
+};
+use crate::{
+    common::{
+        datastore::{
+            action::action::Action, mino_operation_with_key::MinoOperationWithKey,
+            operation::Operation, simple_operation::SimpleOperation,
+        },
+        parser::operation_transform,
+    },
+    sfinder_core::{
+        field::{field::Field, field_constants::FIELD_WIDTH},
+        mino::{mino_factory::MinoFactory, piece::Piece},
+        srs::rotate::Rotate,
+    },
+};
+use std::fmt::Display;
+
+const ENCODE_TABLE: &[u8; ENCODE_TABLE_SIZE as usize] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const ENCODE_TABLE_SIZE: u32 = 64;
+const COMMENT_TABLE: &[u8] =
+    b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+const COMMENT_TABLE_SIZE: u32 = COMMENT_TABLE.len() as u32 + 1;
+
+const FIELD_TOP: u8 = 23;
+pub const FIELD_MAX_HEIGHT: u8 = FIELD_TOP + 1;
+const FIELD_BLOCKS: u32 = FIELD_MAX_HEIGHT as u32 * FIELD_WIDTH as u32;
+// The diff of a block is offset by this value so that it is always positive
+const DIFF_OFFSET: u32 = 8;
+
+const PREFIXES: [&str; 3] = ["v115@", "m115@", "d115@"];
+const URL: &str = "https://fumen.zui.jp/?";
+// The length of the escaped comment is stored in 2 values
+const MAX_COMMENT_LENGTH: usize = ENCODE_TABLE_SIZE.pow(2) as usize - 1;
+
+#[derive(Debug, PartialEq)]
+pub enum TetfuError {
+    // The data does not contain a supported version prefix
+    InvalidPrefix,
+    // position is the index of the character in the data after the prefix
+    InvalidCharacter { position: usize, character: char },
+    // The data ended in the middle of a page
+    Truncated { page: usize },
+    // A block in the field is not a valid ColorType after applying the diff
+    InvalidBlock { page: usize },
+    // The coordinate of the piece is outside of the field
+    InvalidPiecePosition { page: usize },
+    InvalidComment { page: usize },
+}
+
+impl Display for TetfuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TetfuError::InvalidPrefix => write!(f, "Fumen data should start with v115@"),
+            TetfuError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "Invalid character in fumen data: position={position}, character={character}"
+            ),
+            TetfuError::Truncated { page } => write!(f, "Fumen data is truncated: page={page}"),
+            TetfuError::InvalidBlock { page } => {
+                write!(f, "Invalid block in fumen field: page={page}")
+            }
+            TetfuError::InvalidPiecePosition { page } => {
+                write!(f, "Piece is placed outside of the fumen field: page={page}")
+            }
+            TetfuError::InvalidComment { page } => write!(f, "Invalid fumen comment: page={page}"),
+        }
+    }
+}
+
+// Porting note: replaces the LinkedList of values used for decoding
+struct ValueReader {
+    values: Vec<u32>,
+    index: usize,
+}
+
+impl ValueReader {
+    fn new(data: &str) -> Result<Self, TetfuError> {
+        let values = data
+            .chars()
+            .enumerate()
+            .map(|(position, character)| {
+                ENCODE_TABLE
+                    .iter()
+                    .position(|&c| c as char == character)
+                    .map(|value| value as u32)
+                    .ok_or(TetfuError::InvalidCharacter {
+                        position,
+                        character,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { values, index: 0 })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.len() <= self.index
+    }
+
+    // Reads `count` values as a little-endian number
+    fn poll(&mut self, count: usize) -> Option<u32> {
+        let values = self.values.get(self.index..self.index + count)?;
+        self.index += count;
+        Some(
+            values
+                .iter()
+                .rev()
+                .fold(0, |acc, value| acc * ENCODE_TABLE_SIZE + value),
+        )
+    }
+}
+
+// Porting note: replaces the List<Integer> of values used for encoding
+struct ValueWriter {
+    values: Vec<u32>,
+}
+
+impl ValueWriter {
+    fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    // Writes `value` as `count` values in little-endian
+    fn push(&mut self, mut value: u32, count: usize) {
+        for _ in 0..count {
+            self.values.push(value % ENCODE_TABLE_SIZE);
+            value /= ENCODE_TABLE_SIZE;
+        }
+    }
+}
+
+impl Display for ValueWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.values
+                .iter()
+                .map(|&value| ENCODE_TABLE[value as usize] as char)
+                .collect::<String>()
+        )
+    }
+}
+
+// The whole state of the fumen field, including the garbage row below the field used by rise.
+struct TetfuField {
+    field: ArrayColoredField,
+    garbage: [ColorType; FIELD_WIDTH as usize],
+}
+
+impl TetfuField {
+    fn new(field: ArrayColoredField) -> Self {
+        Self {
+            field,
+            garbage: [ColorType::Empty; FIELD_WIDTH as usize],
+        }
+    }
+
+    // index 0 is the top left block, the last row is the garbage row
+    fn get(&self, index: u32) -> ColorType {
+        let x = (index % FIELD_WIDTH as u32) as u8;
+        match (FIELD_TOP - 1).checked_sub((index / FIELD_WIDTH as u32) as u8) {
+            Some(y) => self.field.get_color(x, y),
+            None => self.garbage[x as usize],
+        }
+    }
+
+    fn set(&mut self, index: u32, color_type: ColorType) {
+        let x = (index % FIELD_WIDTH as u32) as u8;
+        match (FIELD_TOP - 1).checked_sub((index / FIELD_WIDTH as u32) as u8) {
+            Some(y) => self.field.set_color(x, y, color_type),
+            None => self.garbage[x as usize] = color_type,
+        }
+    }
+
+    fn lock(
+        &mut self,
+        mino_factory: &MinoFactory,
+        operation: Option<&SimpleOperation>,
+        is_block_up: bool,
+        is_mirror: bool,
+    ) {
+        if let Some(operation) = operation {
+            let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+            self.field
+                .put_mino(mino, operation.get_x(), operation.get_y());
+        }
+
+        self.field.clear_filled_rows();
+
+        if is_block_up {
+            self.field.block_up();
+            for (x, color_type) in self.garbage.iter().enumerate() {
+                self.field.set_color(x as u8, 0, *color_type);
+            }
+            self.garbage = [ColorType::Empty; FIELD_WIDTH as usize];
+        }
+
+        if is_mirror {
+            self.field.mirror();
+        }
+    }
+}
+
+// Porting note: replaces the action part of ActionDecoder/ActionEncoder
+struct TetfuAction {
+    color_type: ColorType,
+    rotate: Rotate,
+    coordinate: u32,
+    is_block_up: bool,
+    is_mirror: bool,
+    is_color: bool,
+    is_comment: bool,
+    is_lock: bool,
+}
+
+impl TetfuAction {
+    fn decode(mut value: u32) -> Self {
+        let mut next = |radix: u32| {
+            let digit = value % radix;
+            value /= radix;
+            digit
+        };
+
+        // never fails, as ColorType has more than 8 values
+        let color_type = ColorType::new(next(8) as u8);
+        let rotate = match next(4) {
+            0 => Rotate::Reverse,
+            1 => Rotate::Right,
+            2 => Rotate::Spawn,
+            _ => Rotate::Left,
+        };
+        let coordinate = next(FIELD_BLOCKS);
+        let is_block_up = next(2) == 1;
+        let is_mirror = next(2) == 1;
+        // the color flag only affects how fumen renders the page
+        let is_color = next(2) == 1;
+        let is_comment = next(2) == 1;
+        // the flag is stored inverted
+        let is_lock = next(2) != 1;
+
+        Self {
+            color_type,
+            rotate,
+            coordinate,
+            is_block_up,
+            is_mirror,
+            is_color,
+            is_comment,
+            is_lock,
+        }
+    }
+
+    fn encode(&self) -> u32 {
+        let rotate = match self.rotate {
+            Rotate::Reverse => 0,
+            Rotate::Right => 1,
+            Rotate::Spawn => 2,
+            Rotate::Left => 3,
+        };
+
+        [
+            (self.is_lock as u32 ^ 1, 2),
+            (self.is_comment as u32, 2),
+            (self.is_color as u32, 2),
+            (self.is_mirror as u32, 2),
+            (self.is_block_up as u32, 2),
+            (self.coordinate, FIELD_BLOCKS),
+            (rotate, 4),
+            (self.color_type as u32, 8),
+        ]
+        .into_iter()
+        .fold(0, |acc, (digit, radix)| acc * radix + digit)
+    }
+}
+
+// fumen places O, I, S and Z at a different axis from SRS,
+// as the axis is shared between rotations with the same shape
+fn get_axis_offset(piece: Piece, rotate: Rotate) -> (i8, i8) {
+    match (piece, rotate) {
+        (Piece::O, Rotate::Spawn) => (0, 1),
+        (Piece::O, Rotate::Reverse) => (-1, 0),
+        (Piece::O, Rotate::Left) => (-1, 1),
+        (Piece::I, Rotate::Reverse) => (-1, 0),
+        (Piece::I, Rotate::Left) => (0, 1),
+        (Piece::S, Rotate::Spawn) => (0, 1),
+        (Piece::S, Rotate::Right) => (1, 0),
+        (Piece::Z, Rotate::Spawn) => (0, 1),
+        (Piece::Z, Rotate::Left) => (-1, 0),
+        _ => (0, 0),
+    }
+}
+
+// Returns None if the axis is outside of the fumen field
+fn to_coordinate(operation: &impl Operation) -> Option<u32> {
+    let (dx, dy) = get_axis_offset(operation.get_piece(), operation.get_rotate());
+    let x = operation.get_x() as i8 + dx;
+    let y = operation.get_y() as i8 + dy;
+
+    ((0..FIELD_WIDTH as i8).contains(&x) && (0..FIELD_TOP as i8).contains(&y))
+        .then(|| (FIELD_TOP as i8 - 1 - y) as u32 * FIELD_WIDTH as u32 + x as u32)
+}
+
+fn from_coordinate(
+    mino_factory: &MinoFactory,
+    piece: Piece,
+    rotate: Rotate,
+    coordinate: u32,
+) -> Option<SimpleOperation> {
+    let (dx, dy) = get_axis_offset(piece, rotate);
+    let x = (coordinate % FIELD_WIDTH as u32) as i8 - dx;
+    let y = FIELD_TOP as i8 - 1 - (coordinate / FIELD_WIDTH as u32) as i8 - dy;
+
+    let mino = mino_factory.get(piece, rotate);
+    let is_inside = -mino.get_min_x() <= x
+        && x < FIELD_WIDTH as i8 - mino.get_max_x()
+        && -mino.get_min_y() <= y
+        && y < FIELD_TOP as i8 - mino.get_max_y();
+
+    is_inside.then(|| SimpleOperation::new(piece, rotate, x as u8, y as u8))
+}
+
+// Returns None if any block of the piece is outside of the fumen field
+fn to_valid_coordinate(mino_factory: &MinoFactory, operation: &impl Operation) -> Option<u32> {
+    to_coordinate(operation).filter(|&coordinate| {
+        from_coordinate(
+            mino_factory,
+            operation.get_piece(),
+            operation.get_rotate(),
+            coordinate,
+        )
+        .is_some()
+    })
+}
+
+fn remove_prefix(data: &str) -> Option<&str> {
+    PREFIXES
+        .iter()
+        .find_map(|prefix| data.find(prefix).map(|index| &data[index + prefix.len()..]))
+}
+
+fn decode_comment(values: &mut ValueReader, page: usize) -> Result<String, TetfuError> {
+    let length = values.poll(2).ok_or(TetfuError::Truncated { page })? as usize;
+
+    let mut escaped = String::with_capacity(length.next_multiple_of(4));
+    for _ in 0..length.div_ceil(4) {
+        let mut value = values.poll(5).ok_or(TetfuError::Truncated { page })?;
+        for _ in 0..4 {
+            let c = COMMENT_TABLE
+                .get((value % COMMENT_TABLE_SIZE) as usize)
+                .ok_or(TetfuError::InvalidComment { page })?;
+            escaped.push(*c as char);
+            value /= COMMENT_TABLE_SIZE;
+        }
+    }
+    escaped.truncate(length);
+
+    Ok(comment_escape::unescape(&escaped))
+}
+
+// The escaped comment is cut at the longest prefix that fits in MAX_COMMENT_LENGTH
+fn encode_comment(values: &mut ValueWriter, comment: &str) {
+    let mut escaped = String::with_capacity(comment.len());
+    for c in comment.chars() {
+        let escaped_char = comment_escape::escape(c.encode_utf8(&mut [0; 4]));
+        if MAX_COMMENT_LENGTH < escaped.len() + escaped_char.len() {
+            break;
+        }
+        escaped.push_str(&escaped_char);
+    }
+
+    values.push(escaped.len() as u32, 2);
+    for chunk in escaped.as_bytes().chunks(4) {
+        let value = chunk.iter().rev().fold(0, |acc, c| {
+            // escaped comments only contain printable ascii characters
+            let index = COMMENT_TABLE.iter().position(|t| t == c).unwrap();
+            acc * COMMENT_TABLE_SIZE + index as u32
+        });
+        values.push(value, 5);
+    }
+}
+
+// Porting note: replaces decode
+// Accepts the data with or without the url,
+// e.g. "https://fumen.zui.jp/?v115@vhAAgH" and "v115@vhAAgH" are both valid.
+pub fn decode(mino_factory: &MinoFactory, data: &str) -> Result<Vec<TetfuPage>, TetfuError> {
+    let data = remove_prefix(data)
+        .ok_or(TetfuError::InvalidPrefix)?
+        .replace('?', "");
+    let mut values = ValueReader::new(data.trim_end())?;
+
+    let mut pages = Vec::new();
+    let mut current = TetfuField::new(colored_field_factory::create_field(FIELD_MAX_HEIGHT));
+    let mut prev_comment = String::new();
+    let mut repeat_count: Option<u32> = None;
+
+    while !values.is_empty() {
+        let page = pages.len();
+
+        // フィールドの更新
+        match repeat_count {
+            Some(count) if 0 < count => repeat_count = Some(count - 1),
+            _ => {
+                let mut index = 0;
+                let mut is_change = false;
+                while index < FIELD_BLOCKS {
+                    let diff_block = values.poll(2).ok_or(TetfuError::Truncated { page })?;
+                    let diff = diff_block / FIELD_BLOCKS;
+                    let num_of_blocks = diff_block % FIELD_BLOCKS + 1;
+
+                    if diff != DIFF_OFFSET {
+                        is_change = true;
+                    }
+
+                    if FIELD_BLOCKS < index + num_of_blocks {
+                        return Err(TetfuError::InvalidBlock { page });
+                    }
+
+                    for block_index in index..index + num_of_blocks {
+                        let block = (current.get(block_index) as u32 + diff)
+                            .checked_sub(DIFF_OFFSET)
+                            .ok_or(TetfuError::InvalidBlock { page })?;
+                        let color_type = ColorType::try_from(block as u8)
+                            .map_err(|_| TetfuError::InvalidBlock { page })?;
+                        current.set(block_index, color_type);
+                    }
+
+                    index += num_of_blocks;
+                }
+
+                repeat_count = if is_change {
+                    None
+                } else {
+                    Some(values.poll(1).ok_or(TetfuError::Truncated { page })?)
+                };
+            }
+        }
+
+        // アクションの取得
+        let action = TetfuAction::decode(values.poll(3).ok_or(TetfuError::Truncated { page })?);
+
+        // コメントの更新
+        let comment = if action.is_comment {
+            decode_comment(&mut values, page)?
+        } else {
+            prev_comment.clone()
+        };
+
+        let operation = match Piece::try_from(action.color_type) {
+            Ok(piece) => Some(
+                from_coordinate(mino_factory, piece, action.rotate, action.coordinate)
+                    .ok_or(TetfuError::InvalidPiecePosition { page })?,
+            ),
+            Err(_) => None,
+        };
+
+        let field = current.field.clone();
+
+        if action.is_lock {
+            current.lock(
+                mino_factory,
+                operation.as_ref(),
+                action.is_block_up,
+                action.is_mirror,
+            );
+        }
+
+        prev_comment.clone_from(&comment);
+
+        pages.push(TetfuPage::new(
+            field,
+            operation,
+            comment,
+            action.is_lock,
+            action.is_block_up,
+            action.is_mirror,
+        ));
+    }
+
+    Ok(pages)
+}
+
+// Porting note: replaces encode
+// The field of each page is compared against the field after the previous page is locked,
+// so a page can change the field freely regardless of the previous operation.
+pub fn encode(mino_factory: &MinoFactory, pages: &[TetfuPage]) -> Result<String, TetfuError> {
+    let mut values = ValueWriter::new();
+    let mut prev = TetfuField::new(colored_field_factory::create_field(FIELD_MAX_HEIGHT));
+    let mut prev_comment = "";
+    let mut repeat_index: Option<usize> = None;
+
+    for (page, tetfu_page) in pages.iter().enumerate() {
+        let mut current = TetfuField::new(tetfu_page.get_field().clone());
+        current.garbage = prev.garbage;
+
+        // フィールドの更新
+        let diffs = (0..FIELD_BLOCKS)
+            .map(|index| current.get(index) as u32 + DIFF_OFFSET - prev.get(index) as u32)
+            .collect::<Vec<_>>();
+        let is_change = diffs.iter().any(|&diff| diff != DIFF_OFFSET);
+
+        match repeat_index {
+            Some(index) if !is_change && values.values[index] < ENCODE_TABLE_SIZE - 1 => {
+                values.values[index] += 1;
+            }
+            _ => {
+                for blocks in diffs.chunk_by(|a, b| a == b) {
+                    values.push(blocks[0] * FIELD_BLOCKS + blocks.len() as u32 - 1, 2);
+                }
+
+                repeat_index = (!is_change).then(|| {
+                    values.push(0, 1);
+                    values.len() - 1
+                });
+            }
+        }
+
+        // アクションの更新
+        let operation = tetfu_page.get_operation();
+        let (color_type, rotate, coordinate) = match operation {
+            Some(operation) => (
+                operation.get_piece().into(),
+                operation.get_rotate(),
+                to_valid_coordinate(mino_factory, operation)
+                    .ok_or(TetfuError::InvalidPiecePosition { page })?,
+            ),
+            None => (ColorType::Empty, Rotate::Reverse, 0),
+        };
+
+        let comment = tetfu_page.get_comment();
+        let is_comment = comment != prev_comment;
+
+        let action = TetfuAction {
+            color_type,
+            rotate,
+            coordinate,
+            is_block_up: tetfu_page.is_block_up(),
+            is_mirror: tetfu_page.is_mirror(),
+            is_color: page == 0,
+            is_comment,
+            is_lock: tetfu_page.is_lock(),
+        };
+        values.push(action.encode(), 3);
+
+        // コメントの更新
+        if is_comment {
+            encode_comment(&mut values, comment);
+            prev_comment = comment;
+        }
+
+        if tetfu_page.is_lock() {
+            current.lock(
+                mino_factory,
+                operation,
+                tetfu_page.is_block_up(),
+                tetfu_page.is_mirror(),
+            );
+        }
+
+        prev = current;
+    }
+
+    Ok(format!("{}{values}", PREFIXES[0]))
+}
+
+pub struct TetfuOptions {
+    // The comment of each page. Pages without one carry over the comment of the previous page.
+    pub comments: Vec<String>,
+    pub is_lock: bool,
+    // If false, placed minos turn gray on the following pages, the same as the initial field
+    pub is_colored: bool,
+}
+
+impl Default for TetfuOptions {
+    fn default() -> Self {
+        Self {
+            comments: Vec::new(),
+            is_lock: true,
+            is_colored: true,
+        }
+    }
+}
+
+// Creates a fumen url with one page per operation, placed on the field in order.
+// Filled rows are cleared after each operation,
+// so the operations should be relative to the field after line clears.
+pub fn encode_operations(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[impl Operation],
+    options: &TetfuOptions,
+) -> Result<String, TetfuError> {
+    let mut colored_field = colored_field_factory::create_gray_field(field);
+    colored_field.clear_filled_rows();
+
+    let mut comment = String::new();
+    let mut next_comment = |index: usize| {
+        if let Some(next) = options.comments.get(index) {
+            comment.clone_from(next);
+        }
+        comment.clone()
+    };
+
+    if operations.is_empty() {
+        let page = TetfuPage::new(
+            colored_field,
+            None,
+            next_comment(0),
+            options.is_lock,
+            false,
+            false,
+        );
+        return Ok(format!("{URL}{}", encode(mino_factory, &[page])?));
+    }
+
+    let mut pages = Vec::with_capacity(operations.len());
+    for (index, operation) in operations.iter().enumerate() {
+        if to_valid_coordinate(mino_factory, operation).is_none() {
+            return Err(TetfuError::InvalidPiecePosition { page: index });
+        }
+
+        let simple_operation = SimpleOperation::new(
+            operation.get_piece(),
+            operation.get_rotate(),
+            operation.get_x(),
+            operation.get_y(),
+        );
+        pages.push(TetfuPage::new(
+            colored_field.clone(),
+            Some(simple_operation),
+            next_comment(index),
+            options.is_lock,
+            false,
+            false,
+        ));
+
+        let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+        if options.is_colored {
+            colored_field.put_mino(mino, operation.get_x(), operation.get_y());
+        } else {
+            for position in mino.get_positions() {
+                colored_field.set_color(
+                    (operation.get_x() as i8 + position.x) as u8,
+                    (operation.get_y() as i8 + position.y) as u8,
+                    ColorType::Gray,
+                );
+            }
+        }
+        colored_field.clear_filled_rows();
+    }
+
+    Ok(format!("{URL}{}", encode(mino_factory, &pages)?))
+}
+
+// Porting note: the keys are resolved with parse_to_operations
+pub fn encode_operations_with_key(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[impl MinoOperationWithKey],
+    height: u8,
+    options: &TetfuOptions,
+) -> Result<String, TetfuError> {
+    let operations = operation_transform::parse_to_operations(field, operations, height);
+    encode_operations(
+        mino_factory,
+        field.prune(height).as_ref(),
+        operations.get_operations(),
+        options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{datastore::operations::Operations, tetfu::field::colored_field_view},
+        sfinder_core::field::field_factory,
+    };
+
+    fn assert_field(page: &TetfuPage, marks: &str) {
+        let expected = colored_field_factory::create_colored_field(marks.to_string());
+        assert_eq!(
+            colored_field_view::to_string_with_type(page.get_field()),
+            colored_field_view::to_string_with_type(&expected),
+        );
+    }
+
+    #[test]
+    fn decode_empty() {
+        let mino_factory = MinoFactory::new();
+        let pages = decode(&mino_factory, "v115@vhAAgH").unwrap();
+
+        assert_eq!(pages.len(), 1);
+        let page = &pages[0];
+        assert!(page.get_field().is_empty());
+        assert!(page.get_operation().is_none());
+        assert_eq!(page.get_comment(), "");
+        assert!(page.is_lock());
+        assert!(!page.is_block_up());
+        assert!(!page.is_mirror());
+    }
+
+    #[test]
+    fn decode_with_url() {
+        let mino_factory = MinoFactory::new();
+        let pages = decode(&mino_factory, "https://fumen.zui.jp/?v115@vhAVQJ").unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            pages[0].get_operation(),
+            Some(&SimpleOperation::new(Piece::T, Rotate::Spawn, 4, 0))
+        );
+    }
+
+    #[test]
+    fn decode_field() {
+        let mino_factory = MinoFactory::new();
+        // gray blocks on the bottom left and right corners,
+        // followed by a page with the I piece locked on it
+        let pages = decode(&mino_factory, "v115@RhA8IeA8HeA8JeAgHvhARwB").unwrap();
+
+        assert_eq!(pages.len(), 2);
+
+        #[rustfmt::skip]
+        assert_field(&pages[0], &(String::new()
+            + "X_________"
+            + "X________X"
+        ));
+        assert!(pages[0].get_operation().is_none());
+
+        #[rustfmt::skip]
+        assert_field(&pages[1], &(String::new()
+            + "X_________"
+            + "X________X"
+        ));
+        assert_eq!(
+            pages[1].get_operation(),
+            Some(&SimpleOperation::new(Piece::I, Rotate::Spawn, 4, 0))
+        );
+    }
+
+    #[test]
+    fn decode_lock() {
+        let mino_factory = MinoFactory::new();
+        let pages = decode(&mino_factory, "v115@vhBVQJAAA").unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].get_field().is_empty());
+        #[rustfmt::skip]
+        assert_field(&pages[1], &(String::new()
+            + "____T_____"
+            + "___TTT____"
+        ));
+        assert!(pages[1].get_operation().is_none());
+    }
+
+    #[test]
+    fn decode_comment() {
+        let mino_factory = MinoFactory::new();
+        let pages = decode(&mino_factory, "v115@vhAAgWDABUYCA").unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].get_comment(), "abc");
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        let mino_factory = MinoFactory::new();
+        assert_eq!(
+            decode(&mino_factory, "v110@vhAAgH").err(),
+            Some(TetfuError::InvalidPrefix)
+        );
+    }
+
+    #[test]
+    fn invalid_character() {
+        let mino_factory = MinoFactory::new();
+        assert_eq!(
+            decode(&mino_factory, "v115@vh!AgH").err(),
+            Some(TetfuError::InvalidCharacter {
+                position: 2,
+                character: '!'
+            })
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let mino_factory = MinoFactory::new();
+        assert_eq!(
+            decode(&mino_factory, "v115@vhAAg").err(),
+            Some(TetfuError::Truncated { page: 0 })
+        );
+        assert_eq!(
+            decode(&mino_factory, "v115@vhBVQJA").err(),
+            Some(TetfuError::Truncated { page: 1 })
+        );
+    }
+
+    #[test]
+    fn encode_decoded() {
+        let mino_factory = MinoFactory::new();
+        for data in [
+            "v115@vhAAgH",
+            "v115@vhAVQJ",
+            "v115@RhA8IeA8HeA8JeAgHvhARwB",
+            "v115@vhBVQJAAA",
+            "v115@vhAAgWDABUYCA",
+        ] {
+            let pages = decode(&mino_factory, data).unwrap();
+            assert_eq!(encode(&mino_factory, &pages).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn encode_repeat() {
+        let mino_factory = MinoFactory::new();
+        let page = TetfuPage::new(
+            colored_field_factory::create_field(FIELD_MAX_HEIGHT),
+            None,
+            String::new(),
+            true,
+            false,
+            false,
+        );
+
+        // more pages than a single repeat count can hold
+        let pages = vec![page; 150];
+        let data = encode(&mino_factory, &pages).unwrap();
+        assert_eq!(decode(&mino_factory, &data).unwrap().len(), 150);
+    }
+
+    #[test]
+    fn encode_long_comment() {
+        let mino_factory = MinoFactory::new();
+        let page = TetfuPage::new(
+            colored_field_factory::create_field(FIELD_MAX_HEIGHT),
+            None,
+            "テ".repeat(1000),
+            true,
+            false,
+            false,
+        );
+
+        let data = encode(&mino_factory, &[page]).unwrap();
+        let pages = decode(&mino_factory, &data).unwrap();
+        // each character is escaped to 6 characters
+        assert_eq!(pages[0].get_comment(), "テ".repeat(MAX_COMMENT_LENGTH / 6));
+    }
+
+    #[test]
+    fn encode_operations_colored() {
+        let mino_factory = MinoFactory::new();
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "XXXXXX____"
+        );
+        let operations = [
+            SimpleOperation::new(Piece::I, Rotate::Spawn, 7, 0),
+            SimpleOperation::new(Piece::T, Rotate::Spawn, 1, 0),
+        ];
+        let options = TetfuOptions {
+            comments: vec!["IT".to_owned()],
+            ..Default::default()
+        };
+
+        let url = encode_operations(&mino_factory, field.as_ref(), &operations, &options).unwrap();
+        assert!(url.starts_with(URL));
+
+        let pages = decode(&mino_factory, &url).unwrap();
+        assert_eq!(pages.len(), 2);
+        #[rustfmt::skip]
+        assert_field(&pages[0], &(String::new()
+            + "XXXXXX____"
+        ));
+        assert!(pages[1].get_field().is_empty());
+        for (page, operation) in pages.iter().zip(&operations) {
+            assert_eq!(page.get_operation(), Some(operation));
+            assert_eq!(page.get_comment(), "IT");
+            assert!(page.is_lock());
+        }
+    }
+
+    #[test]
+    fn encode_operations_gray() {
+        let mino_factory = MinoFactory::new();
+        let field = field_factory::create_field(4);
+        let operations = [
+            SimpleOperation::new(Piece::T, Rotate::Spawn, 1, 0),
+            SimpleOperation::new(Piece::O, Rotate::Spawn, 8, 0),
+        ];
+        let options = TetfuOptions {
+            comments: vec!["T".to_owned(), "O".to_owned()],
+            is_lock: false,
+            is_colored: false,
+        };
+
+        let url = encode_operations(&mino_factory, field.as_ref(), &operations, &options).unwrap();
+        let pages = decode(&mino_factory, &url).unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].get_field().is_empty());
+        #[rustfmt::skip]
+        assert_field(&pages[1], &(String::new()
+            + "_X________"
+            + "XXX_______"
+        ));
+        assert_eq!(pages[0].get_comment(), "T");
+        assert_eq!(pages[1].get_comment(), "O");
+        assert!(pages.iter().all(|page| !page.is_lock()));
+    }
+
+    #[test]
+    fn encode_operations_with_key_after_clear() {
+        let mino_factory = MinoFactory::new();
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "XXXXXX____"
+        );
+        let operations = Operations::from_vec(vec![
+            SimpleOperation::new(Piece::I, Rotate::Spawn, 7, 0),
+            SimpleOperation::new(Piece::O, Rotate::Spawn, 0, 0),
+        ]);
+        let keys = operation_transform::parse_to_operations_with_key(
+            field.as_ref(),
+            &operations,
+            &mino_factory,
+            4,
+        );
+
+        let url = encode_operations_with_key(
+            &mino_factory,
+            field.as_ref(),
+            &keys,
+            4,
+            &TetfuOptions::default(),
+        )
+        .unwrap();
+        let pages = decode(&mino_factory, &url).unwrap();
+
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.get_operation().unwrap().clone())
+                .collect::<Vec<_>>(),
+            operations.get_operations()
+        );
+    }
+
+    #[test]
+    fn encode_invalid_position() {
+        let mino_factory = MinoFactory::new();
+        let field = field_factory::create_field(24);
+        let operations = [SimpleOperation::new(Piece::I, Rotate::Left, 0, 22)];
+
+        assert_eq!(
+            encode_operations(
+                &mino_factory,
+                field.as_ref(),
+                &operations,
+                &TetfuOptions::default()
+            )
+            .err(),
+            Some(TetfuError::InvalidPiecePosition { page: 0 })
+        );
+    }
+}
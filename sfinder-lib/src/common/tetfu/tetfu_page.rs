@@ -0,0 +1,62 @@
+use crate::common::{
+    datastore::simple_operation::SimpleOperation,
+    tetfu::field::array_colored_field::ArrayColoredField,
+};
+
+// Porting note: replaces TetfuPage and DecodeTetfuPage
+// The field is the one displayed on the page, i.e. before the operation is locked.
+#[derive(Clone)]
+pub struct TetfuPage {
+    field: ArrayColoredField,
+    operation: Option<SimpleOperation>,
+    comment: String,
+    is_lock: bool,
+    // "rise" in the fumen editor
+    is_block_up: bool,
+    is_mirror: bool,
+}
+
+impl TetfuPage {
+    pub fn new(
+        field: ArrayColoredField,
+        operation: Option<SimpleOperation>,
+        comment: String,
+        is_lock: bool,
+        is_block_up: bool,
+        is_mirror: bool,
+    ) -> Self {
+        Self {
+            field,
+            operation,
+            comment,
+            is_lock,
+            is_block_up,
+            is_mirror,
+        }
+    }
+
+    pub fn get_field(&self) -> &ArrayColoredField {
+        &self.field
+    }
+
+    // Porting note: replaces getColorType, getRotate, getX, getY and isPutMino
+    pub fn get_operation(&self) -> Option<&SimpleOperation> {
+        self.operation.as_ref()
+    }
+
+    pub fn get_comment(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn is_lock(&self) -> bool {
+        self.is_lock
+    }
+
+    pub fn is_block_up(&self) -> bool {
+        self.is_block_up
+    }
+
+    pub fn is_mirror(&self) -> bool {
+        self.is_mirror
+    }
+}
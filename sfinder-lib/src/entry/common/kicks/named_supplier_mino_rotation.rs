@@ -1,8 +1,9 @@
 //! Wrapper to bundle a name of the MinoRotation
 //! Mainly used in command line parsing
 
-use super::factory::srs_mino_rotation_factory;
+use super::factory::{file_mino_rotation_factory, srs_mino_rotation_factory};
 use crate::sfinder_core::srs::mino_rotation::MinoRotation;
+use std::path::Path;
 
 pub struct NamedSupplierMinoRotation {
     name: String,
@@ -13,6 +14,38 @@ impl NamedSupplierMinoRotation {
     pub fn new(name: String, rotation: Box<dyn MinoRotation>) -> Self {
         Self { name, rotation }
     }
+
+    // "srs" or "default" for SRS, "@name" for name.properties in the kicks directory,
+    // or the path to a properties file
+    pub fn from_option(value: &str, kicks_directory: &Path) -> Result<Self, String> {
+        if value.eq_ignore_ascii_case("srs") || value.eq_ignore_ascii_case("default") {
+            return Ok(Self::default());
+        }
+
+        let path = match value.strip_prefix('@') {
+            Some(name) => kicks_directory.join(format!("{name}.properties")),
+            None => Path::new(value).to_path_buf(),
+        };
+        if path
+            .extension()
+            .is_none_or(|extension| extension != "properties")
+            || !path.is_file()
+        {
+            return Err(format!("Kicks file is not found: {}", path.display()));
+        }
+
+        let rotation = file_mino_rotation_factory::create(path)
+            .map_err(|e| format!("Cannot load kicks from {value}: {e}"))?;
+        Ok(Self::new(value.to_string(), rotation))
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_rotation(&self) -> &dyn MinoRotation {
+        self.rotation.as_ref()
+    }
 }
 
 impl Default for NamedSupplierMinoRotation {
@@ -23,3 +56,25 @@ impl Default for NamedSupplierMinoRotation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_option() {
+        let kicks_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("kicks");
+
+        let srs = NamedSupplierMinoRotation::from_option("SRS", &kicks_directory).unwrap();
+        assert_eq!(srs.get_name(), "srs");
+        assert!(srs.get_rotation().no_supports_180());
+
+        let jstris =
+            NamedSupplierMinoRotation::from_option("@jstris180", &kicks_directory).unwrap();
+        assert_eq!(jstris.get_name(), "@jstris180");
+        assert!(!jstris.get_rotation().no_supports_180());
+
+        assert!(NamedSupplierMinoRotation::from_option("@unknown", &kicks_directory).is_err());
+        assert!(NamedSupplierMinoRotation::from_option("unknown.txt", &kicks_directory).is_err());
+    }
+}
@@ -1 +1,4 @@
 pub mod kicks;
+pub mod option_parser;
+pub mod output;
+pub mod settings;
@@ -0,0 +1,140 @@
+//! Porting note: replaces the parsing of commons-cli. Every option takes a value, given as
+//! `--name value`, `--name=value` or `-short value`. --help is handled by the entry point.
+
+use std::collections::HashMap;
+
+pub struct OptionSpec {
+    pub name: &'static str,
+    pub short: &'static str,
+}
+
+impl OptionSpec {
+    pub const fn new(name: &'static str, short: &'static str) -> Self {
+        Self { name, short }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedOptions {
+    // Keyed by the long name
+    values: HashMap<&'static str, String>,
+}
+
+impl ParsedOptions {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    // Returns None if the option is not given
+    pub fn parse<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value of --{name}: {value}"))
+            })
+            .transpose()
+    }
+
+    // Adds the options of the fallback that are not given yet
+    pub fn fill_from(&mut self, fallback: ParsedOptions) {
+        for (name, value) in fallback.values {
+            self.values.entry(name).or_insert(value);
+        }
+    }
+}
+
+pub fn parse(args: &[String], specs: &[&[OptionSpec]]) -> Result<ParsedOptions, String> {
+    let find = |key: &str| {
+        specs
+            .iter()
+            .flat_map(|specs| specs.iter())
+            .find(|spec| match key.strip_prefix("--") {
+                Some(name) => spec.name == name,
+                None => key.strip_prefix('-') == Some(spec.short),
+            })
+    };
+
+    let mut options = ParsedOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (key, value) = match arg.split_once('=') {
+            Some((key, value)) if key.starts_with("--") => (key, Some(value.to_owned())),
+            _ => (arg.as_str(), None),
+        };
+        if !key.starts_with('-') {
+            return Err(format!("Unexpected argument: {arg}"));
+        }
+
+        let spec = find(key).ok_or_else(|| format!("Unknown option: {key}"))?;
+        let value = match value {
+            Some(value) => value,
+            None => args
+                .next()
+                .ok_or_else(|| format!("Option --{} should have a value", spec.name))?
+                .clone(),
+        };
+
+        if options.values.insert(spec.name, value).is_some() {
+            return Err(format!("Option --{} is given more than once", spec.name));
+        }
+    }
+
+    Ok(options)
+}
+
+pub fn format_usage(command: &str, specs: &[&[OptionSpec]]) -> String {
+    let mut usage = format!("Usage: {command} [options]\n");
+    for spec in specs.iter().flat_map(|specs| specs.iter()) {
+        usage += &format!("  -{}, --{} <value>\n", spec.short, spec.name);
+    }
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECS: &[OptionSpec] = &[
+        OptionSpec::new("tetfu", "t"),
+        OptionSpec::new("patterns", "p"),
+        OptionSpec::new("patterns-path", "pp"),
+    ];
+
+    fn args(str: &str) -> Vec<String> {
+        str.split(' ').map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn parse_values() {
+        let options = parse(
+            &args("-t v115@vhAAgH --patterns=*p7 -pp input.txt"),
+            &[SPECS],
+        )
+        .unwrap();
+        assert_eq!(options.get("tetfu"), Some("v115@vhAAgH"));
+        assert_eq!(options.get("patterns"), Some("*p7"));
+        assert_eq!(options.get("patterns-path"), Some("input.txt"));
+
+        let mut options = parse(&args("-t v115@vhAAgH"), &[SPECS]).unwrap();
+        options.fill_from(parse(&args("-t v115@vhAVQJ -p *p4"), &[SPECS]).unwrap());
+        assert_eq!(options.get("tetfu"), Some("v115@vhAAgH"));
+        assert_eq!(options.get("patterns"), Some("*p4"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&args("--unknown 1"), &[SPECS]).is_err());
+        assert!(parse(&args("-t"), &[SPECS]).is_err());
+        assert!(parse(&args("-t a --tetfu b"), &[SPECS]).is_err());
+        assert!(parse(&args("value"), &[SPECS]).is_err());
+
+        let options = parse(&args("-p 7"), &[SPECS]).unwrap();
+        assert_eq!(options.parse::<u8>("patterns"), Ok(Some(7)));
+        assert_eq!(options.parse::<u8>("tetfu"), Ok(None));
+        assert!(parse(&args("-p x"), &[SPECS])
+            .unwrap()
+            .parse::<u8>("patterns")
+            .is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Porting note: replaces the output of the entries. The results are returned as text for stdout,
+//! instead of being written to html and csv files.
+
+use super::settings::Settings;
+use crate::{
+    common::{
+        buildup::build_order::BuildOrderValidator,
+        datastore::full_operation_with_key::FullOperationWithKey,
+        tetfu::tetfu::{self, TetfuOptions},
+    },
+    searcher::percent::percent_core::SuccessCount,
+    sfinder_core::{
+        action::reachable::reachable::Reachable,
+        field::{field::Field, field_view},
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+use std::fmt::Write;
+
+pub fn format_header(settings: &Settings, field: &dyn Field, max_clear_line: u8) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "# Setup Field").unwrap();
+    writeln!(
+        output,
+        "{}",
+        field_view::to_string_with_height(field, max_clear_line)
+    )
+    .unwrap();
+    writeln!(output).unwrap();
+
+    writeln!(output, "# Initialize / User-defined").unwrap();
+    writeln!(output, "Max clear lines: {max_clear_line}").unwrap();
+    writeln!(
+        output,
+        "Using hold: {}",
+        if settings.is_using_hold() {
+            "use"
+        } else {
+            "avoid"
+        }
+    )
+    .unwrap();
+    writeln!(output, "Drop: {}", settings.get_drop_type()).unwrap();
+    writeln!(output, "Kicks: {}", settings.get_kicks().get_name()).unwrap();
+    writeln!(output, "Threads: {}", settings.get_threads()).unwrap();
+    writeln!(output, "Searching patterns:").unwrap();
+    for pattern in settings.get_patterns() {
+        writeln!(output, "  {pattern}").unwrap();
+    }
+    writeln!(output).unwrap();
+
+    writeln!(output, "# Output").unwrap();
+    output
+}
+
+// e.g. 84.23% (4245/5040)
+pub fn format_count(count: &SuccessCount) -> String {
+    format!(
+        "{:.2}% ({}/{})",
+        count.get_success_rate() * 100.0,
+        count.get_success(),
+        count.get_total()
+    )
+}
+
+pub fn format_pieces(pieces: impl IntoIterator<Item = Piece>) -> String {
+    pieces.into_iter().map(|piece| piece.to_string()).collect()
+}
+
+// Returns the operations in the order that they can be built, or as is if there is no such order
+pub fn to_build_order(
+    mino_factory: &MinoFactory,
+    reachable: &mut dyn Reachable,
+    field: &dyn Field,
+    operations: &[FullOperationWithKey],
+    height: u8,
+) -> Vec<FullOperationWithKey> {
+    let order = BuildOrderValidator::new(mino_factory, field, operations, height, reachable)
+        .find_order()
+        .unwrap_or_else(|| (0..operations.len()).collect());
+
+    order
+        .into_iter()
+        .map(|index| operations[index].clone())
+        .collect()
+}
+
+// The fumen with one page for each operation, given in the build order
+pub fn to_fumen(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    operations: &[FullOperationWithKey],
+    height: u8,
+) -> Result<String, String> {
+    tetfu::encode_operations_with_key(
+        mino_factory,
+        field,
+        operations,
+        height,
+        &TetfuOptions::default(),
+    )
+    .map_err(|e| format!("Cannot encode tetfu: {e}"))
+}
@@ -0,0 +1,442 @@
+//! Porting note: replaces the common part of the SettingParser of each entry.
+//! The options in the comment of the selected page are used for the options that are not given on
+//! the command line, the same as the Java version.
+
+use super::{
+    kicks::named_supplier_mino_rotation::NamedSupplierMinoRotation,
+    option_parser::{self, OptionSpec, ParsedOptions},
+};
+use crate::{
+    common::{
+        datastore::{action::action::Action, operation::Operation},
+        pattern::pattern_generator::PatternGenerator,
+        tetfu::{
+            field::{
+                array_colored_field::ArrayColoredField, colored_field::ColoredField,
+                colored_field_factory,
+            },
+            quiz::{self, Quiz},
+            tetfu,
+        },
+    },
+    sfinder_core::{
+        action::drop_type::DropType, field::field_constants::FIELD_WIDTH,
+        mino::mino_factory::MinoFactory,
+    },
+};
+use std::path::Path;
+
+pub const COMMON_OPTIONS: &[OptionSpec] = &[
+    OptionSpec::new("tetfu", "t"),
+    OptionSpec::new("page", "P"),
+    OptionSpec::new("field-path", "fp"),
+    OptionSpec::new("patterns", "p"),
+    OptionSpec::new("patterns-path", "pp"),
+    OptionSpec::new("clear-line", "c"),
+    OptionSpec::new("hold", "H"),
+    OptionSpec::new("drop", "d"),
+    OptionSpec::new("kicks", "K"),
+    OptionSpec::new("threads", "th"),
+];
+
+const DEFAULT_FIELD_PATH: &str = "input/field.txt";
+const DEFAULT_PATTERNS_PATH: &str = "input/patterns.txt";
+const KICKS_DIRECTORY: &str = "kicks";
+
+pub struct Settings {
+    // The fields of the pages from the selected page, with the piece of each page put on it
+    fields: Vec<ArrayColoredField>,
+    max_clear_line: Option<u8>,
+    patterns: Vec<String>,
+    generator: PatternGenerator,
+    is_using_hold: bool,
+    drop_type: DropType,
+    kicks: NamedSupplierMinoRotation,
+    threads: usize,
+}
+
+impl Settings {
+    // Returns the settings with all options, including the options of each entry in specs
+    pub fn load(
+        mino_factory: &MinoFactory,
+        args: &[String],
+        specs: &[OptionSpec],
+    ) -> Result<(Self, ParsedOptions), String> {
+        let all_specs = [COMMON_OPTIONS, specs];
+        let mut options = option_parser::parse(args, &all_specs)?;
+
+        let (fields, fallback, quiz) = match options.get("tetfu") {
+            Some(data) => {
+                let page = options.parse::<usize>("page")?.unwrap_or(1);
+                load_tetfu(mino_factory, data, page, &all_specs)?
+            }
+            None => {
+                let path = options.get("field-path").unwrap_or(DEFAULT_FIELD_PATH);
+                let (fields, options) = load_field_file(Path::new(path))?;
+                (fields, options, None)
+            }
+        };
+        options.fill_from(fallback);
+
+        let max_clear_line = options.parse::<u8>("clear-line")?;
+        if max_clear_line.is_some_and(|line| !(1..=48).contains(&line)) {
+            return Err("Clear line should be 1 <= line <= 48".into());
+        }
+
+        let patterns = match (options.get("patterns"), quiz) {
+            // Porting note: the patterns are separated by ';', the same as the Java version
+            (Some(patterns), _) => patterns.split(';').map(str::to_owned).collect(),
+            // クイズのときは、クイズのミノ列を使う
+            (None, Some(quiz)) => vec![quiz
+                .get_pieces()
+                .iter()
+                .map(|piece| piece.to_string())
+                .collect::<Vec<_>>()
+                .join(",")],
+            (None, None) => {
+                let path = options
+                    .get("patterns-path")
+                    .unwrap_or(DEFAULT_PATTERNS_PATH);
+                std::fs::read_to_string(path)
+                    .map_err(|e| format!("Cannot read patterns from {path}: {e}"))?
+                    .lines()
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            }
+        };
+        let generator = patterns
+            .join("\n")
+            .parse::<PatternGenerator>()
+            .map_err(|e| format!("Invalid patterns: {e}"))?;
+        let patterns = patterns
+            .into_iter()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .collect();
+
+        let is_using_hold = match options.get("hold").unwrap_or("use") {
+            "use" | "true" | "yes" => true,
+            "avoid" | "false" | "no" => false,
+            hold => return Err(format!("Unsupported hold: {hold}")),
+        };
+
+        let drop_type = options
+            .get("drop")
+            .map_or(Ok(DropType::default()), str::parse::<DropType>)?;
+
+        let kicks = NamedSupplierMinoRotation::from_option(
+            options.get("kicks").unwrap_or("srs"),
+            Path::new(KICKS_DIRECTORY),
+        )?;
+        if drop_type.use_180_rotation() && kicks.get_rotation().no_supports_180() {
+            return Err(format!(
+                "Kicks {} do not support 180 rotation",
+                kicks.get_name()
+            ));
+        }
+
+        let threads = options.parse::<usize>("threads")?.unwrap_or(1);
+        if threads == 0 {
+            return Err("Threads should be 1 or more".into());
+        }
+
+        let settings = Self {
+            fields,
+            max_clear_line,
+            patterns,
+            generator,
+            is_using_hold,
+            drop_type,
+            kicks,
+            threads,
+        };
+        Ok((settings, options))
+    }
+
+    pub fn get_field(&self) -> &ArrayColoredField {
+        &self.fields[0]
+    }
+
+    // The fields of the selected page and the pages after it
+    pub fn get_fields(&self) -> &[ArrayColoredField] {
+        &self.fields
+    }
+
+    pub fn get_max_clear_line(&self, default: u8) -> u8 {
+        self.max_clear_line.unwrap_or(default)
+    }
+
+    // Same as get_max_clear_line, for the subcommands that support up to max_line lines
+    pub fn get_max_clear_line_up_to(&self, default: u8, max_line: u8) -> Result<u8, String> {
+        debug_assert!(default <= max_line);
+        match self.max_clear_line {
+            Some(line) if max_line < line => {
+                Err(format!("Clear line should be 1 <= line <= {max_line}"))
+            }
+            line => Ok(line.unwrap_or(default)),
+        }
+    }
+
+    pub fn get_patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn get_generator(&self) -> &PatternGenerator {
+        &self.generator
+    }
+
+    pub fn is_using_hold(&self) -> bool {
+        self.is_using_hold
+    }
+
+    pub fn get_drop_type(&self) -> DropType {
+        self.drop_type
+    }
+
+    pub fn get_kicks(&self) -> &NamedSupplierMinoRotation {
+        &self.kicks
+    }
+
+    // Porting note: only the percent, cover and ren entries split the search between the threads
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
+    // For the subcommands whose search runs on a single thread
+    pub fn check_single_thread(&self) -> Result<(), String> {
+        if 1 < self.threads {
+            return Err("Threads should be 1, since the search runs on a single thread".into());
+        }
+        Ok(())
+    }
+}
+
+// Returns the fields from the page, with the options in the comment of the page.
+// The quiz is also returned if the comment of the page is a quiz, whose options follow the quiz.
+fn load_tetfu(
+    mino_factory: &MinoFactory,
+    data: &str,
+    page: usize,
+    specs: &[&[OptionSpec]],
+) -> Result<(Vec<ArrayColoredField>, ParsedOptions, Option<Quiz>), String> {
+    let pages = tetfu::decode(mino_factory, data).map_err(|e| format!("Invalid tetfu: {e}"))?;
+    if !(1..=pages.len()).contains(&page) {
+        return Err(format!(
+            "Tetfu has only {} pages, but page {page} is selected",
+            pages.len()
+        ));
+    }
+
+    let fields = pages[page - 1..]
+        .iter()
+        .map(|page| {
+            let mut field = page.get_field().clone();
+            if let Some(operation) = page.get_operation() {
+                let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+                field.put_mino(mino, operation.get_x(), operation.get_y());
+            }
+            field
+        })
+        .collect();
+
+    // The field has the piece of the page put on it, so the quiz is advanced with the page
+    let quiz =
+        quiz::get_quiz_at(&pages, page - 1).map(|quiz| quiz.advance_with_page(&pages[page - 1]));
+    if quiz.is_none() && quiz::is_quiz(pages[page - 1].get_comment()) {
+        return Err(format!("Invalid quiz: {}", pages[page - 1].get_comment()));
+    }
+
+    // コメントがオプションとして解釈できないときは無視する
+    let comment = match &quiz {
+        Some(quiz) => quiz.get_comment(),
+        None => pages[page - 1].get_comment(),
+    };
+    let comment = comment
+        .split_whitespace()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let options = option_parser::parse(&comment, specs).unwrap_or_default();
+
+    Ok((fields, options, quiz))
+}
+
+// The first line can be the clear line, followed by the rows of the field from the top
+fn load_field_file(path: &Path) -> Result<(Vec<ArrayColoredField>, ParsedOptions), String> {
+    let str = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read field from {}: {e}", path.display()))?;
+
+    let mut lines = str.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut options = ParsedOptions::default();
+    let mut rows = Vec::new();
+    if let Some(first) = lines.next() {
+        if first.parse::<u8>().is_ok() {
+            options = option_parser::parse(
+                &["--clear-line".to_owned(), first.to_owned()],
+                &[COMMON_OPTIONS],
+            )?;
+        } else {
+            rows.push(first);
+        }
+    }
+    rows.extend(lines);
+
+    if let Some(row) = rows
+        .iter()
+        .find(|row| row.len() != FIELD_WIDTH as usize || !row.is_ascii())
+    {
+        return Err(format!("Invalid row of field: {row}"));
+    }
+    if rows.len() > 24 {
+        return Err("Field should have 24 rows or less".into());
+    }
+
+    Ok((
+        vec![colored_field_factory::create_colored_field(rows.concat())],
+        options,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{
+            datastore::simple_operation::SimpleOperation,
+            tetfu::{common::color_type::ColorType, tetfu_page::TetfuPage},
+        },
+        extras::test_functions::to_args,
+        sfinder_core::{mino::piece::Piece, srs::rotate::Rotate},
+    };
+
+    #[test]
+    fn load_with_comment() {
+        let mino_factory = MinoFactory::new();
+        let field = colored_field_factory::create_colored_field("XXXX______".to_owned());
+        let page = TetfuPage::new(
+            field,
+            Some(SimpleOperation::new(Piece::I, Rotate::Spawn, 5, 0)),
+            "-p *p4 --hold avoid".to_owned(),
+            true,
+            false,
+            false,
+        );
+        let data = tetfu::encode(&mino_factory, &[page]).unwrap();
+
+        let (settings, _) = Settings::load(
+            &mino_factory,
+            &to_args(&format!("-t {data} -H yes -c 2")),
+            &[],
+        )
+        .unwrap();
+
+        let field = settings.get_field();
+        assert_eq!(field.get_color(0, 0), ColorType::Gray);
+        assert_eq!(field.get_color(4, 0), ColorType::I);
+        assert_eq!(field.get_color(7, 0), ColorType::I);
+        assert_eq!(field.get_color(8, 0), ColorType::Empty);
+
+        assert_eq!(settings.get_patterns(), ["*p4"]);
+        assert_eq!(settings.get_generator().count(), Some(840));
+        assert!(settings.is_using_hold());
+        assert_eq!(settings.get_max_clear_line(4), 2);
+        assert_eq!(settings.get_drop_type(), DropType::Softdrop);
+        assert_eq!(settings.get_kicks().get_name(), "srs");
+    }
+
+    #[test]
+    fn load_quiz() {
+        let mino_factory = MinoFactory::new();
+        let field = colored_field_factory::create_colored_field("XXXX______".to_owned());
+        let page = TetfuPage::new(
+            field,
+            Some(SimpleOperation::new(Piece::I, Rotate::Spawn, 5, 0)),
+            "#Q=[](I)TO;-c 2 -H avoid".to_owned(),
+            true,
+            false,
+            false,
+        );
+        let data = tetfu::encode(&mino_factory, &[page]).unwrap();
+
+        // the I of the page is already put on the field
+        let (settings, _) =
+            Settings::load(&mino_factory, &to_args(&format!("-t {data}")), &[]).unwrap();
+        assert_eq!(settings.get_patterns(), ["T,O"]);
+        assert_eq!(settings.get_generator().count(), Some(1));
+        assert_eq!(settings.get_max_clear_line(4), 2);
+        assert!(!settings.is_using_hold());
+
+        let (settings, _) =
+            Settings::load(&mino_factory, &to_args(&format!("-t {data} -p *p2")), &[]).unwrap();
+        assert_eq!(settings.get_patterns(), ["*p2"]);
+
+        let page = TetfuPage::new(
+            colored_field_factory::create_colored_field("XXXX______".to_owned()),
+            None,
+            "#Q=[](I)TX".to_owned(),
+            true,
+            false,
+            false,
+        );
+        let data = tetfu::encode(&mino_factory, &[page]).unwrap();
+        assert!(Settings::load(&mino_factory, &to_args(&format!("-t {data}")), &[]).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let mino_factory = MinoFactory::new();
+        let load = |str: &str| Settings::load(&mino_factory, &to_args(str), &[]).err();
+
+        assert!(load("-t v115@vhAAgH -p *p7 -P 2").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -H sometimes").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -d tspin").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -d tspin4").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -c 0").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -th 0").is_some());
+        assert!(load("-t v115@vhAAgH -p X").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7 -K unknown").is_some());
+        assert!(load("-t v115@vhAAgH -p *p7;*p6").is_some());
+        // SRSは180度回転に対応していない
+        assert!(load("-t v115@vhAAgH -p *p7 -d 180").is_some());
+        assert_eq!(load("-t v115@vhAAgH -p *p7;T,*p6 -d harddrop"), None);
+        assert_eq!(load("-t v115@vhAAgH -p *p7 -d tspin2"), None);
+    }
+
+    #[test]
+    fn clear_line_up_to() {
+        let mino_factory = MinoFactory::new();
+        let load = |str: &str| Settings::load(&mino_factory, &to_args(str), &[]).unwrap().0;
+
+        let settings = load("-t v115@vhAAgH -p *p7 -c 24");
+        assert_eq!(settings.get_max_clear_line(4), 24);
+        assert!(settings.get_max_clear_line_up_to(4, 12).is_err());
+
+        let settings = load("-t v115@vhAAgH -p *p7 -c 12");
+        assert_eq!(settings.get_max_clear_line_up_to(4, 12), Ok(12));
+
+        let settings = load("-t v115@vhAAgH -p *p7");
+        assert_eq!(settings.get_max_clear_line_up_to(4, 12), Ok(4));
+    }
+
+    #[test]
+    fn clear_line_above_24() {
+        let mino_factory = MinoFactory::new();
+        let load = |str: &str| Settings::load(&mino_factory, &to_args(str), &[]);
+
+        let (settings, _) = load("-t v115@vhAAgH -p *p7 -c 48").unwrap();
+        assert_eq!(settings.get_max_clear_line(4), 48);
+        assert!(load("-t v115@vhAAgH -p *p7 -c 49").is_err());
+    }
+
+    #[test]
+    fn single_thread() {
+        let mino_factory = MinoFactory::new();
+        let load = |str: &str| Settings::load(&mino_factory, &to_args(str), &[]).unwrap().0;
+
+        assert!(load("-t v115@vhAAgH -p *p7").check_single_thread().is_ok());
+        assert!(load("-t v115@vhAAgH -p *p7 -th 1")
+            .check_single_thread()
+            .is_ok());
+        assert!(load("-t v115@vhAAgH -p *p7 -th 2")
+            .check_single_thread()
+            .is_err());
+    }
+}
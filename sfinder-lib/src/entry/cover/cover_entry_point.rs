@@ -0,0 +1,365 @@
+//! Porting note: replaces CoverEntryPoint and CoverSettingParser.
+//! The gray blocks of the selected page are the field, and the colored blocks of each page from
+//! the selected page are a setup. The pieces of a setup are recognized from the blocks of each
+//! color, so the blocks of a piece should not be split by cleared rows.
+//! The sequences are distributed to the threads, each with its own cover and reachable.
+
+use crate::{
+    common::{
+        cover::condition_cover::{ConditionCover, CoverCondition},
+        datastore::full_operation_with_key::FullOperationWithKey,
+        spin_checker::SpinRule,
+        tetfu::{common::color_type::ColorType, field::colored_field::ColoredField},
+    },
+    entry::common::{option_parser::OptionSpec, output, settings::Settings},
+    searcher::{
+        common::parallel_executor::ParallelExecutor,
+        cover::cover_core::{self, CoverCore},
+        percent::percent_core::SuccessCount,
+    },
+    sfinder_core::{
+        field::{field_constants::FIELD_WIDTH, field_factory},
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::rotate::Rotate,
+    },
+};
+use std::{collections::BTreeSet, fmt::Write};
+
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec::new("mode", "M"),
+    OptionSpec::new("last-softdrop", "l"),
+    OptionSpec::new("spin-rule", "sr"),
+];
+
+const MIN_MAX_CLEAR_LINE: u8 = 4;
+
+fn parse_mode(value: &str) -> Result<CoverCondition, String> {
+    let t_spin = |min_cleared_rows, regular_only| CoverCondition::TSpin {
+        min_cleared_rows,
+        regular_only,
+    };
+
+    match value {
+        "normal" => Ok(CoverCondition::Normal),
+        "tsm" => Ok(t_spin(1, false)),
+        "tss" => Ok(t_spin(1, true)),
+        "tsd" => Ok(t_spin(2, true)),
+        "tst" => Ok(t_spin(3, true)),
+        "b2b" => Ok(CoverCondition::B2BContinuous),
+        "tetris" => Ok(CoverCondition::Tetris),
+        "1L" | "2L" | "3L" | "4L" => Ok(CoverCondition::ClearedRows {
+            min_cleared_rows: value[..1].parse().unwrap(),
+        }),
+        _ => Err(format!("Unsupported mode: {value}")),
+    }
+}
+
+// The pieces that cover the colored blocks, or None if the blocks cannot be split into pieces
+fn recognize_operations(
+    mino_factory: &MinoFactory,
+    colored_field: &dyn ColoredField,
+    height: u8,
+) -> Option<Vec<FullOperationWithKey>> {
+    // 下の行から順に探索するため、(y, x) で並べる
+    let mut blocks = BTreeSet::new();
+    for y in 0..height.min(colored_field.get_max_height() as u8) {
+        for x in 0..FIELD_WIDTH {
+            let color = colored_field.get_color(x, y);
+            if color.is_mino_block() {
+                blocks.insert((y, x, Piece::try_from(color).unwrap()));
+            }
+        }
+    }
+
+    let mut operations = Vec::new();
+    recognize(mino_factory, &mut blocks, height, &mut operations).then_some(operations)
+}
+
+fn recognize(
+    mino_factory: &MinoFactory,
+    blocks: &mut BTreeSet<(u8, u8, Piece)>,
+    height: u8,
+    operations: &mut Vec<FullOperationWithKey>,
+) -> bool {
+    let Some(&(y, x, piece)) = blocks.first() else {
+        return true;
+    };
+
+    for &rotate in Rotate::value_list() {
+        let mino = mino_factory.get(piece, rotate);
+        // 最も下の行で、最も左にあるブロックを合わせる
+        let positions = mino.get_positions();
+        let lowest = positions
+            .iter()
+            .min_by_key(|position| (position.y, position.x))
+            .unwrap();
+        let (Ok(mino_x), Ok(mino_y)) = (
+            u8::try_from(x as i8 - lowest.x),
+            u8::try_from(y as i8 - lowest.y),
+        ) else {
+            continue;
+        };
+
+        let mino_blocks = positions
+            .iter()
+            .map(|position| {
+                (
+                    (mino_y as i8 + position.y) as u8,
+                    (mino_x as i8 + position.x) as u8,
+                    piece,
+                )
+            })
+            .collect::<Vec<_>>();
+        if !mino_blocks.iter().all(|block| blocks.contains(block)) {
+            continue;
+        }
+
+        for block in &mino_blocks {
+            blocks.remove(block);
+        }
+        operations.push(FullOperationWithKey::create(
+            mino, mino_x, mino_y, 0, height,
+        ));
+
+        if recognize(mino_factory, blocks, height, operations) {
+            return true;
+        }
+
+        operations.pop();
+        blocks.extend(mino_blocks);
+    }
+
+    false
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, options) = Settings::load(&mino_factory, args, OPTIONS)?;
+    let mino_rotation = settings.get_kicks().get_rotation();
+
+    let condition = parse_mode(options.get("mode").unwrap_or("normal"))?;
+    let last_softdrop = options.parse::<u8>("last-softdrop")?;
+    let spin_rule = options.parse::<SpinRule>("spin-rule")?.unwrap_or_default();
+
+    // 指定がなければ、すべてのページのブロックが収まる高さ
+    let max_clear_line = settings.get_max_clear_line(
+        settings
+            .get_fields()
+            .iter()
+            .map(|field| field.get_max_y())
+            .fold(MIN_MAX_CLEAR_LINE, u8::max),
+    );
+
+    let mut gray_field = settings.get_field().clone();
+    for y in 0..max_clear_line.min(gray_field.get_max_height() as u8) {
+        for x in 0..FIELD_WIDTH {
+            if gray_field.get_color(x, y) != ColorType::Gray {
+                gray_field.set_color(x, y, ColorType::Empty);
+            }
+        }
+    }
+    let field = field_factory::from_colored_field(&gray_field, max_clear_line);
+
+    let setups = settings
+        .get_fields()
+        .iter()
+        .enumerate()
+        .map(|(index, colored_field)| {
+            recognize_operations(&mino_factory, colored_field, max_clear_line)
+                .filter(|operations| !operations.is_empty())
+                .ok_or_else(|| format!("Cannot recognize the pieces of the setup {}", index + 1))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let create_core = || {
+        CoverCore::new(
+            vec![Box::new(ConditionCover::new(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation,
+                max_clear_line,
+                settings.get_drop_type().use_180_rotation(),
+                condition,
+                spin_rule,
+            ))],
+            cover_core::create_reachable(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation,
+                max_clear_line,
+                settings.get_drop_type(),
+                last_softdrop,
+            ),
+            max_clear_line,
+            settings.is_using_hold(),
+        )
+    };
+    let mut cover_core = create_core();
+    let sequences = settings.get_generator().iter();
+    let result = match settings.get_threads() {
+        1 => cover_core.run(field.as_ref(), &setups, sequences),
+        threads => cover_core.run_parallel(
+            field.as_ref(),
+            &setups,
+            sequences,
+            &ParallelExecutor::new(threads),
+            create_core,
+        ),
+    }
+    .pop()
+    .unwrap();
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    let mut reachable = settings.get_drop_type().create_reachable(
+        &mino_factory,
+        &mino_shifter,
+        mino_rotation,
+        max_clear_line,
+    );
+    for (index, operations) in setups.iter().enumerate() {
+        let operations = output::to_build_order(
+            &mino_factory,
+            reachable.as_mut(),
+            field.as_ref(),
+            operations,
+            max_clear_line,
+        );
+        let fumen = output::to_fumen(&mino_factory, field.as_ref(), &operations, max_clear_line)?;
+        writeln!(
+            output,
+            "{} {fumen}",
+            output::format_count(&result.get_count(index))
+        )
+        .unwrap();
+    }
+    writeln!(output).unwrap();
+
+    let total = result.get_sequences().len();
+    let and_count = (0..total)
+        .filter(|&sequence| (0..setups.len()).all(|setup| result.is_covered(setup, sequence)))
+        .count();
+    writeln!(
+        output,
+        "OR  = {}",
+        output::format_count(&result.get_union_count())
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "AND = {}",
+        output::format_count(&SuccessCount::new(and_count, total))
+    )
+    .unwrap();
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{
+            datastore::operation::Operation,
+            tetfu::{field::colored_field_factory, tetfu, tetfu_page::TetfuPage},
+        },
+        extras::test_functions::to_args,
+    };
+
+    fn to_tetfu_data(pages: &[&str]) -> String {
+        let pages = pages
+            .iter()
+            .map(|marks| {
+                TetfuPage::new(
+                    colored_field_factory::create_colored_field(marks.to_string()),
+                    None,
+                    String::new(),
+                    true,
+                    false,
+                    false,
+                )
+            })
+            .collect::<Vec<_>>();
+        tetfu::encode(&MinoFactory::new(), &pages).unwrap()
+    }
+
+    #[test]
+    fn recognize_pieces() {
+        let mino_factory = MinoFactory::new();
+        #[rustfmt::skip]
+        let colored_field = colored_field_factory::create_colored_field(String::new()
+            + "LLLTTT____"
+            + "LOOXTZZ___"
+            + "XOOXXXZZ__"
+        );
+
+        let operations = recognize_operations(&mino_factory, &colored_field, 4).unwrap();
+        let mut pieces = operations
+            .iter()
+            .map(|operation| operation.get_piece())
+            .collect::<Vec<_>>();
+        pieces.sort();
+        assert_eq!(pieces, [Piece::T, Piece::L, Piece::Z, Piece::O]);
+
+        let colored_field = colored_field_factory::create_colored_field("TTT_______".to_owned());
+        assert!(recognize_operations(&mino_factory, &colored_field, 4).is_none());
+    }
+
+    #[test]
+    fn cover() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&[
+            &(String::new()
+                + "XXXXXXIIII"
+                + "XXXXXXIIII"),
+            &(String::new()
+                + "XXXXXXOOOO"
+                + "XXXXXXOOOO"),
+        ]);
+        let output = run(&to_args(&format!("-t {data} -c 2 -p I,I;O,O;I,O -H avoid"))).unwrap();
+
+        assert!(output.contains("\n33.33% (1/3) https://fumen.zui.jp/?v115@"));
+        assert!(output.contains("OR  = 66.67% (2/3)\n"));
+        assert!(output.contains("AND = 0.00% (0/3)\n"));
+
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p I,I -M unknown"))).is_err());
+    }
+
+    #[test]
+    fn threads() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&[
+            &(String::new()
+                + "XXXXXXIIII"
+                + "XXXXXXIIII"),
+            &(String::new()
+                + "XXXXXXOOOO"
+                + "XXXXXXOOOO"),
+        ]);
+        let args = format!("-t {data} -c 2 -p [IO]p2 -H use");
+
+        let expected = run(&to_args(&args)).unwrap();
+        let actual = run(&to_args(&format!("{args} -th 3"))).unwrap();
+        assert!(actual.contains("Threads: 3\n"));
+        assert_eq!(actual.replace("Threads: 3\n", "Threads: 1\n"), expected);
+    }
+
+    #[test]
+    fn spin_rule() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&[
+            &(String::new()
+                + "XXX_ZZXXXX"
+                + "XXXXXZZXXX"
+                + "XXXX__XXXX"),
+        ]);
+
+        // the Z spin keeps the back-to-back only when all pieces can spin
+        let output = run(&to_args(&format!("-t {data} -c 6 -p Z -M b2b"))).unwrap();
+        assert!(output.contains("\n0.00% (0/1) https://fumen.zui.jp/?v115@"));
+        let output = run(&to_args(&format!("-t {data} -c 6 -p Z -M b2b -sr allspin"))).unwrap();
+        assert!(output.contains("\n100.00% (1/1) https://fumen.zui.jp/?v115@"));
+
+        assert!(run(&to_args(&format!("-t {data} -p Z -sr unknown"))).is_err());
+    }
+}
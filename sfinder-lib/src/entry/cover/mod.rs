@@ -0,0 +1 @@
+pub mod cover_entry_point;
@@ -0,0 +1,52 @@
+//! Porting note: replaces EntryPointMain. The output is returned instead of being printed.
+
+use super::{
+    common::{option_parser, settings::COMMON_OPTIONS},
+    cover::cover_entry_point,
+    path::path_entry_point,
+    percent::percent_entry_point,
+    ren::ren_entry_point,
+    setup::setup_entry_point,
+    spin::spin_entry_point,
+};
+
+type EntryPoint = fn(&[String]) -> Result<String, String>;
+
+const COMMANDS: &str = "percent, path, setup, cover, ren, spin";
+
+fn usage() -> String {
+    format!("Usage: <command> [options]\n  <command>: {COMMANDS}\n")
+}
+
+// The first argument is the command, and the rest are its options
+pub fn run(args: &[String]) -> Result<String, String> {
+    let Some((command, options)) = args.split_first() else {
+        return Err(format!("Command is not specified\n{}", usage()));
+    };
+
+    if command == "-h" || command == "--help" {
+        return Ok(usage());
+    }
+
+    let (run, specs): (EntryPoint, _) = match command.as_str() {
+        "percent" => (percent_entry_point::run, percent_entry_point::OPTIONS),
+        "path" => (path_entry_point::run, path_entry_point::OPTIONS),
+        "setup" => (setup_entry_point::run, setup_entry_point::OPTIONS),
+        "cover" => (cover_entry_point::run, cover_entry_point::OPTIONS),
+        "ren" => (ren_entry_point::run, &[][..]),
+        "spin" => (spin_entry_point::run, spin_entry_point::OPTIONS),
+        _ => return Err(format!("Invalid command: {command}\n{}", usage())),
+    };
+
+    if options
+        .iter()
+        .any(|option| option == "-h" || option == "--help")
+    {
+        return Ok(option_parser::format_usage(
+            command,
+            &[COMMON_OPTIONS, specs],
+        ));
+    }
+
+    run(options)
+}
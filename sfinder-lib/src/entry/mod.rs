@@ -1 +1,8 @@
 pub mod common;
+pub mod cover;
+pub mod entry_point_main;
+pub mod path;
+pub mod percent;
+pub mod ren;
+pub mod setup;
+pub mod spin;
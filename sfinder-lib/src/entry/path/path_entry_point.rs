@@ -0,0 +1,148 @@
+//! Porting note: replaces PathEntryPoint and PathSettingParser.
+//! The minimal or the unique solutions are listed by the kind option, with the pieces in the order
+//! that they can be built. The minimal solutions are searched only for the minimal kind.
+
+use crate::{
+    common::datastore::operation::Operation,
+    entry::common::{option_parser::OptionSpec, output, settings::Settings},
+    searcher::{
+        pack::perfect_pack_searcher::PerfectPackSearcher, path::path_core::PathCore,
+        percent::percent_core::SuccessCount,
+    },
+    sfinder_core::{
+        field::field_factory,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+    },
+};
+use std::fmt::Write;
+
+pub const OPTIONS: &[OptionSpec] = &[OptionSpec::new("kind", "k")];
+
+const DEFAULT_MAX_CLEAR_LINE: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathKind {
+    Minimal,
+    Unique,
+}
+
+fn parse_kind(value: &str) -> Result<PathKind, String> {
+    match value {
+        "minimal" => Ok(PathKind::Minimal),
+        "unique" => Ok(PathKind::Unique),
+        _ => Err(format!("Unsupported kind: {value}")),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, options) = Settings::load(&mino_factory, args, OPTIONS)?;
+    settings.check_single_thread()?;
+    let mino_rotation = settings.get_kicks().get_rotation();
+    let kind = parse_kind(options.get("kind").unwrap_or("minimal"))?;
+
+    let max_clear_line = settings
+        .get_max_clear_line_up_to(DEFAULT_MAX_CLEAR_LINE, PerfectPackSearcher::MAX_HEIGHT)?;
+    let field = field_factory::from_colored_field(settings.get_field(), max_clear_line);
+
+    let mut path_core = PathCore::new(
+        &mino_factory,
+        &mino_shifter,
+        mino_rotation,
+        max_clear_line,
+        settings.is_using_hold(),
+        settings.get_drop_type(),
+    );
+    let result = path_core.run(field.as_ref(), settings.get_generator().iter())?;
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    writeln!(
+        output,
+        "Found path [unique] = {}",
+        result.get_unique().len()
+    )
+    .unwrap();
+    let solutions = match kind {
+        PathKind::Minimal => {
+            let minimal = result.get_minimal();
+            writeln!(output, "Found path [minimal] = {}", minimal.len()).unwrap();
+            minimal
+        }
+        PathKind::Unique => result.get_unique().iter().collect(),
+    };
+    writeln!(output).unwrap();
+
+    let mut reachable = settings.get_drop_type().create_reachable(
+        &mino_factory,
+        &mino_shifter,
+        mino_rotation,
+        max_clear_line,
+    );
+    let total = result.get_sequences().len();
+    for solution in solutions {
+        let operations = output::to_build_order(
+            &mino_factory,
+            reachable.as_mut(),
+            field.as_ref(),
+            solution.get_operations(),
+            max_clear_line,
+        );
+        let fumen = output::to_fumen(&mino_factory, field.as_ref(), &operations, max_clear_line)?;
+
+        writeln!(
+            output,
+            "{} {} {fumen}",
+            output::format_pieces(operations.iter().map(|operation| operation.get_piece())),
+            output::format_count(&SuccessCount::new(solution.get_coverage_count(), total)),
+        )
+        .unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::{to_args, to_tetfu_data};
+
+    #[test]
+    fn path() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&(String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+        ));
+        let output = run(&to_args(&format!("-t {data} -c 2 -p I,I;O,O;I,O"))).unwrap();
+
+        assert!(output.contains("Found path [unique] = 2\n"));
+        assert!(output.contains("Found path [minimal] = 2\n"));
+        assert!(output.contains("\nII 33.33% (1/3) https://fumen.zui.jp/?v115@"));
+        assert!(output.contains("\nOO 33.33% (1/3) https://fumen.zui.jp/?v115@"));
+
+        // the pack search supports up to 12 lines
+        assert!(run(&to_args(&format!("-t {data} -c 13 -p I,I"))).is_err());
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p I,I -th 2"))).is_err());
+    }
+
+    #[test]
+    fn kind() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&(String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+        ));
+
+        // the unique solutions are listed without searching the minimal solutions
+        let output = run(&to_args(&format!("-t {data} -c 2 -p *,* -k unique"))).unwrap();
+        assert!(output.contains("Found path [unique] = 4\n"));
+        assert!(!output.contains("Found path [minimal]"));
+        assert_eq!(output.matches("https://fumen.zui.jp/?v115@").count(), 4);
+
+        let output = run(&to_args(&format!("-t {data} -c 2 -p *,* -k minimal"))).unwrap();
+        assert!(output.contains("Found path [minimal] = 4\n"));
+
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p *,* -k all"))).is_err());
+    }
+}
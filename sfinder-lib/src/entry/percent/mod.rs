@@ -0,0 +1 @@
+pub mod percent_entry_point;
@@ -0,0 +1,158 @@
+//! Porting note: replaces PercentEntryPoint and PercentSettingParser.
+//! The success pattern tree is only shown for the first piece.
+//! The width option searches on a VariableWidthField, which the original does not support.
+
+use crate::{
+    common::tetfu::{common::color_type::ColorType, field::colored_field::ColoredField},
+    entry::common::{option_parser::OptionSpec, output, settings::Settings},
+    searcher::{common::parallel_executor::ParallelExecutor, percent::percent_core::PercentCore},
+    sfinder_core::{
+        field::{
+            field_constants::FIELD_WIDTH,
+            field_factory,
+            variable_width_field::{MAX_FIELD_HEIGHT as VARIABLE_WIDTH_MAX, MAX_FIELD_WIDTH},
+        },
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+    },
+};
+use std::fmt::Write;
+
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec::new("failed-count", "fc"),
+    OptionSpec::new("width", "w"),
+];
+
+const DEFAULT_MAX_CLEAR_LINE: u8 = 4;
+const DEFAULT_FAILED_COUNT: usize = 100;
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, options) = Settings::load(&mino_factory, args, OPTIONS)?;
+    let failed_count = options
+        .parse::<usize>("failed-count")?
+        .unwrap_or(DEFAULT_FAILED_COUNT);
+
+    let width = options.parse::<u8>("width")?.unwrap_or(FIELD_WIDTH);
+    if !(1..=MAX_FIELD_WIDTH).contains(&width) {
+        return Err(format!("Width should be 1 <= width <= {MAX_FIELD_WIDTH}"));
+    }
+
+    let max_clear_line = settings.get_max_clear_line(DEFAULT_MAX_CLEAR_LINE);
+    if width != FIELD_WIDTH && VARIABLE_WIDTH_MAX < max_clear_line {
+        return Err(format!(
+            "Clear line should be 1 <= line <= {VARIABLE_WIDTH_MAX} with the width"
+        ));
+    }
+    let colored_field = settings.get_field();
+    // 幅より右にあるブロックは無視せずにエラーにする
+    if (0..max_clear_line.min(colored_field.get_max_height() as u8))
+        .any(|y| (width..FIELD_WIDTH).any(|x| colored_field.get_color(x, y) != ColorType::Empty))
+    {
+        return Err("Field should have no blocks outside of the width".into());
+    }
+    let field = field_factory::from_colored_field_with_width(colored_field, max_clear_line, width);
+
+    let mut percent_core = PercentCore::new(
+        &mino_factory,
+        &mino_shifter,
+        settings.get_kicks().get_rotation(),
+        max_clear_line,
+        settings.is_using_hold(),
+        settings.get_drop_type(),
+    );
+    // The subtrees of the prefixes of the sequences are split between the threads
+    let result = match settings.get_threads() {
+        1 => percent_core.run_tree(field.as_ref(), settings.get_generator().iter())?,
+        threads => percent_core.run_parallel(
+            field.as_ref(),
+            settings.get_generator().iter(),
+            &ParallelExecutor::new(threads),
+        )?,
+    };
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    writeln!(
+        output,
+        "success = {}",
+        output::format_count(&result.get_count())
+    )
+    .unwrap();
+    writeln!(output).unwrap();
+
+    writeln!(output, "Success pattern tree [Head 1 pieces]:").unwrap();
+    writeln!(output, "* -> {:.2} %", result.get_success_rate() * 100.0).unwrap();
+    for (piece, count) in result.get_first_piece_counts() {
+        writeln!(
+            output,
+            "{piece} -> {:.2} %",
+            count.get_success_rate() * 100.0
+        )
+        .unwrap();
+    }
+    writeln!(output, "-------------------").unwrap();
+
+    writeln!(output, "Fail pattern (max. {failed_count})").unwrap();
+    for sequence in result.get_failed_sequences().iter().take(failed_count) {
+        writeln!(output, "{sequence:?}").unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::{to_args, to_tetfu_data};
+
+    #[test]
+    fn percent() {
+        let data = to_tetfu_data(&(String::new() + "____XXXXXX" + "____XXXXXX"));
+        let output = run(&to_args(&format!(
+            "-t {data} -c 2 -H avoid -p [OTIL],[OTIL] -fc 3"
+        )))
+        .unwrap();
+
+        // II, LL, OO are successful
+        assert!(output.contains("Max clear lines: 2\n"));
+        assert!(output.contains("Using hold: avoid\n"));
+        assert!(output.contains("success = 18.75% (3/16)\n"));
+        assert!(output.contains("T -> 0.00 %\n"));
+        assert!(output.contains("I -> 25.00 %\n"));
+        assert!(output.ends_with("Fail pattern (max. 3)\n[O, T]\n[O, I]\n[O, L]\n"));
+    }
+
+    #[test]
+    fn threads() {
+        let data = to_tetfu_data(&(String::new() + "____XXXXXX" + "____XXXXXX"));
+        let args = format!("-t {data} -c 2 -H use -p *p3");
+
+        let expected = run(&to_args(&args)).unwrap();
+        let actual = run(&to_args(&format!("{args} -th 3"))).unwrap();
+        assert!(actual.contains("Threads: 3\n"));
+        assert_eq!(actual.replace("Threads: 3\n", "Threads: 1\n"), expected);
+    }
+
+    #[test]
+    fn width() {
+        let output = run(&to_args("-t v115@vhAAgH -c 2 -p O,O;T,T -w 4")).unwrap();
+        assert!(output.contains("success = 50.00% (1/2)\n"));
+
+        let output = run(&to_args("-t v115@vhAAgH -c 2 -p I,I,I,I,I,I -w 12")).unwrap();
+        assert!(output.contains("success = 100.00% (1/1)\n"));
+
+        assert!(run(&to_args("-t v115@vhAAgH -c 2 -p O,O -w 0")).is_err());
+        assert!(run(&to_args("-t v115@vhAAgH -c 2 -p O,O -w 65")).is_err());
+        assert!(run(&to_args("-t v115@vhAAgH -c 30 -p O,O -w 4")).is_err());
+
+        // 幅の外にブロックがある
+        let data = to_tetfu_data("___X______");
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p O,O -w 3"))).is_err());
+    }
+
+    #[test]
+    fn invalid_field() {
+        let data = to_tetfu_data(&(String::new() + "_____XXXXX" + "____XXXXXX"));
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p *p2"))).is_err());
+    }
+}
@@ -0,0 +1 @@
+pub mod ren_entry_point;
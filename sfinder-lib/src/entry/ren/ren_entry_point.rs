@@ -0,0 +1,131 @@
+//! Porting note: replaces RenEntryPoint and RenSettingParser.
+//! One of the longest combos is shown for each distinct sequence.
+//! The distinct sequences are distributed to the threads, each with its own candidate and table.
+
+use crate::{
+    common::tetfu::{
+        field::colored_field::ColoredField,
+        tetfu::{self, TetfuOptions},
+    },
+    entry::common::{output, settings::Settings},
+    searcher::{
+        common::{parallel_executor::ParallelExecutor, transposition_table::TranspositionTable},
+        ren::ren_searcher::RenSearcher,
+    },
+    sfinder_core::{
+        field::field_factory,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+    },
+};
+use std::{collections::HashSet, fmt::Write};
+
+const MIN_MAX_CLEAR_LINE: u8 = 4;
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, _) = Settings::load(&mino_factory, args, &[])?;
+
+    // 指定がなければ、フィールドのブロックが収まる高さ
+    let max_clear_line =
+        settings.get_max_clear_line(settings.get_field().get_max_y().max(MIN_MAX_CLEAR_LINE));
+    let field = field_factory::from_colored_field(settings.get_field(), max_clear_line);
+
+    let mut visited = HashSet::new();
+    let sequences = settings
+        .get_generator()
+        .iter()
+        .filter(|sequence| visited.insert(sequence.clone()))
+        .collect::<Vec<_>>();
+
+    let searcher = RenSearcher::new(&mino_factory, max_clear_line, settings.is_using_hold());
+    let results = ParallelExecutor::new(settings.get_threads()).map(
+        &sequences,
+        || {
+            let candidate = settings.get_drop_type().create_candidate(
+                &mino_factory,
+                &mino_shifter,
+                settings.get_kicks().get_rotation(),
+                max_clear_line,
+            );
+            (candidate, TranspositionTable::default())
+        },
+        |(candidate, table), sequence| {
+            searcher
+                .search_with_table(field.as_ref(), sequence, candidate.as_mut(), table)
+                .into_iter()
+                .next()
+        },
+    );
+
+    let mut lines = Vec::new();
+    let mut max_ren = 0;
+    for (sequence, result) in sequences.into_iter().zip(results) {
+        let line = match result {
+            Some(result) => {
+                max_ren = max_ren.max(result.get_ren());
+                let fumen = tetfu::encode_operations(
+                    &mino_factory,
+                    field.as_ref(),
+                    result.get_operations(),
+                    &TetfuOptions::default(),
+                )
+                .map_err(|e| format!("Cannot encode tetfu: {e}"))?;
+                format!(
+                    "{} -> {} REN {fumen}",
+                    output::format_pieces(sequence),
+                    result.get_ren()
+                )
+            }
+            None => format!("{} -> 0 REN", output::format_pieces(sequence)),
+        };
+        lines.push(line);
+    }
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    writeln!(output, "Max REN = {max_ren}").unwrap();
+    writeln!(output).unwrap();
+    for line in lines {
+        writeln!(output, "{line}").unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::{to_args, to_tetfu_data};
+
+    #[test]
+    fn ren() {
+        let data = to_tetfu_data(&"XXX____XXX".repeat(4));
+        let output = run(&to_args(&format!("-t {data} -p I,I,I,I;O,I,I,I -H avoid"))).unwrap();
+
+        assert!(output.contains("Max clear lines: 4\n"));
+        assert!(output.contains("Max REN = 4\n"));
+        assert!(output.contains("\nIIII -> 4 REN https://fumen.zui.jp/?v115@"));
+        assert!(output.contains("\nOIII -> 0 REN\n"));
+    }
+
+    #[test]
+    fn above_24_rows() {
+        // the rows above the tetfu field are empty
+        let data = to_tetfu_data(&"XXX____XXX".repeat(4));
+        let output = run(&to_args(&format!("-t {data} -p I,I,I,I -c 30"))).unwrap();
+
+        assert!(output.contains("Max clear lines: 30\n"));
+        assert!(output.contains("Max REN = 4\n"));
+    }
+
+    #[test]
+    fn threads() {
+        let data = to_tetfu_data(&"XXX____XXX".repeat(4));
+        let args = format!("-t {data} -p [IOT]p3 -H avoid");
+        let expected = run(&to_args(&args)).unwrap();
+        let actual = run(&to_args(&format!("{args} -th 3"))).unwrap();
+
+        assert!(actual.contains("Threads: 3\n"));
+        assert_eq!(actual.replace("Threads: 3\n", "Threads: 1\n"), expected);
+    }
+}
@@ -0,0 +1,176 @@
+//! Porting note: replaces SetupEntryPoint and SetupSettingParser.
+//! The blocks of the fill color must be filled, and the blocks of the margin color may be filled.
+//! The other colored blocks are the field, and the empty blocks are kept empty.
+
+use crate::{
+    common::{
+        datastore::operation::Operation,
+        tetfu::{common::color_type::ColorType, field::colored_field::ColoredField},
+    },
+    entry::common::{option_parser::OptionSpec, output, settings::Settings},
+    searcher::{
+        percent::percent_core::SuccessCount,
+        setup::{
+            setup_filter::SetupFilter,
+            setup_searcher::{SetupSearcher, SetupTarget},
+        },
+    },
+    sfinder_core::{
+        field::{field::Field, field_constants::FIELD_WIDTH, field_factory},
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+    },
+};
+use std::fmt::Write;
+
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec::new("fill", "f"),
+    OptionSpec::new("margin", "m"),
+    OptionSpec::new("exclude", "e"),
+];
+
+const DEFAULT_MAX_CLEAR_LINE: u8 = 4;
+// Same as the original
+const MAX_CLEAR_LINE: u8 = 12;
+
+fn parse_color(value: &str) -> Result<ColorType, String> {
+    value
+        .to_ascii_uppercase()
+        .parse::<Piece>()
+        .map(ColorType::from)
+        .map_err(|_| format!("Unsupported color: {value}"))
+}
+
+fn parse_exclude(value: &str) -> Result<Option<SetupFilter>, String> {
+    match value {
+        "none" => Ok(None),
+        "holes" => Ok(Some(SetupFilter::NoHoles)),
+        "strict" => Ok(Some(SetupFilter::Strict)),
+        "tspin" => Ok(Some(SetupFilter::TSpinSlot)),
+        _ => Err(format!("Unsupported exclude: {value}")),
+    }
+}
+
+// Returns the blocks of the colored field that satisfy the predicate
+fn to_field(
+    colored_field: &dyn ColoredField,
+    height: u8,
+    predicate: impl Fn(ColorType) -> bool,
+) -> Box<dyn Field> {
+    let mut field = field_factory::create_field(height);
+    for y in 0..height {
+        for x in 0..FIELD_WIDTH {
+            if predicate(colored_field.get_color(x, y)) {
+                field.set_block(x, y);
+            }
+        }
+    }
+    field
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, options) = Settings::load(&mino_factory, args, OPTIONS)?;
+    settings.check_single_thread()?;
+    let mino_rotation = settings.get_kicks().get_rotation();
+
+    let fill_color = parse_color(options.get("fill").unwrap_or("i"))?;
+    let margin_color = parse_color(options.get("margin").unwrap_or("o"))?;
+    if fill_color == margin_color {
+        return Err("Fill and margin colors should be different".into());
+    }
+    let filter = parse_exclude(options.get("exclude").unwrap_or("none"))?;
+
+    let max_clear_line =
+        settings.get_max_clear_line_up_to(DEFAULT_MAX_CLEAR_LINE, MAX_CLEAR_LINE)?;
+    let colored_field = settings.get_field();
+    let field = to_field(colored_field, max_clear_line, |color| {
+        color != ColorType::Empty && color != fill_color && color != margin_color
+    });
+    let need_filled = to_field(colored_field, max_clear_line, |color| color == fill_color);
+    let may_filled = to_field(colored_field, max_clear_line, |color| color == margin_color);
+    if need_filled.is_empty() {
+        return Err("Field should have blocks of the fill color".into());
+    }
+
+    let target = SetupTarget::new(
+        need_filled.as_ref(),
+        Some(may_filled.as_ref()),
+        None,
+        max_clear_line,
+    )?;
+    let mut searcher = SetupSearcher::new(
+        &mino_factory,
+        &mino_shifter,
+        mino_rotation,
+        max_clear_line,
+        settings.is_using_hold(),
+        settings.get_drop_type(),
+    );
+    let mut result = searcher.run(field.as_ref(), &target, settings.get_generator().iter())?;
+    if let Some(filter) = filter {
+        result.retain(&mino_factory, field.as_ref(), max_clear_line, &filter);
+    }
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    writeln!(output, "Found solutions = {}", result.get_solutions().len()).unwrap();
+    writeln!(output).unwrap();
+
+    let mut reachable = settings.get_drop_type().create_reachable(
+        &mino_factory,
+        &mino_shifter,
+        mino_rotation,
+        max_clear_line,
+    );
+    let total = result.get_sequences().len();
+    for solution in result.get_solutions() {
+        let operations = output::to_build_order(
+            &mino_factory,
+            reachable.as_mut(),
+            field.as_ref(),
+            solution.get_operations(),
+            max_clear_line,
+        );
+        let fumen = output::to_fumen(&mino_factory, field.as_ref(), &operations, max_clear_line)?;
+
+        writeln!(
+            output,
+            "{} {} {fumen}",
+            output::format_pieces(operations.iter().map(|operation| operation.get_piece())),
+            output::format_count(&SuccessCount::new(
+                solution.get_covered_sequences().len(),
+                total
+            )),
+        )
+        .unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::{to_args, to_tetfu_data};
+
+    #[test]
+    fn setup() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&(String::new()
+            + "XXXXXXIIII"
+            + "XXXXXXIIII"
+        ));
+        let output = run(&to_args(&format!("-t {data} -c 2 -p I,I;O,O;T,T"))).unwrap();
+
+        assert!(output.contains("Found solutions = 2\n"));
+        assert!(output.contains("\nII 33.33% (1/3) https://fumen.zui.jp/?v115@"));
+        assert!(output.contains("\nOO 33.33% (1/3) https://fumen.zui.jp/?v115@"));
+
+        assert!(run(&to_args(&format!("-t {data} -c 13 -p I,I"))).is_err());
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p I,I -th 2"))).is_err());
+
+        // the fill color is not found
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p I,I -f t"))).is_err());
+        assert!(run(&to_args(&format!("-t {data} -c 2 -p I,I -f i -m i"))).is_err());
+    }
+}
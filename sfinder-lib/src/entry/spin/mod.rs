@@ -0,0 +1 @@
+pub mod spin_entry_point;
@@ -0,0 +1,187 @@
+//! Porting note: replaces SpinEntryPoint and SpinSettingParser.
+//! The solutions are listed from the highest priority of the spin, with the spin piece placed last.
+
+use crate::{
+    common::{
+        datastore::operation::Operation,
+        spin_checker::SpinRule,
+        tetfu::{
+            field::colored_field::ColoredField,
+            tetfu::{self, TetfuOptions},
+        },
+    },
+    entry::common::{option_parser::OptionSpec, output, settings::Settings},
+    searcher::{
+        percent::percent_core::SuccessCount,
+        spins::{
+            spin::{ClearedRows, Spin, TSpinNames, TSpins},
+            spin_filter::SpinFilter,
+            spin_searcher::SpinSearcher,
+        },
+    },
+    sfinder_core::{
+        field::field_factory,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+    },
+};
+use std::fmt::Write;
+
+pub const OPTIONS: &[OptionSpec] = &[
+    OptionSpec::new("line", "l"),
+    OptionSpec::new("fill-top", "ft"),
+    OptionSpec::new("filter", "fi"),
+    OptionSpec::new("spin-rule", "sr"),
+];
+
+const MIN_MAX_CLEAR_LINE: u8 = 4;
+const DEFAULT_MIN_CLEARED_ROWS: u8 = 2;
+
+fn parse_filter(value: &str) -> Result<Option<SpinFilter>, String> {
+    match value {
+        "none" => Ok(None),
+        "strict" => Ok(Some(SpinFilter::NoHolesBelowT)),
+        _ => Err(format!("Unsupported filter: {value}")),
+    }
+}
+
+// e.g. TSD, TSMS, TSD [Neo], ZSMS
+fn format_spin(piece: Piece, spin: &Spin) -> String {
+    let mini = match spin.spin {
+        TSpins::Regular => "",
+        TSpins::Mini => "M",
+    };
+    let cleared_rows = match spin.cleared_rows {
+        ClearedRows::Zero => "Z",
+        ClearedRows::Single => "S",
+        ClearedRows::Double => "D",
+        ClearedRows::Triple => "T",
+        ClearedRows::Quadruple => "Q",
+    };
+    let name = match spin.name {
+        TSpinNames::NoName => "",
+        TSpinNames::Fin => " [Fin]",
+        TSpinNames::Iso => " [Iso]",
+        TSpinNames::Neo => " [Neo]",
+    };
+    format!("{piece}S{mini}{cleared_rows}{name}")
+}
+
+pub fn run(args: &[String]) -> Result<String, String> {
+    let mino_factory = MinoFactory::new();
+    let mino_shifter = MinoShifter::new();
+    let (settings, options) = Settings::load(&mino_factory, args, OPTIONS)?;
+    settings.check_single_thread()?;
+
+    // 指定がなければ、フィールドのブロックが収まる高さ
+    let max_clear_line =
+        settings.get_max_clear_line(settings.get_field().get_max_y().max(MIN_MAX_CLEAR_LINE));
+    let min_cleared_rows = options
+        .parse::<u8>("line")?
+        .unwrap_or(DEFAULT_MIN_CLEARED_ROWS);
+    if 3 < min_cleared_rows {
+        return Err("Line should be 0 <= line <= 3".into());
+    }
+    let fill_top = options.parse::<u8>("fill-top")?.unwrap_or(max_clear_line);
+    let filter = parse_filter(options.get("filter").unwrap_or("none"))?;
+    let spin_rule = options.parse::<SpinRule>("spin-rule")?.unwrap_or_default();
+
+    let field = field_factory::from_colored_field(settings.get_field(), max_clear_line);
+
+    let mut searcher = SpinSearcher::new(
+        &mino_factory,
+        &mino_shifter,
+        settings.get_kicks().get_rotation(),
+        max_clear_line,
+        fill_top,
+        settings.is_using_hold(),
+        settings.get_drop_type(),
+        spin_rule,
+    );
+    let mut result = searcher.run(
+        field.as_ref(),
+        settings.get_generator().iter(),
+        min_cleared_rows,
+    )?;
+    if let Some(filter) = filter {
+        result.retain(&mino_factory, field.as_ref(), max_clear_line, &filter);
+    }
+    result.sort_by_priority();
+
+    let mut output = output::format_header(&settings, field.as_ref(), max_clear_line);
+    writeln!(output, "Found solutions = {}", result.get_solutions().len()).unwrap();
+    writeln!(output).unwrap();
+
+    let total = result.get_sequences().len();
+    for solution in result.get_solutions() {
+        let fumen = tetfu::encode_operations(
+            &mino_factory,
+            field.as_ref(),
+            solution.get_operations(),
+            &TetfuOptions::default(),
+        )
+        .map_err(|e| format!("Cannot encode tetfu: {e}"))?;
+
+        writeln!(
+            output,
+            "{} {} {fumen}",
+            format_spin(
+                solution.get_spin_operation().get_piece(),
+                solution.get_spin()
+            ),
+            output::format_count(&SuccessCount::new(
+                solution.get_covered_sequences().len(),
+                total
+            )),
+        )
+        .unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::{to_args, to_tetfu_data};
+
+    #[test]
+    fn spin() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&(String::new()
+            + "X__XX_____"
+            + "X___XX____"
+            + "XX_XXXXXXX"
+        ));
+        let output = run(&to_args(&format!("-t {data} -c 6 -p I,T;T,I;T,O -H avoid"))).unwrap();
+
+        assert!(output.contains("Found solutions = 1\n"));
+        assert!(output.contains("\nTSD 33.33% (1/3) https://fumen.zui.jp/?v115@"));
+
+        // 4つのミノで4段を消すスピンはない
+        let output = run(&to_args("-t v115@vhAAgH -c 4 -p *p4")).unwrap();
+        assert!(output.contains("Found solutions = 0\n"));
+
+        assert!(run(&to_args(&format!("-t {data} -p T,I -l 4"))).is_err());
+        assert!(run(&to_args(&format!("-t {data} -p T,I -sr spin"))).is_err());
+        assert!(run(&to_args(&format!("-t {data} -c 6 -p T,I -th 2"))).is_err());
+    }
+
+    #[test]
+    fn all_spin() {
+        #[rustfmt::skip]
+        let data = to_tetfu_data(&(String::new()
+            + "XXX___XXXX"
+            + "XXXXX__XXX"
+            + "XXXX__XXXX"
+        ));
+
+        let output = run(&to_args(&format!("-t {data} -c 6 -p Z -l 1"))).unwrap();
+        assert!(output.contains("Found solutions = 0\n"));
+
+        let output = run(&to_args(&format!(
+            "-t {data} -c 6 -p Z -l 1 --spin-rule allspin"
+        )))
+        .unwrap();
+        assert!(output.contains("\nZSMS 100.00% (1/1) https://fumen.zui.jp/?v115@"));
+    }
+}
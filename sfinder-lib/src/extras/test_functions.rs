@@ -2,3 +2,36 @@
 pub fn assert_partialord_symmetric<T: PartialOrd>(a: T, b: T) {
     assert_eq!(a.partial_cmp(&b), b.partial_cmp(&a).map(|o| o.reverse()));
 }
+
+// Splits the command line by spaces
+#[cfg(test)]
+pub fn to_args(command: &str) -> Vec<String> {
+    command.split(' ').map(str::to_owned).collect()
+}
+
+// The tetfu data of a page with the field
+#[cfg(test)]
+pub fn to_tetfu_data(marks: &str) -> String {
+    use crate::{
+        common::tetfu::{field::colored_field_factory, tetfu, tetfu_page::TetfuPage},
+        sfinder_core::mino::mino_factory::MinoFactory,
+    };
+
+    let page = TetfuPage::new(
+        colored_field_factory::create_colored_field(marks.to_owned()),
+        None,
+        String::new(),
+        true,
+        false,
+        false,
+    );
+    tetfu::encode(&MinoFactory::new(), &[page]).unwrap()
+}
+
+// The pieces of the sequence, e.g. "TIO"
+#[cfg(test)]
+pub fn pieces(str: &str) -> Vec<crate::sfinder_core::mino::piece::Piece> {
+    str.chars()
+        .map(|c| c.to_string().parse().unwrap())
+        .collect()
+}
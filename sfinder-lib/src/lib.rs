@@ -8,5 +8,3 @@ pub mod extras;
 pub mod searcher;
 mod sfinder_core;
 pub mod sfinder_lib;
-
-pub fn test_function() {}
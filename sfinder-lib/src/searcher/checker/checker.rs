@@ -0,0 +1,75 @@
+use crate::{
+    common::datastore::{action::action::Action, simple_operation::SimpleOperation},
+    searcher::common::validator::validator::Validator,
+    sfinder_core::{
+        action::candidate::candidate::Candidate,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+
+pub trait Checker<T: Action> {
+    // Returns the operations of a solution if found.
+    // Each operation is relative to the field after the rows cleared by the previous operations.
+    fn check(
+        &mut self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        max_depth: u8,
+    ) -> Option<Vec<SimpleOperation>>;
+}
+
+type SearchNext<'a, T> =
+    dyn FnMut(&dyn Field, &mut dyn Candidate<T>, u8, &mut Vec<SimpleOperation>) -> bool + 'a;
+
+// Porting note: the part shared between CheckerNoHold and CheckerUsingHold.
+// Tries every placement of the piece, and calls `next` with the field after the placement
+// until it finds a solution for the remaining depth.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn search_piece<T: Action>(
+    mino_factory: &MinoFactory,
+    validator: &dyn Validator,
+    field: &dyn Field,
+    piece: Piece,
+    candidate: &mut dyn Candidate<T>,
+    max_clear_line: u8,
+    left_depth: u8,
+    operations: &mut Vec<SimpleOperation>,
+    next: &mut SearchNext<'_, T>,
+) -> bool {
+    for action in candidate.search(field, piece, max_clear_line) {
+        let mino = mino_factory.get(piece, action.get_rotate());
+        let mut next_field = field.prune(max_clear_line);
+        next_field.put(mino, action.get_x(), action.get_y());
+        let next_max_clear_line = max_clear_line - next_field.clear_filled_rows() as u8;
+
+        operations.push(SimpleOperation::new(
+            piece,
+            action.get_rotate(),
+            action.get_x(),
+            action.get_y(),
+        ));
+
+        let is_found = if left_depth == 1 {
+            validator.satisfies(next_field.as_ref(), next_max_clear_line)
+        } else {
+            validator.validate(next_field.as_ref(), next_max_clear_line)
+                && next(
+                    next_field.as_ref(),
+                    candidate,
+                    next_max_clear_line,
+                    operations,
+                )
+        };
+
+        if is_found {
+            return true;
+        }
+
+        operations.pop();
+    }
+
+    false
+}
@@ -0,0 +1,236 @@
+use super::checker::{self, Checker};
+use crate::{
+    common::datastore::{
+        action::action::Action, piece_counter::PieceCounter, simple_operation::SimpleOperation,
+    },
+    searcher::common::{
+        transposition_table::{StateKey, TranspositionTable},
+        validator::validator::Validator,
+    },
+    sfinder_core::{
+        action::candidate::candidate::Candidate,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+
+pub struct CheckerNoHold<'a, V: Validator> {
+    mino_factory: &'a MinoFactory,
+    validator: V,
+    // Porting note: the states that are known to fail in the current check
+    table: TranspositionTable<()>,
+}
+
+impl<'a, V: Validator> CheckerNoHold<'a, V> {
+    pub fn new(mino_factory: &'a MinoFactory, validator: V) -> Self {
+        Self::with_table(mino_factory, validator, TranspositionTable::default())
+    }
+
+    pub fn with_table(
+        mino_factory: &'a MinoFactory,
+        validator: V,
+        table: TranspositionTable<()>,
+    ) -> Self {
+        Self {
+            mino_factory,
+            validator,
+            table,
+        }
+    }
+
+    pub fn get_table(&self) -> &TranspositionTable<()> {
+        &self.table
+    }
+
+    // Porting note: an associated function, so that the table can be borrowed with the validator
+    #[allow(clippy::too_many_arguments)]
+    fn search<T: Action>(
+        mino_factory: &MinoFactory,
+        validator: &dyn Validator,
+        table: &mut TranspositionTable<()>,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        left_depth: u8,
+        operations: &mut Vec<SimpleOperation>,
+    ) -> bool {
+        let Some((&piece, rest)) = pieces.split_first() else {
+            return false;
+        };
+
+        // The remaining pieces are a suffix of the sequence, so their counts decide the state
+        let key = StateKey::new(field, PieceCounter::from(pieces.iter().copied()), None);
+        if table.get(&key).is_some() {
+            return false;
+        }
+
+        let is_found = checker::search_piece(
+            mino_factory,
+            validator,
+            field,
+            piece,
+            candidate,
+            max_clear_line,
+            left_depth,
+            operations,
+            &mut |field, candidate, max_clear_line, operations| {
+                Self::search(
+                    mino_factory,
+                    validator,
+                    table,
+                    field,
+                    rest,
+                    candidate,
+                    max_clear_line,
+                    left_depth - 1,
+                    operations,
+                )
+            },
+        );
+
+        if !is_found {
+            table.insert(key, left_depth, ());
+        }
+        is_found
+    }
+}
+
+impl<T: Action, V: Validator> Checker<T> for CheckerNoHold<'_, V> {
+    fn check(
+        &mut self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        max_depth: u8,
+    ) -> Option<Vec<SimpleOperation>> {
+        assert!(0 < max_depth);
+        assert!(max_depth as usize <= pieces.len());
+
+        let mut freeze = field.prune(max_clear_line);
+        let max_clear_line = max_clear_line - freeze.clear_filled_rows() as u8;
+
+        // 別のミノ列では、同じ残りのミノでも順番が違うことがある
+        self.table.new_search();
+
+        let mut operations = Vec::with_capacity(max_depth as usize);
+        Self::search(
+            self.mino_factory,
+            &self.validator,
+            &mut self.table,
+            freeze.as_ref(),
+            &pieces[..max_depth as usize],
+            candidate,
+            max_clear_line,
+            max_depth,
+            &mut operations,
+        )
+        .then_some(operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::datastore::operation::Operation,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::common::{
+            transposition_table::ReplacementPolicy, validator::perfect_validator::PerfectValidator,
+        },
+        sfinder_core::{
+            action::candidate::candidate_facade, field::field_factory,
+            mino::mino_shifter::MinoShifter,
+        },
+    };
+
+    fn check(marks: &str, pieces: &[Piece], max_clear_line: u8) -> Option<Vec<SimpleOperation>> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            max_clear_line,
+        );
+        let mut checker = CheckerNoHold::new(&mino_factory, PerfectValidator);
+
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        checker.check(
+            field.as_ref(),
+            pieces,
+            candidate.as_mut(),
+            max_clear_line,
+            (pieces.len()) as u8,
+        )
+    }
+
+    #[test]
+    fn check_2x4() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+
+        assert!(check(&marks, &[Piece::O, Piece::O], 2).is_some());
+        assert!(check(&marks, &[Piece::I, Piece::I], 2).is_some());
+        assert!(check(&marks, &[Piece::L, Piece::L], 2).is_some());
+        assert!(check(&marks, &[Piece::T, Piece::T], 2).is_none());
+        assert!(check(&marks, &[Piece::L, Piece::J], 2).is_none());
+    }
+
+    #[test]
+    fn check_result() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____";
+
+        let operations = check(&marks, &[Piece::I, Piece::I, Piece::I, Piece::I], 4).unwrap();
+        assert_eq!(operations.len(), 4);
+
+        // the operations should reproduce the perfect clear
+        let mino_factory = MinoFactory::new();
+        let mut field = field_factory::create_field_with_marks(marks);
+        for operation in &operations {
+            let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+            assert!(field.can_put(mino, operation.get_x(), operation.get_y()));
+            field.put(mino, operation.get_x(), operation.get_y());
+            field.clear_filled_rows();
+        }
+        assert!(field.is_empty());
+    }
+
+    #[test]
+    fn shared_table() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+        );
+        let mut checker = CheckerNoHold::with_table(
+            &mino_factory,
+            PerfectValidator,
+            TranspositionTable::new(1 << 8, ReplacementPolicy::AlwaysReplace),
+        );
+
+        let field =
+            field_factory::create_field_with_marks(String::new() + "____XXXXXX" + "____XXXXXX");
+        for (pieces, expected) in [
+            ([Piece::T, Piece::T], false),
+            ([Piece::L, Piece::L], true),
+            ([Piece::J, Piece::L], false),
+            ([Piece::L, Piece::J], false),
+            ([Piece::J, Piece::J], true),
+        ] {
+            let actual = checker.check(field.as_ref(), &pieces, candidate.as_mut(), 2, 2);
+            assert_eq!(actual.is_some(), expected, "{pieces:?}");
+        }
+        assert!(0 < checker.get_table().get_stats().get_stores());
+    }
+}
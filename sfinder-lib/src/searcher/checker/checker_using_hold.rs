@@ -0,0 +1,258 @@
+use super::checker::{self, Checker};
+use crate::{
+    common::datastore::{
+        action::action::Action, piece_counter::PieceCounter, simple_operation::SimpleOperation,
+    },
+    searcher::common::{
+        hold_choices,
+        transposition_table::{StateKey, TranspositionTable},
+        validator::validator::Validator,
+    },
+    sfinder_core::{
+        action::candidate::candidate::Candidate,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+
+pub struct CheckerUsingHold<'a, V: Validator> {
+    mino_factory: &'a MinoFactory,
+    validator: V,
+    // Porting note: the states that are known to fail in the current check
+    table: TranspositionTable<()>,
+}
+
+impl<'a, V: Validator> CheckerUsingHold<'a, V> {
+    pub fn new(mino_factory: &'a MinoFactory, validator: V) -> Self {
+        Self::with_table(mino_factory, validator, TranspositionTable::default())
+    }
+
+    pub fn with_table(
+        mino_factory: &'a MinoFactory,
+        validator: V,
+        table: TranspositionTable<()>,
+    ) -> Self {
+        Self {
+            mino_factory,
+            validator,
+            table,
+        }
+    }
+
+    pub fn get_table(&self) -> &TranspositionTable<()> {
+        &self.table
+    }
+
+    // Porting note: an associated function, so that the table can be borrowed with the validator
+    #[allow(clippy::too_many_arguments)]
+    fn search<T: Action>(
+        mino_factory: &MinoFactory,
+        validator: &dyn Validator,
+        table: &mut TranspositionTable<()>,
+        field: &dyn Field,
+        hold: Option<Piece>,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        left_depth: u8,
+        operations: &mut Vec<SimpleOperation>,
+    ) -> bool {
+        // The remaining pieces are a suffix of the sequence, so their counts decide the state
+        let key = StateKey::new(field, PieceCounter::from(pieces.iter().copied()), hold);
+        if table.get(&key).is_some() {
+            return false;
+        }
+
+        let is_found = hold_choices::get_choices_from_slice(hold, pieces, true)
+            .into_iter()
+            .any(|(piece, next_hold, next_pieces)| {
+                checker::search_piece(
+                    mino_factory,
+                    validator,
+                    field,
+                    piece,
+                    candidate,
+                    max_clear_line,
+                    left_depth,
+                    operations,
+                    &mut |field, candidate, max_clear_line, operations| {
+                        Self::search(
+                            mino_factory,
+                            validator,
+                            table,
+                            field,
+                            next_hold,
+                            next_pieces,
+                            candidate,
+                            max_clear_line,
+                            left_depth - 1,
+                            operations,
+                        )
+                    },
+                )
+            });
+
+        if !is_found {
+            table.insert(key, left_depth, ());
+        }
+        is_found
+    }
+}
+
+impl<T: Action, V: Validator> Checker<T> for CheckerUsingHold<'_, V> {
+    // The pieces after max_depth + 1 are not used, as they cannot be reached with hold.
+    fn check(
+        &mut self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        max_depth: u8,
+    ) -> Option<Vec<SimpleOperation>> {
+        assert!(0 < max_depth);
+        assert!(max_depth as usize <= pieces.len());
+
+        let mut freeze = field.prune(max_clear_line);
+        let max_clear_line = max_clear_line - freeze.clear_filled_rows() as u8;
+
+        // 別のミノ列では、同じ残りのミノでも順番が違うことがある
+        self.table.new_search();
+
+        let mut operations = Vec::with_capacity(max_depth as usize);
+        Self::search(
+            self.mino_factory,
+            &self.validator,
+            &mut self.table,
+            freeze.as_ref(),
+            None,
+            &pieces[..pieces.len().min(max_depth as usize + 1)],
+            candidate,
+            max_clear_line,
+            max_depth,
+            &mut operations,
+        )
+        .then_some(operations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::datastore::operation::Operation,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::common::{
+            transposition_table::{ReplacementPolicy, DEFAULT_MAX_BYTES},
+            validator::perfect_validator::PerfectValidator,
+        },
+        sfinder_core::{
+            action::candidate::candidate_facade, field::field_factory,
+            mino::mino_shifter::MinoShifter,
+        },
+    };
+
+    fn check(pieces: &[Piece], max_depth: u8) -> Option<Vec<SimpleOperation>> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+        );
+        let mut checker = CheckerUsingHold::new(&mino_factory, PerfectValidator);
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "____XXXXXX"
+            + "____XXXXXX"
+        );
+        checker.check(field.as_ref(), pieces, candidate.as_mut(), 2, max_depth)
+    }
+
+    #[test]
+    fn hold_first_piece() {
+        let operations = check(&[Piece::T, Piece::O, Piece::O], 2).unwrap();
+        assert!(operations
+            .iter()
+            .all(|operation| operation.get_piece() == Piece::O));
+    }
+
+    #[test]
+    fn swap_with_hold() {
+        assert!(check(&[Piece::O, Piece::T, Piece::O], 2).is_some());
+    }
+
+    #[test]
+    fn unreachable_pieces() {
+        // only one piece can be skipped by holding it
+        assert!(check(&[Piece::T, Piece::T, Piece::O, Piece::O], 2).is_none());
+    }
+
+    #[test]
+    fn fail() {
+        assert!(check(&[Piece::T, Piece::S, Piece::Z], 2).is_none());
+        assert!(check(&[Piece::L, Piece::J], 2).is_none());
+    }
+
+    #[test]
+    fn failed_states_are_memoized() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+        );
+
+        // 同じチェッカーで別のミノ列を探索しても、結果は変わらない
+        for policy in [
+            ReplacementPolicy::AlwaysReplace,
+            ReplacementPolicy::DepthPreferred,
+        ] {
+            for max_bytes in [0, 1 << 8, DEFAULT_MAX_BYTES] {
+                let mut checker = CheckerUsingHold::with_table(
+                    &mino_factory,
+                    PerfectValidator,
+                    TranspositionTable::new(max_bytes, policy),
+                );
+
+                for pieces in [
+                    "SZTLJ", "IIIIT", "LJSZO", "TIIII", "OOOOS", "LJOOO", "ZZZZZ",
+                ] {
+                    let pieces = pieces
+                        .chars()
+                        .map(|c| c.to_string().parse().unwrap())
+                        .collect::<Vec<_>>();
+
+                    let expected = CheckerUsingHold::new(&mino_factory, PerfectValidator).check(
+                        field.as_ref(),
+                        &pieces,
+                        candidate.as_mut(),
+                        4,
+                        4,
+                    );
+                    let actual = checker.check(field.as_ref(), &pieces, candidate.as_mut(), 4, 4);
+                    assert_eq!(actual.is_some(), expected.is_some(), "{pieces:?}");
+                }
+
+                let stats = checker.get_table().get_stats();
+                assert!(0 < stats.get_stores());
+                if max_bytes == DEFAULT_MAX_BYTES {
+                    assert!(0 < stats.get_hits());
+                }
+            }
+        }
+    }
+}
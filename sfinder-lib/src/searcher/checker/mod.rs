@@ -0,0 +1,4 @@
+pub mod checker;
+pub mod checker_no_hold;
+pub mod checker_using_hold;
+pub mod tree_checker;
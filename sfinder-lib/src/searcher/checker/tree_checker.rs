@@ -0,0 +1,309 @@
+//! Porting note: checks all sequences of a SequenceTree at once.
+//! The results are memoized on the state of the search, so the sequences that share a prefix
+//! or reach the same field with the same remaining pieces are only searched once.
+//! A state is keyed by the node of the remaining sequences, which also decides the max clear line
+//! together with the field and the hold, since the number of the placed pieces is decided by them.
+
+use crate::{
+    common::datastore::{action::action::Action, piece_counter::PieceCounter},
+    searcher::{
+        common::{
+            hold_choices,
+            transposition_table::{StateKey, TranspositionTable},
+            validator::validator::Validator,
+        },
+        percent::sequence_tree::SequenceTree,
+    },
+    sfinder_core::{
+        action::candidate::candidate::Candidate,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+use std::{ops::Range, rc::Rc};
+
+pub struct TreeChecker<'a, V: Validator> {
+    mino_factory: &'a MinoFactory,
+    validator: V,
+    is_using_hold: bool,
+    // The results of the subtrees from the states in the current check
+    table: TranspositionTable<Rc<Vec<bool>>>,
+}
+
+struct Search<'a, 'b, T: Action> {
+    tree: &'a SequenceTree,
+    candidate: &'a mut dyn Candidate<T>,
+    table: &'b mut TranspositionTable<Rc<Vec<bool>>>,
+    mino_factory: &'b MinoFactory,
+    validator: &'b dyn Validator,
+    is_using_hold: bool,
+}
+
+impl<T: Action> Search<'_, '_, T> {
+    // Returns whether each sequence in the subtree of the node succeeds from this state
+    fn search(
+        &mut self,
+        field: &dyn Field,
+        max_clear_line: u8,
+        hold: Option<Piece>,
+        node: usize,
+        left_depth: u8,
+    ) -> Rc<Vec<bool>> {
+        let key = StateKey::new(field, PieceCounter::new(), hold).with_extra(node as u64);
+        if let Some(results) = self.table.get(&key) {
+            return Rc::clone(results);
+        }
+
+        let leaves = self.tree.get_leaves(node);
+        let offset = leaves.start;
+        let mut results = vec![false; leaves.len()];
+
+        // ミノ列が終わっているときは、ホールドのミノしか使えない
+        if let (Some(terminal), Some(hold)) = (self.tree.get_terminal(node), hold) {
+            let slot = terminal - offset..terminal - offset + 1;
+            self.place(
+                field,
+                max_clear_line,
+                hold,
+                None,
+                node,
+                left_depth,
+                &mut results,
+                slot,
+            );
+        }
+
+        for &(piece, child) in self.tree.get_children(node) {
+            let choices = hold_choices::get_choices(
+                Some(piece),
+                hold,
+                child,
+                self.tree.get_children(child).iter().copied(),
+                self.is_using_hold,
+            );
+
+            for (piece, next_hold, next_node) in choices {
+                let slots = self.tree.get_leaves(next_node);
+                let slots = slots.start - offset..slots.end - offset;
+                self.place(
+                    field,
+                    max_clear_line,
+                    piece,
+                    next_hold,
+                    next_node,
+                    left_depth,
+                    &mut results,
+                    slots,
+                );
+            }
+        }
+
+        let results = Rc::new(results);
+        self.table.insert(key, left_depth, Rc::clone(&results));
+        results
+    }
+
+    // Tries every placement of the piece, and marks the slots of the sequences that succeed
+    #[allow(clippy::too_many_arguments)]
+    fn place(
+        &mut self,
+        field: &dyn Field,
+        max_clear_line: u8,
+        piece: Piece,
+        next_hold: Option<Piece>,
+        next_node: usize,
+        left_depth: u8,
+        results: &mut [bool],
+        slots: Range<usize>,
+    ) {
+        let results = &mut results[slots];
+        if results.iter().all(|&is_success| is_success) {
+            return;
+        }
+
+        for action in self.candidate.search(field, piece, max_clear_line) {
+            let mino = self.mino_factory.get(piece, action.get_rotate());
+            let mut next_field = field.prune(max_clear_line);
+            next_field.put(mino, action.get_x(), action.get_y());
+            let next_max_clear_line = max_clear_line - next_field.clear_filled_rows() as u8;
+
+            if left_depth == 1 {
+                if self
+                    .validator
+                    .satisfies(next_field.as_ref(), next_max_clear_line)
+                {
+                    results.fill(true);
+                    return;
+                }
+            } else if self
+                .validator
+                .validate(next_field.as_ref(), next_max_clear_line)
+            {
+                let next_results = self.search(
+                    next_field.as_ref(),
+                    next_max_clear_line,
+                    next_hold,
+                    next_node,
+                    left_depth - 1,
+                );
+
+                // The slots always start from the first sequence of the next node.
+                // They can be fewer than the results only when the sequence ends and the hold is used.
+                for (result, &next_result) in results.iter_mut().zip(next_results.iter()) {
+                    *result |= next_result;
+                }
+
+                if results.iter().all(|&is_success| is_success) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, V: Validator> TreeChecker<'a, V> {
+    pub fn new(mino_factory: &'a MinoFactory, validator: V, is_using_hold: bool) -> Self {
+        Self {
+            mino_factory,
+            validator,
+            is_using_hold,
+            table: TranspositionTable::default(),
+        }
+    }
+
+    pub fn get_table(&self) -> &TranspositionTable<Rc<Vec<bool>>> {
+        &self.table
+    }
+
+    // Returns whether each sequence of the tree succeeds, in the order of the sequences.
+    // The same as checking each sequence with CheckerNoHold or CheckerUsingHold.
+    pub fn check<T: Action>(
+        &mut self,
+        field: &dyn Field,
+        tree: &SequenceTree,
+        candidate: &mut dyn Candidate<T>,
+        max_clear_line: u8,
+        max_depth: u8,
+    ) -> Vec<bool> {
+        assert!(0 < max_depth);
+
+        let mut freeze = field.prune(max_clear_line);
+        let max_clear_line = max_clear_line - freeze.clear_filled_rows() as u8;
+
+        // 別の木では、同じノードでも残りのミノ列が違う
+        self.table.new_search();
+
+        let mut search = Search {
+            tree,
+            candidate,
+            table: &mut self.table,
+            mino_factory: self.mino_factory,
+            validator: &self.validator,
+            is_using_hold: self.is_using_hold,
+        };
+        let results = search.search(
+            freeze.as_ref(),
+            max_clear_line,
+            None,
+            SequenceTree::ROOT,
+            max_depth,
+        );
+        results.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use crate::{
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::common::validator::perfect_validator::PerfectValidator,
+        sfinder_core::{
+            action::candidate::candidate_facade, field::field_factory,
+            mino::mino_shifter::MinoShifter,
+        },
+    };
+
+    fn check(sequences: &[&str], is_using_hold: bool, max_depth: u8) -> Vec<bool> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+        );
+        let mut checker = TreeChecker::new(&mino_factory, PerfectValidator, is_using_hold);
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "____XXXXXX"
+            + "____XXXXXX"
+        );
+        let tree = SequenceTree::new(sequences.iter().map(|str| pieces(str)).collect());
+        checker.check(field.as_ref(), &tree, candidate.as_mut(), 2, max_depth)
+    }
+
+    #[test]
+    fn no_hold() {
+        // sorted: TT, II, LL, LJ, OO
+        assert_eq!(
+            check(&["OO", "TT", "II", "LL", "LJ"], false, 2),
+            vec![false, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn using_hold() {
+        // sorted: TTO, TOO, OTO, OOT
+        assert_eq!(
+            check(&["TOO", "OTO", "TTO", "OOT"], true, 2),
+            vec![false, true, true, true]
+        );
+    }
+
+    #[test]
+    fn using_hold_short_sequence() {
+        // sorted: TO, OT, OO
+        // the held piece is used after the sequence ends, but it cannot fill the rest
+        assert_eq!(
+            check(&["OO", "TO", "OT"], true, 2),
+            vec![false, false, true]
+        );
+    }
+
+    #[test]
+    fn reuse_table() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+        );
+        let mut checker = TreeChecker::new(&mino_factory, PerfectValidator, true);
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "____XXXXXX"
+            + "____XXXXXX"
+        );
+
+        // 同じノードでも、前の木の結果は使われない
+        // the results are in the sorted order of the sequences
+        for (sequences, expected) in [
+            (["TOO", "OTO"], vec![true, true]),
+            (["OOT", "TTO"], vec![false, true]),
+            (["TOO", "OTO"], vec![true, true]),
+        ] {
+            let tree = SequenceTree::new(sequences.iter().map(|str| pieces(str)).collect());
+            let actual = checker.check(field.as_ref(), &tree, candidate.as_mut(), 2, 2);
+            assert_eq!(actual, expected, "{sequences:?}");
+        }
+        assert!(0 < checker.get_table().get_stats().get_stores());
+    }
+}
@@ -0,0 +1,152 @@
+//! Porting note: the choices of the piece to use next, shared by the searchers that use hold.
+//! The rest of the sequence is generic, so that a slice, an index or a node of a tree can be used.
+
+use crate::sfinder_core::mino::piece::Piece;
+
+// (使うミノ, 次のホールド, 残りのミノ)
+// rest is the rest after the current piece, and nexts are the next pieces with the rest after them,
+// which are used only when the hold is empty.
+// The current piece is used first, and the same piece is never chosen twice,
+// since the result does not change when the same pieces are swapped.
+pub fn get_choices<T: Clone>(
+    current: Option<Piece>,
+    hold: Option<Piece>,
+    rest: T,
+    nexts: impl IntoIterator<Item = (Piece, T)>,
+    is_using_hold: bool,
+) -> Vec<(Piece, Option<Piece>, T)> {
+    let mut choices = Vec::with_capacity(2);
+    if let Some(current) = current {
+        choices.push((current, hold, rest.clone()));
+    }
+    if is_using_hold {
+        match hold {
+            Some(hold) if current != Some(hold) => choices.push((hold, current, rest)),
+            Some(_) => {}
+            None => {
+                for (next, next_rest) in nexts {
+                    if current != Some(next) {
+                        choices.push((next, current, next_rest));
+                    }
+                }
+            }
+        }
+    }
+    choices
+}
+
+// Same as get_choices, with the rest of the sequence as a slice
+pub fn get_choices_from_slice(
+    hold: Option<Piece>,
+    pieces: &[Piece],
+    is_using_hold: bool,
+) -> Vec<(Piece, Option<Piece>, &[Piece])> {
+    let rest = pieces.get(1..).unwrap_or_default();
+    get_choices(
+        pieces.first().copied(),
+        hold,
+        rest,
+        rest.split_first()
+            .map(|(&next, next_rest)| (next, next_rest)),
+        is_using_hold,
+    )
+}
+
+// Same as get_choices, with the index of the next piece in the sequence
+pub fn get_choices_from_index(
+    hold: Option<Piece>,
+    pieces: &[Piece],
+    index: usize,
+    is_using_hold: bool,
+) -> Vec<(Piece, Option<Piece>, usize)> {
+    get_choices(
+        pieces.get(index).copied(),
+        hold,
+        index + 1,
+        pieces.get(index + 1).map(|&next| (next, index + 2)),
+        is_using_hold,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+
+    #[test]
+    fn from_slice() {
+        let sequence = pieces("TIO");
+        assert_eq!(
+            get_choices_from_slice(None, &sequence, true),
+            vec![
+                (Piece::T, None, &sequence[1..]),
+                (Piece::I, Some(Piece::T), &sequence[2..]),
+            ]
+        );
+        assert_eq!(
+            get_choices_from_slice(Some(Piece::S), &sequence, true),
+            vec![
+                (Piece::T, Some(Piece::S), &sequence[1..]),
+                (Piece::S, Some(Piece::T), &sequence[1..]),
+            ]
+        );
+        assert_eq!(
+            get_choices_from_slice(Some(Piece::S), &sequence, false),
+            vec![(Piece::T, Some(Piece::S), &sequence[1..])]
+        );
+
+        // 同じミノは入れ替えない
+        let sequence = pieces("TT");
+        assert_eq!(
+            get_choices_from_slice(None, &sequence, true),
+            vec![(Piece::T, None, &sequence[1..])]
+        );
+        assert_eq!(
+            get_choices_from_slice(Some(Piece::T), &sequence, true),
+            vec![(Piece::T, Some(Piece::T), &sequence[1..])]
+        );
+
+        // ミノ列が終わっているときは、ホールドのミノだけを使う
+        assert_eq!(
+            get_choices_from_slice(Some(Piece::S), &[], true),
+            vec![(Piece::S, None, &[] as &[Piece])]
+        );
+        assert!(get_choices_from_slice(None, &[], true).is_empty());
+    }
+
+    #[test]
+    fn from_index() {
+        let sequence = pieces("TIO");
+        assert_eq!(
+            get_choices_from_index(None, &sequence, 1, true),
+            vec![(Piece::I, None, 2), (Piece::O, Some(Piece::I), 3)]
+        );
+        assert_eq!(
+            get_choices_from_index(None, &sequence, 2, true),
+            vec![(Piece::O, None, 3)]
+        );
+        assert_eq!(
+            get_choices_from_index(Some(Piece::Z), &sequence, 3, true),
+            vec![(Piece::Z, None, 4)]
+        );
+    }
+
+    #[test]
+    fn many_nexts() {
+        let choices = get_choices(
+            Some(Piece::T),
+            None,
+            0,
+            [(Piece::I, 1), (Piece::T, 2), (Piece::O, 3)],
+            true,
+        );
+        assert_eq!(
+            choices,
+            vec![
+                (Piece::T, None, 0),
+                (Piece::I, Some(Piece::T), 1),
+                (Piece::O, Some(Piece::T), 3),
+            ]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod hold_choices;
+pub mod parallel_executor;
+pub mod transposition_table;
+pub mod validator;
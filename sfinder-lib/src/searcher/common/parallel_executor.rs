@@ -0,0 +1,136 @@
+//! Porting note: replaces the ExecutorService used with the threads option.
+//! The items are taken one by one by the workers, so that a worker that finishes early takes over
+//! the rest of the items. Each worker creates its own state, e.g. a Candidate and its caches,
+//! which therefore does not need to be Send or Sync.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelExecutor {
+    threads: usize,
+}
+
+impl ParallelExecutor {
+    pub fn new(threads: usize) -> Self {
+        assert!(0 < threads, "Threads should be positive");
+        Self { threads }
+    }
+
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
+    // Returns the results in the order of the items, regardless of which worker processed them.
+    // The items are processed on the current thread without spawning when there is only one worker.
+    pub fn map<I, S, R>(
+        &self,
+        items: &[I],
+        create_state: impl Fn() -> S + Sync,
+        task: impl Fn(&mut S, &I) -> R + Sync,
+    ) -> Vec<R>
+    where
+        I: Sync,
+        R: Send,
+    {
+        let workers = self.threads.min(items.len());
+        if workers <= 1 {
+            let mut state = create_state();
+            return items.iter().map(|item| task(&mut state, item)).collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let mut indexed_results = thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut state = create_state();
+                        let mut results = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(index) else {
+                                break;
+                            };
+                            results.push((index, task(&mut state, item)));
+                        }
+                        results
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        indexed_results.sort_unstable_by_key(|&(index, _)| index);
+        indexed_results
+            .into_iter()
+            .map(|(_, result)| result)
+            .collect()
+    }
+}
+
+impl Default for ParallelExecutor {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Mutex, thread::ThreadId};
+
+    #[test]
+    fn keeps_order() {
+        let items = (0..1000).collect::<Vec<u64>>();
+        let expected = items.iter().map(|item| item * item).collect::<Vec<_>>();
+
+        for threads in [1, 2, 3, 8, 2000] {
+            let results = ParallelExecutor::new(threads).map(&items, || (), |_, item| item * item);
+            assert_eq!(results, expected, "{threads}");
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let results = ParallelExecutor::new(4).map(&[] as &[u8], || (), |_, &item| item);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn state_per_worker() {
+        let items = (0..200).collect::<Vec<usize>>();
+        let created = Mutex::new(Vec::<ThreadId>::new());
+
+        // 各ワーカーは自分の状態だけを使う
+        let results = ParallelExecutor::new(4).map(
+            &items,
+            || {
+                let id = thread::current().id();
+                created.lock().unwrap().push(id);
+                (id, 0)
+            },
+            |(id, count), &item| {
+                assert_eq!(*id, thread::current().id());
+                *count += 1;
+                item
+            },
+        );
+
+        assert_eq!(results, items);
+        let created = created.into_inner().unwrap();
+        assert_eq!(created.len(), 4);
+        assert!(!created.contains(&thread::current().id()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_threads() {
+        ParallelExecutor::new(0);
+    }
+}
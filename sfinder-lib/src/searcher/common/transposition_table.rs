@@ -0,0 +1,419 @@
+//! Porting note: a bounded memo of the search states, which is not in the original.
+//! A state is identified by the fingerprint of the field, the remaining pieces and the hold piece.
+//! Each key is stored in a single slot selected by its hash, and the replacement policy decides
+//! which entry is kept when two keys share a slot.
+//! The fingerprints are not compared by the fields themselves, so two different fields with the
+//! same fingerprint are treated as the same state, which is accepted as in other transposition tables.
+//! The slots are allocated on the first store and grow up to the memory cap as the entries increase,
+//! so a table that is created but barely used stays small.
+
+use crate::{
+    common::datastore::piece_counter::PieceCounter,
+    extras::hash_code::HashCode,
+    sfinder_core::{field::field::Field, mino::piece::Piece},
+};
+use std::mem;
+
+pub const DEFAULT_MAX_BYTES: usize = 1 << 24;
+// The number of slots allocated on the first store
+const INITIAL_SLOT_COUNT: usize = 1 << 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateKey {
+    fingerprint: u64,
+    piece_counter: PieceCounter,
+    hold: Option<Piece>,
+    // The rest of the state that is not in the field, the pieces or the hold, e.g. the node of a tree
+    extra: u64,
+}
+
+impl StateKey {
+    pub fn new(field: &dyn Field, piece_counter: PieceCounter, hold: Option<Piece>) -> Self {
+        Self {
+            fingerprint: field.fingerprint(),
+            piece_counter,
+            hold,
+            extra: 0,
+        }
+    }
+
+    pub fn with_extra(self, extra: u64) -> Self {
+        Self { extra, ..self }
+    }
+
+    fn get_hash(&self) -> u64 {
+        let hold = self.hold.map_or(0, |piece| piece as u64 + 1);
+        self.fingerprint
+            ^ self
+                .piece_counter
+                .hash_code()
+                .wrapping_mul(0x9e3779b97f4a7c15)
+            ^ hold.wrapping_mul(0xc2b2ae3d27d4eb4f)
+            ^ self.extra.wrapping_mul(0x165667b19e3779f9)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    // The new entry always replaces the old one
+    AlwaysReplace,
+    // The new entry replaces the old one only if it is searched at the same or a greater depth,
+    // since the deeper entry saves more work when it is hit
+    #[default]
+    DepthPreferred,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TranspositionStats {
+    hits: u64,
+    misses: u64,
+    stores: u64,
+    // The stores that overwrote the entry of another key in the same search
+    replacements: u64,
+    // The stores that were discarded by the replacement policy
+    rejections: u64,
+}
+
+impl TranspositionStats {
+    pub fn get_hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn get_misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn get_stores(&self) -> u64 {
+        self.stores
+    }
+
+    pub fn get_replacements(&self) -> u64 {
+        self.replacements
+    }
+
+    pub fn get_rejections(&self) -> u64 {
+        self.rejections
+    }
+
+    // Returns 0 if there are no lookups
+    pub fn get_hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry<V> {
+    key: StateKey,
+    generation: u32,
+    depth: u8,
+    value: V,
+}
+
+#[derive(Debug)]
+pub struct TranspositionTable<V> {
+    // Empty until the first store, and doubled while more than half of the slots are used
+    slots: Vec<Option<Entry<V>>>,
+    // The number of the slots at the memory cap
+    capacity: usize,
+    // The number of the slots that have an entry of the current search
+    used_slots: usize,
+    policy: ReplacementPolicy,
+    // The entries of the previous searches are ignored, so that the table is reused without clearing
+    generation: u32,
+    stats: TranspositionStats,
+}
+
+impl<V: Clone> TranspositionTable<V> {
+    // The number of slots is chosen so that the slots fit in max_bytes, but there is at least one slot
+    pub fn new(max_bytes: usize, policy: ReplacementPolicy) -> Self {
+        Self {
+            slots: Vec::new(),
+            capacity: (max_bytes / mem::size_of::<Option<Entry<V>>>()).max(1),
+            used_slots: 0,
+            policy,
+            generation: 0,
+            stats: TranspositionStats::default(),
+        }
+    }
+
+    // The max number of the slots, which are allocated as needed
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn get_policy(&self) -> ReplacementPolicy {
+        self.policy
+    }
+
+    // The statistics since the table is created, including the previous searches
+    pub fn get_stats(&self) -> &TranspositionStats {
+        &self.stats
+    }
+
+    // Should be called before a search whose states mean something different from the previous one,
+    // e.g. the same remaining pieces in another order
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.used_slots = 0;
+        if self.generation == 0 {
+            // 一周したときは、古い世代が残らないようにすべて消す
+            self.slots.fill(None);
+        }
+    }
+
+    fn get_index(slot_count: usize, key: &StateKey) -> usize {
+        (key.get_hash() % slot_count as u64) as usize
+    }
+
+    pub fn get(&mut self, key: &StateKey) -> Option<&V> {
+        if self.slots.is_empty() {
+            self.stats.misses += 1;
+            return None;
+        }
+
+        let index = Self::get_index(self.slots.len(), key);
+        match &self.slots[index] {
+            Some(entry) if entry.generation == self.generation && entry.key == *key => {
+                self.stats.hits += 1;
+                Some(&entry.value)
+            }
+            _ => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    // The depth is the remaining depth of the search from the state
+    pub fn insert(&mut self, key: StateKey, depth: u8, value: V) {
+        if self.slots.len() < self.capacity && self.slots.len() < self.used_slots * 2 + 2 {
+            self.grow();
+        }
+
+        let index = Self::get_index(self.slots.len(), &key);
+        let generation = self.generation;
+
+        match &self.slots[index] {
+            Some(entry) if entry.generation == generation => {
+                if entry.key != key {
+                    if self.policy == ReplacementPolicy::DepthPreferred && depth < entry.depth {
+                        self.stats.rejections += 1;
+                        return;
+                    }
+                    self.stats.replacements += 1;
+                }
+            }
+            _ => self.used_slots += 1,
+        }
+
+        self.stats.stores += 1;
+        self.slots[index] = Some(Entry {
+            key,
+            generation,
+            depth,
+            value,
+        });
+    }
+
+    // Doubles the slots, keeping the entries of the current search.
+    // The entries that fall in the same slot are kept by the replacement policy.
+    fn grow(&mut self) {
+        let slot_count = (self.slots.len() * 2)
+            .max(INITIAL_SLOT_COUNT)
+            .min(self.capacity);
+        let old_slots = mem::replace(&mut self.slots, vec![None; slot_count]);
+
+        self.used_slots = 0;
+        for entry in old_slots.into_iter().flatten() {
+            if entry.generation != self.generation {
+                continue;
+            }
+
+            let index = Self::get_index(slot_count, &entry.key);
+            match &self.slots[index] {
+                Some(other)
+                    if self.policy == ReplacementPolicy::DepthPreferred
+                        && entry.depth < other.depth => {}
+                Some(_) => self.slots[index] = Some(entry),
+                None => {
+                    self.used_slots += 1;
+                    self.slots[index] = Some(entry);
+                }
+            }
+        }
+    }
+}
+
+impl<V: Clone> Default for TranspositionTable<V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BYTES, ReplacementPolicy::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::field::field_factory;
+
+    fn key(marks: &str, pieces: &[Piece], hold: Option<Piece>) -> StateKey {
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        StateKey::new(
+            field.as_ref(),
+            PieceCounter::from(pieces.iter().copied()),
+            hold,
+        )
+    }
+
+    #[test]
+    fn get_and_insert() {
+        let mut table = TranspositionTable::<usize>::default();
+        let state = key("XXXX______", &[Piece::I, Piece::T], None);
+
+        assert_eq!(table.get(&state), None);
+        table.insert(state.clone(), 2, 3);
+        assert_eq!(table.get(&state), Some(&3));
+
+        // 置き換えのときも同じキーなら上書きされる
+        table.insert(state.clone(), 1, 4);
+        assert_eq!(table.get(&state), Some(&4));
+
+        assert_eq!(table.get(&key("XXXX______", &[Piece::I], None)), None);
+        assert_eq!(
+            table.get(&key("XXXX______", &[Piece::I, Piece::T], Some(Piece::O))),
+            None
+        );
+        assert_eq!(
+            table.get(&key("_XXXX_____", &[Piece::I, Piece::T], None)),
+            None
+        );
+
+        let stats = table.get_stats();
+        assert_eq!(
+            (stats.get_hits(), stats.get_misses(), stats.get_stores()),
+            (2, 4, 2)
+        );
+        assert_eq!(stats.get_hit_rate(), 2.0 / 6.0);
+    }
+
+    #[test]
+    fn same_blocks_in_different_board_counts() {
+        let mut table = TranspositionTable::default();
+        let small = field_factory::create_small_field_with_marks("XXXX______".to_owned());
+        let large = field_factory::create_large_field_with_marks("XXXX______".to_owned());
+
+        table.insert(
+            StateKey::new(&small, PieceCounter::from([Piece::I]), None),
+            1,
+            true,
+        );
+        assert_eq!(
+            table.get(&StateKey::new(&large, PieceCounter::from([Piece::I]), None)),
+            Some(&true)
+        );
+    }
+
+    #[test]
+    fn memory_cap() {
+        let table = TranspositionTable::<usize>::new(1 << 10, ReplacementPolicy::AlwaysReplace);
+        assert!(table.get_capacity() * mem::size_of::<Option<Entry<usize>>>() <= 1 << 10);
+        assert!(0 < table.get_capacity());
+
+        let table = TranspositionTable::<usize>::new(0, ReplacementPolicy::AlwaysReplace);
+        assert_eq!(table.get_capacity(), 1);
+    }
+
+    #[test]
+    fn lazy_allocation() {
+        let mut table = TranspositionTable::<usize>::default();
+        assert!(table.slots.is_empty());
+        assert_eq!(table.get(&key("XXXX______", &[Piece::I], None)), None);
+        assert!(table.slots.is_empty());
+
+        // 使われた分だけ広がり、上限を超えない
+        let mut table = TranspositionTable::<usize>::new(
+            4 * INITIAL_SLOT_COUNT * mem::size_of::<Option<Entry<usize>>>(),
+            ReplacementPolicy::AlwaysReplace,
+        );
+        table.insert(key("XXXX______", &[Piece::I], None), 1, 0);
+        assert_eq!(table.slots.len(), INITIAL_SLOT_COUNT);
+
+        let keys = (0..4 * INITIAL_SLOT_COUNT)
+            .map(|index| {
+                let marks = format!("{:010b}", index % 1024)
+                    .replace('0', "_")
+                    .replace('1', "X");
+                key(&marks, &[Piece::I], None).with_extra((index / 1024) as u64)
+            })
+            .collect::<Vec<_>>();
+        for (index, state) in keys.iter().enumerate() {
+            table.insert(state.clone(), 1, index);
+        }
+        assert_eq!(table.slots.len(), table.get_capacity());
+
+        // 広げたあとも、最後に入れたエントリは残っている
+        let last = keys.len() - 1;
+        assert_eq!(table.get(&keys[last]), Some(&last));
+    }
+
+    #[test]
+    fn extra() {
+        let mut table = TranspositionTable::default();
+        let state = key("XXXX______", &[Piece::I], None);
+
+        table.insert(state.clone().with_extra(1), 1, 'a');
+        assert_eq!(table.get(&state.clone().with_extra(1)), Some(&'a'));
+        assert_eq!(table.get(&state.with_extra(2)), None);
+    }
+
+    #[test]
+    fn always_replace() {
+        let mut table = TranspositionTable::new(0, ReplacementPolicy::AlwaysReplace);
+        let deep = key("XXXX______", &[Piece::I, Piece::T], None);
+        let shallow = key("XXXX______", &[Piece::I], None);
+
+        table.insert(deep.clone(), 2, 'd');
+        table.insert(shallow.clone(), 1, 's');
+        assert_eq!(table.get(&deep), None);
+        assert_eq!(table.get(&shallow), Some(&'s'));
+        assert_eq!(table.get_stats().get_replacements(), 1);
+    }
+
+    #[test]
+    fn depth_preferred() {
+        let mut table = TranspositionTable::new(0, ReplacementPolicy::DepthPreferred);
+        let deep = key("XXXX______", &[Piece::I, Piece::T], None);
+        let shallow = key("XXXX______", &[Piece::I], None);
+
+        table.insert(deep.clone(), 2, 'd');
+        table.insert(shallow.clone(), 1, 's');
+        assert_eq!(table.get(&deep), Some(&'d'));
+        assert_eq!(table.get(&shallow), None);
+        assert_eq!(table.get_stats().get_rejections(), 1);
+
+        table.insert(shallow.clone(), 2, 's');
+        assert_eq!(table.get(&shallow), Some(&'s'));
+        assert_eq!(table.get_stats().get_replacements(), 1);
+    }
+
+    #[test]
+    fn new_search() {
+        let mut table = TranspositionTable::new(0, ReplacementPolicy::DepthPreferred);
+        let deep = key("XXXX______", &[Piece::I, Piece::T], None);
+        let shallow = key("XXXX______", &[Piece::I], None);
+
+        table.insert(deep.clone(), 2, 'd');
+        table.new_search();
+        assert_eq!(table.get(&deep), None);
+
+        // 前の探索のエントリは深さに関係なく置き換えられる
+        table.insert(shallow.clone(), 1, 's');
+        assert_eq!(table.get(&shallow), Some(&'s'));
+        assert_eq!(table.get_stats().get_rejections(), 0);
+        assert_eq!(table.get_stats().get_replacements(), 0);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod perfect_validator;
+pub mod validator;
@@ -0,0 +1,66 @@
+use super::validator::Validator;
+use crate::sfinder_core::field::field::Field;
+
+pub struct PerfectValidator;
+
+impl Validator for PerfectValidator {
+    fn satisfies(&self, _field: &dyn Field, max_y: u8) -> bool {
+        max_y == 0
+    }
+
+    // 壁で区切られたそれぞれの領域で、空白の数が4の倍数であること
+    fn validate(&self, field: &dyn Field, max_clear_line: u8) -> bool {
+        let empty_count_in_column =
+            |x: u8| max_clear_line as u32 - field.get_block_count_in_column(x, max_clear_line);
+
+        let mut sum = empty_count_in_column(0);
+        for x in 1..field.get_width() {
+            if field.is_wall_between_left(x, max_clear_line) {
+                if sum % 4 != 0 {
+                    return false;
+                }
+                sum = 0;
+            }
+            sum += empty_count_in_column(x);
+        }
+
+        sum % 4 == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::field::field_factory;
+
+    #[test]
+    fn validate() {
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "____XXXXXX"
+            + "____XXXXXX"
+        );
+        assert!(PerfectValidator.validate(field.as_ref(), 2));
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "__X_XXXXXX"
+            + "__X_XXXXXX"
+        );
+        assert!(!PerfectValidator.validate(field.as_ref(), 2));
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(String::new()
+            + "___XXXXX__"
+            + "_XXXXXXX__"
+        );
+        assert!(PerfectValidator.validate(field.as_ref(), 2));
+    }
+
+    #[test]
+    fn satisfies() {
+        let field = field_factory::create_field(4);
+        assert!(PerfectValidator.satisfies(field.as_ref(), 0));
+        assert!(!PerfectValidator.satisfies(field.as_ref(), 1));
+    }
+}
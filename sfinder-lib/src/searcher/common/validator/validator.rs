@@ -0,0 +1,9 @@
+use crate::sfinder_core::field::field::Field;
+
+pub trait Validator {
+    // 最後のミノを置いたあとのフィールドが条件を満たすとき true を返却
+    fn satisfies(&self, field: &dyn Field, max_y: u8) -> bool;
+
+    // 探索を続けても条件を満たす可能性があるとき true を返却
+    fn validate(&self, field: &dyn Field, max_clear_line: u8) -> bool;
+}
@@ -0,0 +1,405 @@
+//! Porting note: replaces the search of the cover entry.
+//! Each setup is checked against every sequence, so the counts include duplicated sequences.
+
+use crate::{
+    common::{
+        cover::{
+            cover::Cover,
+            reachable::{
+                last_softdrop_reachable::LastSoftdropReachable,
+                reachable_for_cover::{ReachableForCover, ReachableForCoverWrapper},
+            },
+        },
+        datastore::full_operation_with_key::FullOperationWithKey,
+    },
+    searcher::{common::parallel_executor::ParallelExecutor, percent::percent_core::SuccessCount},
+    sfinder_core::{
+        action::{drop_type::DropType, reachable::harddrop_reachable::HarddropReachable},
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::mino_rotation::MinoRotation,
+    },
+};
+
+// Creates the reachable where only the last pieces can be softdropped, and the others are harddropped.
+// All pieces are placed by the drop type if last_softdrop is None.
+pub fn create_reachable<'a>(
+    mino_factory: &'a MinoFactory,
+    mino_shifter: &'a MinoShifter,
+    mino_rotation: &'a dyn MinoRotation,
+    max_y: u8,
+    drop_type: DropType,
+    last_softdrop: Option<u8>,
+) -> Box<dyn ReachableForCover + 'a> {
+    match last_softdrop {
+        None => Box::new(ReachableForCoverWrapper(drop_type.create_reachable(
+            mino_factory,
+            mino_shifter,
+            mino_rotation,
+            max_y,
+        ))),
+        Some(0) => Box::new(ReachableForCoverWrapper(Box::new(HarddropReachable::new(
+            max_y,
+        )))),
+        Some(allow_depth) => Box::new(LastSoftdropReachable::new(
+            mino_factory,
+            mino_shifter,
+            Box::new(HarddropReachable::new(max_y)),
+            mino_rotation,
+            max_y,
+            allow_depth,
+            drop_type.use_180_rotation(),
+        )),
+    }
+}
+
+#[derive(Debug)]
+pub struct CoverResult {
+    sequences: Vec<Vec<Piece>>,
+    // Indexed by the setup, then by the sequence
+    covered: Vec<Vec<bool>>,
+}
+
+impl CoverResult {
+    pub fn get_sequences(&self) -> &[Vec<Piece>] {
+        &self.sequences
+    }
+
+    // Whether the sequence can build the setup
+    pub fn is_covered(&self, setup: usize, sequence: usize) -> bool {
+        self.covered[setup][sequence]
+    }
+
+    pub fn get_count(&self, setup: usize) -> SuccessCount {
+        let mut count = SuccessCount::default();
+        for &is_covered in &self.covered[setup] {
+            count.add(is_covered);
+        }
+        count
+    }
+
+    // The sequences that can build at least one of the setups
+    pub fn get_union_count(&self) -> SuccessCount {
+        let mut count = SuccessCount::default();
+        for sequence in 0..self.sequences.len() {
+            count.add(self.covered.iter().any(|covered| covered[sequence]));
+        }
+        count
+    }
+
+    // The setup that the most sequences can build, the first one if tied
+    pub fn get_best(&self) -> Option<usize> {
+        (0..self.covered.len())
+            .rev()
+            .max_by_key(|&setup| self.get_count(setup).get_success())
+    }
+}
+
+pub struct CoverCore<'a> {
+    covers: Vec<Box<dyn Cover + 'a>>,
+    reachable: Box<dyn ReachableForCover + 'a>,
+    height: u8,
+    is_using_hold: bool,
+}
+
+impl<'a> CoverCore<'a> {
+    // Each cover is run with the same reachable, e.g. a ConditionCover for each condition
+    pub fn new(
+        covers: Vec<Box<dyn Cover + 'a>>,
+        reachable: Box<dyn ReachableForCover + 'a>,
+        height: u8,
+        is_using_hold: bool,
+    ) -> Self {
+        Self {
+            covers,
+            reachable,
+            height,
+            is_using_hold,
+        }
+    }
+
+    // Returns the results in the same order as the covers
+    pub fn run(
+        &mut self,
+        field: &dyn Field,
+        setups: &[Vec<FullOperationWithKey>],
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Vec<CoverResult> {
+        let sequences = sequences.into_iter().collect::<Vec<_>>();
+        let covered = sequences
+            .iter()
+            .map(|pieces| self.check(field, setups, pieces))
+            .collect();
+
+        self.to_results(setups.len(), sequences, covered)
+    }
+
+    // Porting note: the same as `run`, but the sequences are distributed to the workers of the executor.
+    // Each worker creates its own core, e.g. its covers and reachable, so they do not need to be Send.
+    // The created cores should have the same covers as this core.
+    pub fn run_parallel(
+        &self,
+        field: &dyn Field,
+        setups: &[Vec<FullOperationWithKey>],
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+        executor: &ParallelExecutor,
+        create_core: impl Fn() -> Self + Sync,
+    ) -> Vec<CoverResult> {
+        let sequences = sequences.into_iter().collect::<Vec<_>>();
+        let covered = executor.map(&sequences, &create_core, |core, pieces| {
+            core.check(field, setups, pieces)
+        });
+
+        self.to_results(setups.len(), sequences, covered)
+    }
+
+    // Whether the sequence can build each setup, indexed by the cover, then by the setup
+    fn check(
+        &mut self,
+        field: &dyn Field,
+        setups: &[Vec<FullOperationWithKey>],
+        pieces: &[Piece],
+    ) -> Vec<Vec<bool>> {
+        let Self {
+            covers,
+            reachable,
+            height,
+            is_using_hold,
+        } = self;
+
+        covers
+            .iter_mut()
+            .map(|cover| {
+                setups
+                    .iter()
+                    .map(|operations| {
+                        if *is_using_hold {
+                            cover.can_build_with_hold(
+                                field,
+                                operations,
+                                pieces,
+                                *height,
+                                reachable.as_mut(),
+                            )
+                        } else {
+                            cover.can_build(field, operations, pieces, *height, reachable.as_mut())
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Transposes the results of check for each sequence into the results of each cover
+    fn to_results(
+        &self,
+        setup_count: usize,
+        sequences: Vec<Vec<Piece>>,
+        covered: Vec<Vec<Vec<bool>>>,
+    ) -> Vec<CoverResult> {
+        (0..self.covers.len())
+            .map(|cover| CoverResult {
+                sequences: sequences.clone(),
+                covered: (0..setup_count)
+                    .map(|setup| {
+                        covered
+                            .iter()
+                            .map(|by_cover| by_cover[cover][setup])
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use crate::{
+        common::{
+            cover::condition_cover::{ConditionCover, CoverCondition},
+            parser::operation_with_key_interpreter,
+            spin_checker::SpinRule,
+        },
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::field::field_factory,
+    };
+
+    // L and Z are placed after the I clears the third row, and the Z is under the L
+    const OPERATIONS: &str = "J,0,1,0,0,3;I,0,1,2,0,4;L,L,3,1,4,11;Z,0,1,1,4,10";
+    const I_OPERATIONS: &str = "I,0,1,0,0,1;I,0,1,0,1,2;I,0,1,0,3,4;I,0,1,0,7,8";
+
+    fn run(
+        setups: &[&str],
+        sequences: &[&str],
+        is_using_hold: bool,
+        last_softdrop: Option<u8>,
+    ) -> CoverResult {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut cover_core = CoverCore::new(
+            vec![Box::new(ConditionCover::new(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation.as_ref(),
+                4,
+                false,
+                CoverCondition::Normal,
+                SpinRule::TSpin,
+            ))],
+            create_reachable(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation.as_ref(),
+                4,
+                DropType::Softdrop,
+                last_softdrop,
+            ),
+            4,
+            is_using_hold,
+        );
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX",
+        );
+        let setups = setups
+            .iter()
+            .map(|setup| operation_with_key_interpreter::parse_to_vec(setup, &mino_factory))
+            .collect::<Vec<_>>();
+
+        cover_core
+            .run(
+                field.as_ref(),
+                &setups,
+                sequences.iter().map(|sequence| pieces(sequence)),
+            )
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn softdrop() {
+        let result = run(&[OPERATIONS], &["JILZ", "JIZL", "LZJI"], false, None);
+        assert_eq!(result.get_count(0).get_success(), 2);
+        assert!(!result.is_covered(0, 2));
+
+        let result = run(&[OPERATIONS], &["JLIZ", "LZJI"], true, None);
+        assert!(result.is_covered(0, 0));
+        assert!(!result.is_covered(0, 1));
+    }
+
+    #[test]
+    fn last_softdrop() {
+        // the Z needs to be softdropped under the L
+        let result = run(&[OPERATIONS], &["JILZ", "JIZL"], false, Some(0));
+        assert!(!result.is_covered(0, 0));
+        assert!(result.is_covered(0, 1));
+
+        let result = run(&[OPERATIONS], &["JILZ", "JIZL"], false, Some(1));
+        assert!(result.is_covered(0, 0));
+        assert!(result.is_covered(0, 1));
+    }
+
+    #[test]
+    fn union_and_best() {
+        let result = run(
+            &[I_OPERATIONS, OPERATIONS],
+            &["JILZ", "JIZL", "IIII", "LZJI"],
+            false,
+            None,
+        );
+
+        assert_eq!(result.get_count(0).get_success(), 1);
+        assert_eq!(result.get_count(1).get_success(), 2);
+        assert_eq!(result.get_count(1).get_success_rate(), 0.5);
+        assert_eq!(result.get_union_count().get_success(), 3);
+        assert_eq!(result.get_union_count().get_total(), 4);
+        assert_eq!(result.get_best(), Some(1));
+
+        // the first setup is chosen when tied
+        let result = run(&[I_OPERATIONS, OPERATIONS], &["JILZ", "IIII"], false, None);
+        assert_eq!(result.get_best(), Some(0));
+    }
+
+    #[test]
+    fn parallel() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let create_core = || {
+            CoverCore::new(
+                vec![Box::new(ConditionCover::new(
+                    &mino_factory,
+                    &mino_shifter,
+                    mino_rotation.as_ref(),
+                    4,
+                    false,
+                    CoverCondition::Normal,
+                    SpinRule::TSpin,
+                ))],
+                create_reachable(
+                    &mino_factory,
+                    &mino_shifter,
+                    mino_rotation.as_ref(),
+                    4,
+                    DropType::Softdrop,
+                    None,
+                ),
+                4,
+                true,
+            )
+        };
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX"
+                + "____XXXXXX",
+        );
+        let setups = [I_OPERATIONS, OPERATIONS]
+            .iter()
+            .map(|setup| operation_with_key_interpreter::parse_to_vec(setup, &mino_factory))
+            .collect::<Vec<_>>();
+        let sequences = ["JILZ", "JIZL", "IIII", "LZJI", "ZLJI"]
+            .iter()
+            .map(|sequence| pieces(sequence))
+            .collect::<Vec<_>>();
+
+        let mut cover_core = create_core();
+        let expected = cover_core
+            .run(field.as_ref(), &setups, sequences.clone())
+            .pop()
+            .unwrap();
+        let actual = cover_core
+            .run_parallel(
+                field.as_ref(),
+                &setups,
+                sequences.clone(),
+                &ParallelExecutor::new(3),
+                create_core,
+            )
+            .pop()
+            .unwrap();
+
+        assert_eq!(actual.get_sequences(), expected.get_sequences());
+        for setup in 0..setups.len() {
+            for sequence in 0..sequences.len() {
+                assert_eq!(
+                    actual.is_covered(setup, sequence),
+                    expected.is_covered(setup, sequence)
+                );
+            }
+        }
+        assert!(0 < expected.get_union_count().get_success());
+    }
+}
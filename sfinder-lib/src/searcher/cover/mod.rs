@@ -0,0 +1 @@
+pub mod cover_core;
@@ -1,2 +1,9 @@
+pub mod checker;
+pub mod common;
+pub mod cover;
 pub mod pack;
+pub mod path;
+pub mod percent;
+pub mod ren;
+pub mod setup;
 pub mod spins;
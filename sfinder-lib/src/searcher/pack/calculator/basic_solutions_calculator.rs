@@ -0,0 +1,213 @@
+//! Porting note: replaces BasicSolutionsCalculator and OnDemandBasicSolutions.
+//! The connections and the fields of a column field are created when they are first requested,
+//! since only a few of the column fields are reached in a search.
+
+use super::{
+    connections_to_list_callable::ConnectionsToListCallable,
+    solutions_calculator::SolutionsCalculator,
+};
+use crate::{
+    searcher::pack::{
+        connections::{
+            column_field_connections::ColumnFieldConnections,
+            list_column_field_connections::ListColumnFieldConnections,
+        },
+        mino_fields::{
+            memorized_recursive_mino_fields::MemorizedRecursiveMinoFields,
+            recursive_mino_fields::RecursiveMinoFields,
+        },
+        separable_mino::separable_mino::SeparableMino,
+        sized_bit::SizedBit,
+    },
+    sfinder_core::column_field::{
+        column_field::ColumnField, column_field_factory, column_small_field::ColumnSmallField,
+    },
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+pub struct BasicSolutionsCalculator<'a> {
+    sized_bit: SizedBit,
+    // The minos grouped by the lowest bit of their column field.
+    // Porting note: the empty blocks are filled from the lowest bit, so only the minos on the lowest
+    // empty bit are connected, and each set of minos is found once.
+    minos_by_bit: Vec<Vec<&'a dyn SeparableMino>>,
+    limit_outer_field: ColumnSmallField,
+    connections: RefCell<HashMap<u64, Rc<dyn ColumnFieldConnections<'a> + 'a>>>,
+    mino_fields: RefCell<HashMap<u64, Rc<dyn RecursiveMinoFields<'a> + 'a>>>,
+}
+
+impl<'a> BasicSolutionsCalculator<'a> {
+    pub fn new(
+        separable_minos: impl IntoIterator<Item = &'a dyn SeparableMino>,
+        sized_bit: SizedBit,
+        limit_outer_field: ColumnSmallField,
+    ) -> Self {
+        let mut minos_by_bit = vec![Vec::new(); sized_bit.max_bit_digit as usize];
+        for mino in separable_minos {
+            let board = mino.get_column_field().get_board(0);
+            minos_by_bit[board.trailing_zeros() as usize].push(mino);
+        }
+
+        Self {
+            sized_bit,
+            minos_by_bit,
+            limit_outer_field,
+            connections: RefCell::new(HashMap::new()),
+            mino_fields: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<'a> SolutionsCalculator<'a> for BasicSolutionsCalculator<'a> {
+    fn is_filled(&self, column_field: &ColumnSmallField) -> bool {
+        let fill_board = self.sized_bit.fill_board;
+        column_field.get_board(0) & fill_board == fill_board
+    }
+
+    fn get_connections(
+        &self,
+        column_field: &ColumnSmallField,
+    ) -> Rc<dyn ColumnFieldConnections<'a> + 'a> {
+        let board = column_field.get_board(0);
+        if let Some(connections) = self.connections.borrow().get(&board) {
+            return Rc::clone(connections);
+        }
+
+        let lowest_empty_bit = (!board & self.sized_bit.fill_board).trailing_zeros() as usize;
+        let connections: Rc<dyn ColumnFieldConnections<'a> + 'a> =
+            Rc::new(ListColumnFieldConnections::new(
+                self.minos_by_bit[lowest_empty_bit].iter().copied(),
+                column_field,
+                &self.sized_bit,
+            ));
+
+        self.connections
+            .borrow_mut()
+            .insert(board, Rc::clone(&connections));
+        connections
+    }
+
+    fn get_recursive_mino_fields(
+        &self,
+        column_field: &ColumnSmallField,
+    ) -> Rc<dyn RecursiveMinoFields<'a> + 'a> {
+        let board = column_field.get_board(0);
+        if let Some(mino_fields) = self.mino_fields.borrow().get(&board) {
+            return Rc::clone(mino_fields);
+        }
+
+        // The fields of the following column fields are requested while the fields are created,
+        // so the map is not borrowed here
+        let mino_fields: Rc<dyn RecursiveMinoFields<'a> + 'a> = Rc::new(
+            MemorizedRecursiveMinoFields::new(ConnectionsToListCallable::new(
+                self,
+                column_field.clone(),
+                column_field_factory::create_small_field(),
+                self.limit_outer_field.clone(),
+            )),
+        );
+
+        self.mino_fields
+            .borrow_mut()
+            .insert(board, Rc::clone(&mino_fields));
+        mino_fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        searcher::pack::{
+            mino_field::mino_field::MinoField,
+            separable_mino::full_operation_separable_mino::FullOperationSeparableMino,
+            separable_minos::SeparableMinos,
+        },
+        sfinder_core::mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+    };
+
+    fn create_separable_minos(sized_bit: &SizedBit) -> Vec<FullOperationSeparableMino> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let separable_minos = SeparableMinos::new(&mino_factory, &mino_shifter, sized_bit);
+        separable_minos.create_separable_minos(&mino_factory, sized_bit.height)
+    }
+
+    #[test]
+    fn fill_inner_blocks() {
+        let sized_bit = SizedBit::new(2, 4);
+        let separable_minos = create_separable_minos(&sized_bit);
+        let calculator = BasicSolutionsCalculator::new(
+            separable_minos
+                .iter()
+                .map(|mino| mino as &dyn SeparableMino),
+            sized_bit.clone(),
+            column_field_factory::create_small_field(),
+        );
+
+        #[rustfmt::skip]
+        let init_field = column_field_factory::create_small_field_with_marks(
+            String::new()
+                + "__"
+                + "__"
+                + "X_"
+                + "X_",
+            4,
+        );
+        assert!(!calculator.is_filled(&init_field));
+
+        let mino_fields = calculator
+            .get_recursive_mino_fields(&init_field)
+            .recursive_stream()
+            .collect::<Vec<_>>();
+        assert!(!mino_fields.is_empty());
+
+        for mino_field in &mino_fields {
+            // ミノは重ならずに内側を埋めて、はみ出した部分は外側のフィールドになる
+            let mut filled = init_field.clone();
+            let mut outer_board = 0;
+            for mino in mino_field.get_separable_mino_stream() {
+                assert!(filled.can_merge(mino.get_column_field()));
+                filled.merge(mino.get_column_field());
+                outer_board |= mino.get_column_field().get_board(0) & !sized_bit.fill_board;
+            }
+            assert!(calculator.is_filled(&filled));
+            assert_eq!(mino_field.get_outer_field().get_board(0), outer_board);
+        }
+    }
+
+    #[test]
+    fn limit_outer_field() {
+        let sized_bit = SizedBit::new(3, 4);
+        let separable_minos = create_separable_minos(&sized_bit);
+
+        // 外側がすべて埋まっているときは、はみ出すミノは置けない
+        let mut limit_outer_field = column_field_factory::create_small_field();
+        for x in 3..6 {
+            for y in 0..4 {
+                limit_outer_field.set_block(x, y, 4);
+            }
+        }
+
+        let calculator = BasicSolutionsCalculator::new(
+            separable_minos
+                .iter()
+                .map(|mino| mino as &dyn SeparableMino),
+            sized_bit,
+            limit_outer_field,
+        );
+
+        let init_field = column_field_factory::create_small_field();
+        let mino_fields = calculator.get_recursive_mino_fields(&init_field);
+        assert!(mino_fields
+            .recursive_stream()
+            .all(|mino_field| mino_field.get_outer_field().get_board(0) == 0));
+        assert!(0 < mino_fields.recursive_stream().count());
+
+        // 一度作ったフィールドは使いまわされる
+        assert!(Rc::ptr_eq(
+            &mino_fields,
+            &calculator.get_recursive_mino_fields(&init_field)
+        ));
+    }
+}
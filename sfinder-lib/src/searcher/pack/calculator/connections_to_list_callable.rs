@@ -1,15 +1,36 @@
+use super::{
+    connections_to_stream_callable::ConnectionsToStreamCallable,
+    solutions_calculator::SolutionsCalculator,
+};
 use crate::{
     extras::callable::Callable,
     searcher::pack::mino_field::recursive_mino_field::RecursiveMinoField,
+    sfinder_core::column_field::column_small_field::ColumnSmallField,
 };
 
-use super::connections_to_stream_callable::ConnectionsToStreamCallable;
+pub struct ConnectionsToListCallable<'c, 'a> {
+    callable: ConnectionsToStreamCallable<'c, 'a>,
+}
 
-pub struct ConnectionsToListCallable {
-    callable: ConnectionsToStreamCallable,
+impl<'c, 'a> ConnectionsToListCallable<'c, 'a> {
+    pub fn new(
+        calculator: &'c dyn SolutionsCalculator<'a>,
+        init_column_field: ColumnSmallField,
+        outer_column_field: ColumnSmallField,
+        limit_outer_field: ColumnSmallField,
+    ) -> Self {
+        Self {
+            callable: ConnectionsToStreamCallable::new(
+                calculator,
+                init_column_field,
+                outer_column_field,
+                limit_outer_field,
+            ),
+        }
+    }
 }
 
-impl<'a> Callable<Vec<RecursiveMinoField<'a>>> for ConnectionsToListCallable {
+impl<'a> Callable<Vec<RecursiveMinoField<'a>>> for ConnectionsToListCallable<'_, 'a> {
     fn call(&self) -> Vec<RecursiveMinoField<'a>> {
         self.callable.call().collect()
     }
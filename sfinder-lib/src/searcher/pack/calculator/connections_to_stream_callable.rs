@@ -1,27 +1,88 @@
 use super::solutions_calculator::SolutionsCalculator;
 use crate::{
     extras::callable::Callable,
-    searcher::pack::mino_field::recursive_mino_field::RecursiveMinoField,
-    sfinder_core::column_field::column_small_field::ColumnSmallField,
+    searcher::pack::{
+        connections::column_field_connection::ColumnFieldConnection,
+        mino_field::{mino_field::MinoField, recursive_mino_field::RecursiveMinoField},
+    },
+    sfinder_core::column_field::{column_field::ColumnField, column_small_field::ColumnSmallField},
 };
+use std::rc::Rc;
 
-pub struct ConnectionsToStreamCallable {}
+pub struct ConnectionsToStreamCallable<'c, 'a> {
+    calculator: &'c dyn SolutionsCalculator<'a>,
+    init_column_field: ColumnSmallField,
+    // The outer blocks that are already used by the minos placed before
+    outer_column_field: ColumnSmallField,
+    // The outer blocks that no mino can stick out to
+    limit_outer_field: ColumnSmallField,
+}
 
-impl ConnectionsToStreamCallable {
+impl<'c, 'a> ConnectionsToStreamCallable<'c, 'a> {
     pub fn new(
-        calculator: Box<dyn SolutionsCalculator>,
+        calculator: &'c dyn SolutionsCalculator<'a>,
         init_column_field: ColumnSmallField,
         outer_column_field: ColumnSmallField,
         limit_outer_field: ColumnSmallField,
     ) -> Self {
-        todo!()
+        Self {
+            calculator,
+            init_column_field,
+            outer_column_field,
+            limit_outer_field,
+        }
+    }
+
+    fn parse_connection_to_mino_field(
+        &self,
+        connection: &ColumnFieldConnection<'a>,
+    ) -> Vec<RecursiveMinoField<'a>> {
+        let connection_outer_field = connection.get_outer_field();
+
+        // 外側で他のミノか置けない場所と重なるときは置けない
+        if !self.outer_column_field.can_merge(connection_outer_field)
+            || !self.limit_outer_field.can_merge(connection_outer_field)
+        {
+            return Vec::new();
+        }
+
+        let mut next_outer_field = self.outer_column_field.clone();
+        next_outer_field.merge(connection_outer_field);
+
+        let mino = connection.get_mino();
+        let inner_field = connection.get_inner_field();
+
+        // 内側が埋まったときは、このミノで終わり
+        if self.calculator.is_filled(inner_field) {
+            return vec![RecursiveMinoField::new(mino, None, next_outer_field)];
+        }
+
+        self.calculator
+            .get_recursive_mino_fields(inner_field)
+            .recursive_stream()
+            .filter(|mino_field| next_outer_field.can_merge(mino_field.get_outer_field()))
+            .map(|mino_field| {
+                let mut outer_field = next_outer_field.clone();
+                outer_field.merge(mino_field.get_outer_field());
+                RecursiveMinoField::new(mino, Some(Rc::new(mino_field)), outer_field)
+            })
+            .collect()
     }
 }
 
-impl<'a> Callable<Box<dyn Iterator<Item = RecursiveMinoField<'a>>>>
-    for ConnectionsToStreamCallable
+impl<'c, 'a> Callable<Box<dyn Iterator<Item = RecursiveMinoField<'a>> + 'c>>
+    for ConnectionsToStreamCallable<'c, 'a>
 {
-    fn call(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>>> {
-        todo!()
+    // Porting note: the connections are shared with the calculator, so the fields of each connection
+    // are collected before they are returned
+    fn call(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>> + 'c> {
+        let connections = self.calculator.get_connections(&self.init_column_field);
+
+        let mino_fields = connections
+            .get_connection_stream()
+            .flat_map(|connection| self.parse_connection_to_mino_field(connection))
+            .collect::<Vec<_>>();
+
+        Box::new(mino_fields.into_iter())
     }
 }
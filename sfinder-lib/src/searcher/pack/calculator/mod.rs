@@ -1,3 +1,4 @@
+pub mod basic_solutions_calculator;
 pub mod connections_to_list_callable;
 pub mod connections_to_stream_callable;
 pub mod solutions_calculator;
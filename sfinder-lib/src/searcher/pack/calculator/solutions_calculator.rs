@@ -1,22 +1,24 @@
 use crate::{
     searcher::pack::{
         connections::column_field_connections::ColumnFieldConnections,
-        mino_fields::recursive_mino_fields::RecursiveMinoFields, separable_minos::SeparableMinos,
+        mino_fields::recursive_mino_fields::RecursiveMinoFields,
     },
-    sfinder_core::{column_field::column_small_field::ColumnSmallField, field::field::Field},
+    sfinder_core::column_field::column_small_field::ColumnSmallField,
 };
+use std::rc::Rc;
 
-// TODO: check if ownership is necessary
-pub trait SolutionsCalculator {
-    fn get_height(&self) -> u8;
-
+// Porting note: the results are shared with Rc, since they are memorized by the calculator
+pub trait SolutionsCalculator<'a> {
     fn is_filled(&self, column_field: &ColumnSmallField) -> bool;
 
-    fn get_connections(&self, column_field: &ColumnSmallField) -> Box<dyn ColumnFieldConnections>;
-
-    fn get_inverted_outer_field(&self, outer_column_field: &ColumnSmallField) -> Box<dyn Field>;
-
-    fn get_separable_minos(&self) -> SeparableMinos<'_>;
+    fn get_connections(
+        &self,
+        column_field: &ColumnSmallField,
+    ) -> Rc<dyn ColumnFieldConnections<'a> + 'a>;
 
-    fn get_recursive_mino_fields(&self) -> Box<dyn RecursiveMinoFields>;
+    // Returns the fields whose minos fill the inner blocks that are empty in the column field
+    fn get_recursive_mino_fields(
+        &self,
+        column_field: &ColumnSmallField,
+    ) -> Rc<dyn RecursiveMinoFields<'a> + 'a>;
 }
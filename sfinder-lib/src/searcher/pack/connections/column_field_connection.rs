@@ -17,7 +17,9 @@ impl<'a> ColumnFieldConnection<'a> {
         freeze: &ColumnSmallField,
         sized_bit: &SizedBit,
     ) -> Self {
-        assert!(sized_bit.height <= 10);
+        // Porting note: the original asserts height <= 10 for the widths up to 3.
+        // The inner and outer columns only have to fit in the board of the ColumnSmallField.
+        assert!((sized_bit.width + 3) * sized_bit.height <= 64);
         let fill_board = sized_bit.fill_board;
 
         let board = freeze.get_board(0);
@@ -31,7 +33,7 @@ impl<'a> ColumnFieldConnection<'a> {
         }
     }
 
-    pub fn get_mino(&self) -> &dyn SeparableMino {
+    pub fn get_mino(&self) -> &'a dyn SeparableMino {
         self.mino
     }
 
@@ -1,5 +1,5 @@
 use super::column_field_connection::ColumnFieldConnection;
 
-pub trait ColumnFieldConnections {
-    fn get_connection_stream(&self) -> Box<dyn Iterator<Item = ColumnFieldConnection>>;
+pub trait ColumnFieldConnections<'a> {
+    fn get_connection_stream(&self) -> Box<dyn Iterator<Item = &ColumnFieldConnection<'a>> + '_>;
 }
@@ -0,0 +1,38 @@
+use super::{
+    column_field_connection::ColumnFieldConnection,
+    column_field_connections::ColumnFieldConnections,
+};
+use crate::{
+    searcher::pack::{separable_mino::separable_mino::SeparableMino, sized_bit::SizedBit},
+    sfinder_core::column_field::{column_field::ColumnField, column_small_field::ColumnSmallField},
+};
+
+pub struct ListColumnFieldConnections<'a> {
+    connections: Vec<ColumnFieldConnection<'a>>,
+}
+
+impl<'a> ListColumnFieldConnections<'a> {
+    pub fn new(
+        minos: impl IntoIterator<Item = &'a dyn SeparableMino>,
+        column_field: &ColumnSmallField,
+        sized_bit: &SizedBit,
+    ) -> Self {
+        let connections = minos
+            .into_iter()
+            .filter(|mino| column_field.can_merge(mino.get_column_field()))
+            .map(|mino| {
+                let mut freeze = column_field.clone();
+                freeze.merge(mino.get_column_field());
+                ColumnFieldConnection::new(mino, &freeze, sized_bit)
+            })
+            .collect();
+
+        Self { connections }
+    }
+}
+
+impl<'a> ColumnFieldConnections<'a> for ListColumnFieldConnections<'a> {
+    fn get_connection_stream(&self) -> Box<dyn Iterator<Item = &ColumnFieldConnection<'a>> + '_> {
+        Box::new(self.connections.iter())
+    }
+}
@@ -1,3 +1,3 @@
 pub mod column_field_connection;
 pub mod column_field_connections;
-// pub mod list_column_field_connections;
+pub mod list_column_field_connections;
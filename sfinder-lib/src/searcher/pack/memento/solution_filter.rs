@@ -2,7 +2,7 @@ use super::mino_field_memento::MinoFieldMemento;
 use crate::searcher::pack::mino_field::mino_field::MinoField;
 
 // マルチスレッドに対応していなければならない
-pub trait SolutionFilter {
+pub trait SolutionFilter: Sync {
     // memento が有効な場合は true を返却する
     fn test(&self, memento: &dyn MinoFieldMemento) -> bool;
 
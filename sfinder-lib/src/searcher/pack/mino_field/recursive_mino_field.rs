@@ -6,12 +6,14 @@ use crate::{
     },
     sfinder_core::column_field::column_small_field::ColumnSmallField,
 };
+use std::rc::Rc;
 
 // Clone is derived to allow MemorizedRecursiveMinoFields to work, as it needs to give ownership of RecursiveMinoField per the trait definition
 #[derive(Clone)]
 pub struct RecursiveMinoField<'a> {
     separable_mino: &'a dyn SeparableMino,
-    mino_field: Option<&'a dyn MinoField>,
+    // Porting note: the rest of the minos are shared between the fields that are memorized by the calculator
+    mino_field: Option<Rc<RecursiveMinoField<'a>>>,
     outer_field: ColumnSmallField,
     piece_counter: PieceCounter,
     max_separable_mino: &'a dyn SeparableMino,
@@ -20,11 +22,11 @@ pub struct RecursiveMinoField<'a> {
 impl<'a> RecursiveMinoField<'a> {
     pub fn new(
         separable_mino: &'a dyn SeparableMino,
-        mino_field: Option<&'a dyn MinoField>,
+        mino_field: Option<Rc<RecursiveMinoField<'a>>>,
         outer_field: ColumnSmallField,
     ) -> Self {
-        let (max_separable_mino, piece_counter) = if let Some(prev_field) = mino_field {
-            let prev_max = prev_field.get_max_separable_mino();
+        let (max_separable_mino, piece_counter) = if let Some(prev_field) = &mino_field {
+            let prev_max = prev_field.max_separable_mino;
 
             (
                 match SeparableMinos::compare_index(separable_mino, prev_max) {
@@ -79,11 +81,13 @@ impl Traversable for RecursiveMinoField<'_> {
 
     fn get_next(&self) -> Option<&dyn MinoField> {
         self.mino_field
+            .as_deref()
+            .map(|mino_field| mino_field as &dyn MinoField)
     }
 }
 
 impl PartialEq for RecursiveMinoField<'_> {
     fn eq(&self, other: &Self) -> bool {
-        self.mino_field == other.mino_field
+        (self as &dyn MinoField) == (other as &dyn MinoField)
     }
 }
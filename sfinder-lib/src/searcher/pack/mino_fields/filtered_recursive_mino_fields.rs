@@ -5,16 +5,16 @@ use crate::searcher::pack::{
     memento::solution_filter::SolutionFilter, mino_field::recursive_mino_field::RecursiveMinoField,
 };
 
-pub struct FilteredRecursiveMinoFields {
-    mino_fields: Box<dyn RecursiveMinoFields>,
+pub struct FilteredRecursiveMinoFields<'a> {
+    mino_fields: Box<dyn RecursiveMinoFields<'a> + 'a>,
     filter: Box<dyn SolutionFilter>,
 }
 
-impl FilteredRecursiveMinoFields {
+impl<'a> FilteredRecursiveMinoFields<'a> {
     pub fn new(
-        mino_fields: Box<dyn RecursiveMinoFields>,
+        mino_fields: Box<dyn RecursiveMinoFields<'a> + 'a>,
         filter: Box<dyn SolutionFilter>,
-    ) -> FilteredRecursiveMinoFields {
+    ) -> FilteredRecursiveMinoFields<'a> {
         FilteredRecursiveMinoFields {
             mino_fields,
             filter,
@@ -22,8 +22,8 @@ impl FilteredRecursiveMinoFields {
     }
 }
 
-impl RecursiveMinoFields for FilteredRecursiveMinoFields {
-    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField> + '_> {
+impl<'a> RecursiveMinoFields<'a> for FilteredRecursiveMinoFields<'a> {
+    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>> + '_> {
         Box::new(
             self.mino_fields
                 .recursive_stream()
@@ -5,33 +5,28 @@ use crate::{
         mino_field::recursive_mino_field::RecursiveMinoField,
     },
 };
-use std::cell::OnceCell;
 
 use super::recursive_mino_fields::RecursiveMinoFields;
 
 pub struct MemorizedRecursiveMinoFields<'a> {
-    callable: ConnectionsToListCallable,
-    result: OnceCell<Vec<RecursiveMinoField<'a>>>,
+    result: Vec<RecursiveMinoField<'a>>,
 }
 
 impl<'a> MemorizedRecursiveMinoFields<'a> {
-    pub fn new(callable: ConnectionsToListCallable) -> Self {
+    // Porting note: the original calls the callable when the fields are first streamed.
+    // The calculator already creates the fields when they are first requested, and keeps them,
+    // so the callable, which borrows the calculator, is called here instead of being kept.
+    pub fn new(callable: ConnectionsToListCallable<'_, 'a>) -> Self {
         Self {
-            callable,
-            result: OnceCell::new(),
+            result: callable.call(),
         }
     }
 }
 
-impl RecursiveMinoFields for MemorizedRecursiveMinoFields<'_> {
+impl<'a> RecursiveMinoFields<'a> for MemorizedRecursiveMinoFields<'a> {
     // TODO: because this struct retains ownership of RecursiveMinoField, we need to clone to return the iterator with the right signature
     // try to see of modifying RecursiveMinoFields is possible
-    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField> + '_> {
-        Box::new(
-            self.result
-                .get_or_init(|| self.callable.call())
-                .iter()
-                .cloned(),
-        )
+    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>> + '_> {
+        Box::new(self.result.iter().cloned())
     }
 }
@@ -8,18 +8,18 @@ use crate::{
 
 use super::recursive_mino_fields::RecursiveMinoFields;
 
-pub struct OnDemandRecursiveMinoFields {
-    callable: ConnectionsToStreamCallable,
+pub struct OnDemandRecursiveMinoFields<'c, 'a> {
+    callable: ConnectionsToStreamCallable<'c, 'a>,
 }
 
-impl OnDemandRecursiveMinoFields {
-    pub fn new(callable: ConnectionsToStreamCallable) -> Self {
+impl<'c, 'a> OnDemandRecursiveMinoFields<'c, 'a> {
+    pub fn new(callable: ConnectionsToStreamCallable<'c, 'a>) -> Self {
         Self { callable }
     }
 }
 
-impl RecursiveMinoFields for OnDemandRecursiveMinoFields {
-    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField> + '_> {
+impl<'a> RecursiveMinoFields<'a> for OnDemandRecursiveMinoFields<'_, 'a> {
+    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>> + '_> {
         self.callable.call()
     }
 }
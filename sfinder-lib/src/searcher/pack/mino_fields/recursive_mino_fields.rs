@@ -5,11 +5,11 @@ use crate::searcher::pack::mino_field::{
     mino_field::MinoField, recursive_mino_field::RecursiveMinoField,
 };
 
-pub trait RecursiveMinoFields {
-    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField> + '_>;
+pub trait RecursiveMinoFields<'a> {
+    fn recursive_stream(&self) -> Box<dyn Iterator<Item = RecursiveMinoField<'a>> + '_>;
 }
 
-impl MinoFields for dyn RecursiveMinoFields + '_ {
+impl<'a> MinoFields for dyn RecursiveMinoFields<'a> + 'a {
     // Porting note: refactored implementation here, since all implementors directly call recursiveStream anyways
     // TODO: is this needed? Can I just use RecursiveMinoFields directly to avoid the boxing?
     fn stream(&self) -> Box<dyn Iterator<Item = Box<dyn MinoField + '_>> + '_> {
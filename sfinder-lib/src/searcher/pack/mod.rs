@@ -4,6 +4,7 @@ pub mod in_out_pair_field;
 pub mod memento;
 pub mod mino_field;
 pub mod mino_fields;
+pub mod perfect_pack_searcher;
 pub mod separable_mino;
 pub mod separable_minos;
 pub mod sized_bit;
@@ -0,0 +1,377 @@
+//! Porting note: replaces PerfectPackSearcher with the TaskResultHelper it uses.
+//! The field is split into the InOutPairFields of the SizedBit, and the empty blocks of each pair
+//! are filled with the RecursiveMinoFields of the BasicSolutionsCalculator, whose minos may stick
+//! out into the outer columns. The minos that stick out are carried over to the following pairs.
+//! The columns right of the last pair are filled as another pair whose minos must not stick out
+//! of the field, which replaces the TaskResultHelper.
+
+use super::{
+    calculator::{
+        basic_solutions_calculator::BasicSolutionsCalculator,
+        solutions_calculator::SolutionsCalculator,
+    },
+    in_out_pair_field::InOutPairField,
+    mino_field::mino_field::MinoField,
+    separable_mino::{
+        full_operation_separable_mino::FullOperationSeparableMino, separable_mino::SeparableMino,
+    },
+    separable_minos::SeparableMinos,
+    sized_bit::SizedBit,
+};
+use crate::{
+    common::datastore::full_operation_with_key::FullOperationWithKey,
+    sfinder_core::{
+        column_field::{
+            column_field::ColumnField, column_field_factory, column_small_field::ColumnSmallField,
+        },
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+    },
+};
+
+pub struct PerfectPackSearcher<'a> {
+    mino_factory: &'a MinoFactory,
+    sized_bit: SizedBit,
+    separable_minos: Vec<FullOperationSeparableMino>,
+    in_out_pair_fields: Vec<InOutPairField>,
+    // The outer blocks that are filled in every pair, which no mino can stick out to
+    limit_outer_field: ColumnSmallField,
+}
+
+impl<'a> PerfectPackSearcher<'a> {
+    // The 2 columns of a block and the 3 outer columns should fit in a ColumnSmallField
+    pub const MAX_HEIGHT: u8 = 12;
+
+    // Same as the original, 3 columns are used for each block when height <= 4, otherwise 2 columns
+    pub fn get_sized_bit(height: u8) -> Result<SizedBit, String> {
+        match height {
+            1..=4 => Ok(SizedBit::new(3, height)),
+            5..=Self::MAX_HEIGHT => Ok(SizedBit::new(2, height)),
+            _ => Err(format!(
+                "Height should be between 1 and {}: {height}",
+                Self::MAX_HEIGHT
+            )),
+        }
+    }
+
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &MinoShifter,
+        field: &dyn Field,
+        height: u8,
+    ) -> Result<Self, String> {
+        let sized_bit = Self::get_sized_bit(height)?;
+        let separable_minos = SeparableMinos::new(mino_factory, mino_shifter, &sized_bit)
+            .create_separable_minos(mino_factory, height);
+
+        let in_out_pair_fields = InOutPairField::create_in_out_pair_fields(&sized_bit, field);
+        let limit_outer_field = InOutPairField::create_max_outer_board(&sized_bit, field);
+
+        Ok(Self {
+            mino_factory,
+            sized_bit,
+            separable_minos,
+            in_out_pair_fields,
+            limit_outer_field,
+        })
+    }
+
+    // Returns all sets of minos that fill the empty blocks below the height.
+    // The operations are ordered by the pairs, from left to right.
+    pub fn search(&self) -> Vec<Vec<FullOperationWithKey>> {
+        let calculator = BasicSolutionsCalculator::new(
+            self.separable_minos
+                .iter()
+                .map(|mino| mino as &dyn SeparableMino),
+            self.sized_bit.clone(),
+            self.limit_outer_field.clone(),
+        );
+
+        let mut used = Vec::new();
+        let mut solutions = Vec::new();
+        self.search_pair(&calculator, 0, 0, &mut used, &mut solutions);
+        solutions
+    }
+
+    // The carried board has the blocks of the minos that stick out of the previous pairs,
+    // relative to the pair
+    fn search_pair(
+        &self,
+        calculator: &dyn SolutionsCalculator,
+        index: usize,
+        carried_board: u64,
+        used: &mut Vec<FullOperationWithKey>,
+        solutions: &mut Vec<Vec<FullOperationWithKey>>,
+    ) {
+        let fill_board = self.sized_bit.fill_board;
+        let max_bit_digit = self.sized_bit.max_bit_digit;
+
+        let Some(pair) = self.in_out_pair_fields.get(index) else {
+            // 最後のペアの外側を埋める
+            let last_outer_board = self
+                .in_out_pair_fields
+                .last()
+                .map_or(0, |pair| pair.get_outer().get_board(0) >> max_bit_digit);
+            let init_field = column_field_factory::create_small_field_from_inner(
+                (last_outer_board | carried_board) & fill_board,
+            );
+
+            self.search_last(calculator, index, &init_field, used, solutions);
+            return;
+        };
+
+        let mut init_field = pair.get_inner().clone();
+        init_field.merge(&column_field_factory::create_small_field_from_inner(
+            carried_board & fill_board,
+        ));
+
+        let carried_outer_board = carried_board & !fill_board;
+        let mut outer_field = pair.get_outer().clone();
+        outer_field.merge(&column_field_factory::create_small_field_from_inner(
+            carried_outer_board,
+        ));
+
+        if calculator.is_filled(&init_field) {
+            let next_carried_board = carried_outer_board >> max_bit_digit;
+            self.search_pair(calculator, index + 1, next_carried_board, used, solutions);
+            return;
+        }
+
+        for mino_field in calculator
+            .get_recursive_mino_fields(&init_field)
+            .recursive_stream()
+        {
+            if !outer_field.can_merge(mino_field.get_outer_field()) {
+                continue;
+            }
+
+            let prev_len = used.len();
+            self.push_operations(&mino_field, index, used);
+
+            let next_carried_board =
+                (carried_outer_board | mino_field.get_outer_field().get_board(0)) >> max_bit_digit;
+            self.search_pair(calculator, index + 1, next_carried_board, used, solutions);
+            used.truncate(prev_len);
+        }
+    }
+
+    fn search_last(
+        &self,
+        calculator: &dyn SolutionsCalculator,
+        index: usize,
+        init_field: &ColumnSmallField,
+        used: &mut Vec<FullOperationWithKey>,
+        solutions: &mut Vec<Vec<FullOperationWithKey>>,
+    ) {
+        if calculator.is_filled(init_field) {
+            solutions.push(used.clone());
+            return;
+        }
+
+        // フィールドの外にはみ出すミノは置けない
+        for mino_field in calculator
+            .get_recursive_mino_fields(init_field)
+            .recursive_stream()
+            .filter(|mino_field| mino_field.get_outer_field().get_board(0) == 0)
+        {
+            let prev_len = used.len();
+            self.push_operations(&mino_field, index, used);
+            solutions.push(used.clone());
+            used.truncate(prev_len);
+        }
+    }
+
+    fn push_operations(
+        &self,
+        mino_field: &dyn MinoField,
+        index: usize,
+        used: &mut Vec<FullOperationWithKey>,
+    ) {
+        let offset = index as u8 * self.sized_bit.width;
+        used.extend(mino_field.get_operations_stream().map(|operation| {
+            FullOperationWithKey::new(
+                self.mino_factory
+                    .get(operation.get_piece(), operation.get_rotate()),
+                operation.get_x() + offset,
+                operation.get_y(),
+                operation.get_need_deleted_key(),
+                operation.get_using_key(),
+            )
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::datastore::{
+            action::action::Action, mino_operation_with_key::MinoOperationWithKey,
+            operation::Operation, operation_with_key::OperationWithKey,
+        },
+        sfinder_core::{
+            field::{field_constants::FIELD_WIDTH, field_factory},
+            mino::piece::Piece,
+        },
+    };
+
+    fn search(marks: String, height: u8) -> Vec<Vec<FullOperationWithKey>> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let field = field_factory::create_field_with_marks(marks);
+
+        PerfectPackSearcher::new(&mino_factory, &mino_shifter, field.as_ref(), height)
+            .unwrap()
+            .search()
+    }
+
+    fn assert_fills(marks: String, height: u8, solutions: &[Vec<FullOperationWithKey>]) {
+        let field = field_factory::create_field_with_marks(marks);
+        for solution in solutions {
+            let mut filled = field.prune(height);
+            for operation in solution {
+                let mino_field = operation.create_mino_field(height);
+                assert!(filled.can_merge(mino_field.as_ref()));
+                filled.merge(mino_field.as_ref());
+            }
+            assert_eq!(filled.clear_filled_rows(), height as u32);
+        }
+    }
+
+    #[test]
+    fn search_2x4() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+        let solutions = search(marks.clone(), 2);
+        assert_fills(marks, 2, &solutions);
+
+        // II, OO, LL and JJ
+        assert_eq!(solutions.len(), 4);
+        let count_pieces = |piece| {
+            solutions
+                .iter()
+                .filter(|solution| solution.iter().all(|op| op.get_piece() == piece))
+                .count()
+        };
+        assert_eq!(count_pieces(Piece::I), 1);
+        assert_eq!(count_pieces(Piece::O), 1);
+        assert_eq!(count_pieces(Piece::L), 1);
+        assert_eq!(count_pieces(Piece::J), 1);
+    }
+
+    #[test]
+    fn search_with_deleted_rows() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXXXXX_"
+            + "XXXXXXXXX_"
+            + "XXXXXXXX__";
+        let solutions = search(marks.clone(), 4);
+        assert_fills(marks, 4, &solutions);
+
+        // the vertical I clears the middle rows, which the other mino is placed over
+        assert!(solutions.iter().any(|solution| solution
+            .iter()
+            .any(|op| op.get_piece() == Piece::I && op.get_x() == 9)
+            && solution.iter().any(|op| op.get_need_deleted_key() != 0)));
+    }
+
+    #[test]
+    fn search_unique() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____";
+        let solutions = search(marks.clone(), 4);
+        assert_fills(marks, 4, &solutions);
+
+        let keys = solutions
+            .iter()
+            .map(|solution| {
+                let mut keys = solution
+                    .iter()
+                    .map(|op| op.to_unique_key_with_delete_key())
+                    .collect::<Vec<_>>();
+                keys.sort();
+                keys
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(keys.len(), solutions.len());
+    }
+
+    // Fills the field from the lowest empty block of the leftmost column, without splitting blocks
+    fn count_by_brute_force(field: &dyn Field, minos: &[Box<dyn Field>], height: u8) -> usize {
+        let Some((x, y)) = (0..FIELD_WIDTH)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .find(|&(x, y)| field.is_empty_block(x, y))
+        else {
+            return 1;
+        };
+
+        minos
+            .iter()
+            .filter(|mino| mino.exists_block(x, y) && field.can_merge(mino.as_ref()))
+            .map(|mino| {
+                let mut next_field = field.prune(height);
+                next_field.merge(mino.as_ref());
+                count_by_brute_force(next_field.as_ref(), minos, height)
+            })
+            .sum()
+    }
+
+    fn assert_same_as_brute_force(marks: String, height: u8) {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+
+        let sized_bit = SizedBit::new(FIELD_WIDTH, height);
+        let minos = SeparableMinos::new(&mino_factory, &mino_shifter, &sized_bit)
+            .get_minos()
+            .filter(|op| op.get_x() as i8 + op.get_mino().get_max_x() < FIELD_WIDTH as i8)
+            .map(|op| op.create_mino_field(height))
+            .collect::<Vec<_>>();
+
+        let field = field_factory::create_field_with_marks(marks.clone());
+
+        let solutions = search(marks.clone(), height);
+        assert_fills(marks, height, &solutions);
+        assert!(!solutions.is_empty());
+        assert_eq!(
+            solutions.len(),
+            count_by_brute_force(field.as_ref(), &minos, height)
+        );
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXXXX__"
+            + "XXXXXXXX__"
+            + "XXXXXX____";
+        assert_same_as_brute_force(marks, 4);
+    }
+
+    #[test]
+    fn same_as_brute_force_2x6() {
+        // 2列ずつのペアと、最後の2列に置かれるミノ
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXX_XXXX_"
+            + "XXX___XXX_"
+            + "XXXXXXXXX_"
+            + "XXXXXXXXX_"
+            + "XXXXX____X"
+            + "XXXX__X__X";
+        assert_same_as_brute_force(marks, 6);
+    }
+
+    #[test]
+    fn invalid_height() {
+        assert!(PerfectPackSearcher::get_sized_bit(0).is_err());
+        assert!(PerfectPackSearcher::get_sized_bit(13).is_err());
+        assert_eq!(PerfectPackSearcher::get_sized_bit(5).unwrap().width, 2);
+    }
+}
@@ -24,7 +24,7 @@ pub struct FullOperationSeparableMino {
 
 impl<'a> FullOperationSeparableMino {
     pub fn new(operation_with_key: FullOperationWithKey, upper_y: u8, field_height: u8) -> Self {
-        assert!(upper_y <= 10);
+        assert!(upper_y < field_height);
 
         let x = operation_with_key.get_x();
         let y = operation_with_key.get_y();
@@ -40,7 +40,7 @@ impl<'a> FullOperationSeparableMino {
             for nx in u8::try_from(x as i8 + mino.get_min_x()).unwrap()
                 ..=u8::try_from(x as i8 + mino.get_max_x()).unwrap()
             {
-                if mask.exists_block(x, y) {
+                if mask.exists_block(nx, ny) {
                     column_small_field.set_block(nx, ny, field_height);
                 }
             }
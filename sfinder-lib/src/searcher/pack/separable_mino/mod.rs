@@ -1,5 +1,4 @@
 pub mod all_separable_mino_factory;
-#[deprecated = "see issue #20"]
 pub mod full_operation_separable_mino;
 pub mod mask;
 pub mod separable_mino;
@@ -1,6 +1,14 @@
-use super::{separable_mino::separable_mino::SeparableMino, sized_bit::SizedBit};
+use super::{
+    separable_mino::{
+        full_operation_separable_mino::FullOperationSeparableMino, separable_mino::SeparableMino,
+    },
+    sized_bit::SizedBit,
+};
 use crate::{
-    common::datastore::mino_operation_with_key::MinoOperationWithKey,
+    common::datastore::{
+        full_operation_with_key::FullOperationWithKey,
+        mino_operation_with_key::MinoOperationWithKey,
+    },
     searcher::pack::separable_mino::all_separable_mino_factory,
     sfinder_core::{
         field::key_operators,
@@ -46,10 +54,41 @@ impl<'a> SeparableMinos<'a> {
         ))
     }
 
-    fn get_minos(&self) -> impl Iterator<Item = &Box<dyn MinoOperationWithKey + 'a>> {
+    pub fn get_minos(&self) -> impl Iterator<Item = &Box<dyn MinoOperationWithKey + 'a>> {
         self.indexes.iter()
     }
 
+    // Porting note: the original keeps the SeparableMinos themselves, so they are created from the operations here.
+    // The SeparableMinos are in the same order as the operations.
+    pub fn create_separable_minos(
+        &self,
+        mino_factory: &MinoFactory,
+        field_height: u8,
+    ) -> Vec<FullOperationSeparableMino> {
+        self.get_minos()
+            .map(|operation| {
+                let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+                let need_deleted_key = operation.get_need_deleted_key();
+
+                // 消去されるラインはすべて一番上のブロックより下にある
+                let upper_y = u8::try_from(operation.get_y() as i8 + mino.get_max_y()).unwrap()
+                    + need_deleted_key.count_ones() as u8;
+
+                FullOperationSeparableMino::new(
+                    FullOperationWithKey::new(
+                        mino,
+                        operation.get_x(),
+                        operation.get_y(),
+                        need_deleted_key,
+                        operation.get_using_key(),
+                    ),
+                    upper_y,
+                    field_height,
+                )
+            })
+            .collect()
+    }
+
     // Porting note: partially replaces getIndex, used when comparing two SeparableMinos.
     // TODO(#14): phase out this and compare the MinoOperationWithKey directly through the Ord trait
     pub fn compare_index(a: &dyn SeparableMino, b: &dyn SeparableMino) -> std::cmp::Ordering {
@@ -92,6 +131,7 @@ mod tests {
     use crate::{
         searcher::pack::sized_bit::SizedBit,
         sfinder_core::{
+            column_field::column_field::ColumnField,
             field::field_constants::FIELD_WIDTH,
             mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
         },
@@ -136,6 +176,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_separable_minos() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+
+        for sized_bit in [SizedBit::new(3, 4), SizedBit::new(2, 5)] {
+            let height = sized_bit.height;
+            let separable_minos = SeparableMinos::new(&mino_factory, &mino_shifter, &sized_bit);
+            let minos = separable_minos.create_separable_minos(&mino_factory, height);
+            assert_eq!(minos.len(), separable_minos.get_minos().count());
+
+            // 列のフィールドにはミノのブロックだけがある
+            for mino in &minos {
+                let mino_field = mino.get_mino_operation_with_key().create_mino_field(height);
+                for x in 0..sized_bit.width + 3 {
+                    for y in 0..height {
+                        assert_eq!(
+                            mino.get_column_field().is_empty_block(x, y, height),
+                            mino_field.is_empty_block(x, y),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn to_index() {
         // Porting note: checking consistency of index doesn't make sense when the index is elided, so I am checking the consistency of compare_index with Ord
@@ -0,0 +1,2 @@
+pub mod path_core;
+pub mod path_result;
@@ -0,0 +1,333 @@
+//! Porting note: replaces PathCore. The solutions found by PerfectPackSearcher are kept
+//! if they can be built in the order of at least one of the sequences.
+
+use super::path_result::{PathResult, PathSolution};
+use crate::{
+    common::{
+        buildup::build_up,
+        datastore::{
+            full_operation_with_key::FullOperationWithKey, operation::Operation,
+            piece_counter::PieceCounter,
+        },
+    },
+    searcher::{pack::perfect_pack_searcher::PerfectPackSearcher, percent::percent_core},
+    sfinder_core::{
+        action::{drop_type::DropType, reachable::reachable::Reachable},
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::mino_rotation::MinoRotation,
+    },
+};
+use std::collections::HashSet;
+
+pub struct PathCore<'a> {
+    mino_factory: &'a MinoFactory,
+    mino_shifter: &'a MinoShifter,
+    reachable: Box<dyn Reachable + 'a>,
+    max_clear_line: u8,
+    is_using_hold: bool,
+}
+
+impl<'a> PathCore<'a> {
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_clear_line: u8,
+        is_using_hold: bool,
+        drop_type: DropType,
+    ) -> Self {
+        Self {
+            mino_factory,
+            mino_shifter,
+            reachable: drop_type.create_reachable(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_clear_line,
+            ),
+            max_clear_line,
+            is_using_hold,
+        }
+    }
+
+    // Returns the distinct sequences truncated to the pieces that can be used
+    fn prepare_sequences(
+        &self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Result<Vec<Vec<Piece>>, String> {
+        let max_depth = percent_core::get_max_depth(field, self.max_clear_line)? as usize;
+        let used_length = max_depth + self.is_using_hold as usize;
+
+        let mut visited = HashSet::new();
+        let mut prepared = Vec::new();
+        for sequence in sequences {
+            if sequence.len() < max_depth {
+                return Err(format!(
+                    "Sequence should have at least {max_depth} pieces: {}",
+                    sequence.len()
+                ));
+            }
+
+            let pieces = sequence[..sequence.len().min(used_length)].to_vec();
+            if visited.insert(pieces.clone()) {
+                prepared.push(pieces);
+            }
+        }
+
+        Ok(prepared)
+    }
+
+    // Whether the operations can be built in the order of the sequence
+    fn can_build(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        counter: &PieceCounter,
+        pieces: &[Piece],
+    ) -> bool {
+        let pieces_counter = PieceCounter::from(pieces.iter().copied());
+
+        if self.is_using_hold {
+            pieces_counter.contains_all(counter)
+                && build_up::exists_valid_by_order_with_hold(
+                    self.mino_factory,
+                    field,
+                    operations,
+                    pieces,
+                    self.max_clear_line,
+                    self.reachable.as_mut(),
+                )
+        } else {
+            pieces_counter == *counter
+                && build_up::exists_valid_by_order(
+                    self.mino_factory,
+                    field,
+                    operations,
+                    pieces,
+                    self.max_clear_line,
+                    self.reachable.as_mut(),
+                )
+        }
+    }
+
+    // Returns all sets of operations that fill the field below max_clear_line,
+    // and can be built by at least one of the sequences, with the sequences that can build each of them.
+    // The indexes of the covered sequences point into the distinct sequences of the result.
+    pub fn run(
+        &mut self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Result<PathResult, String> {
+        let sequences = self.prepare_sequences(field, sequences)?;
+        let searcher = PerfectPackSearcher::new(
+            self.mino_factory,
+            self.mino_shifter,
+            field,
+            self.max_clear_line,
+        )?;
+
+        let solutions = searcher
+            .search()
+            .into_iter()
+            .filter_map(|operations| {
+                let counter = PieceCounter::from(operations.iter().map(|op| op.get_piece()));
+                let covered_sequences = (0..sequences.len())
+                    .filter(|&index| {
+                        self.can_build(field, &operations, &counter, &sequences[index])
+                    })
+                    .collect::<Vec<_>>();
+
+                (!covered_sequences.is_empty())
+                    .then(|| PathSolution::new(operations, covered_sequences))
+            })
+            .collect();
+
+        Ok(PathResult::new(sequences, solutions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{
+            datastore::operation_with_key::OperationWithKey,
+            parser::operation_with_key_interpreter, pattern::pattern_generator::PatternGenerator,
+        },
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{action::reachable::reachable_facade, field::field_factory},
+    };
+
+    fn run(
+        marks: &str,
+        max_clear_line: u8,
+        is_using_hold: bool,
+        pattern: &str,
+    ) -> Vec<Vec<FullOperationWithKey>> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut path_core = PathCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            max_clear_line,
+            is_using_hold,
+            DropType::Softdrop,
+        );
+
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        let generator = pattern.parse::<PatternGenerator>().unwrap();
+        path_core
+            .run(field.as_ref(), generator.iter())
+            .unwrap()
+            .get_unique()
+            .iter()
+            .map(|solution| solution.get_operations().to_vec())
+            .collect()
+    }
+
+    fn to_keys(operations: &[FullOperationWithKey]) -> Vec<u64> {
+        let mut keys = operations
+            .iter()
+            .map(|op| op.to_unique_key_with_delete_key())
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn path_2x4() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+
+        // II, OO, LL and JJ
+        assert_eq!(run(&marks, 2, false, "*,*").len(), 4);
+        // the same piece is needed twice
+        assert!(run(&marks, 2, false, "*p2").is_empty());
+        assert_eq!(run(&marks, 2, false, "I,*").len(), 1);
+        assert_eq!(run(&marks, 2, true, "[TO]p2,*p1").len(), 1);
+    }
+
+    #[test]
+    fn path_with_deleted_rows() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "____XXXXXX"
+            + "____XXXXXX"
+            + "____XXXXXX"
+            + "____XXXXXX";
+
+        // the L and the Z are placed after the I clears the third row
+        let mino_factory = MinoFactory::new();
+        let expected = to_keys(&operation_with_key_interpreter::parse_to_vec(
+            "J,0,1,0,0,3;I,0,1,2,0,4;L,L,3,1,4,11;Z,0,1,1,4,10",
+            &mino_factory,
+        ));
+
+        let contains = |solutions: Vec<Vec<FullOperationWithKey>>| {
+            solutions
+                .iter()
+                .any(|solution| to_keys(solution) == expected)
+        };
+
+        assert!(contains(run(&marks, 4, false, "J,I,L,Z")));
+        assert!(!contains(run(&marks, 4, false, "L,Z,J,I")));
+        assert!(contains(run(&marks, 4, true, "J,L,I,Z")));
+    }
+
+    #[test]
+    fn solutions_are_buildable() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____";
+        let field = field_factory::create_field_with_marks(marks.clone());
+        let solutions = run(&marks, 4, true, "*p5");
+        assert!(!solutions.is_empty());
+
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut reachable = reachable_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+        );
+        for solution in &solutions {
+            assert_eq!(solution.len(), 4);
+            assert!(build_up::cans_build(
+                &mino_factory,
+                field.as_ref(),
+                solution,
+                4,
+                reachable.as_mut()
+            ));
+        }
+    }
+
+    #[test]
+    fn coverage() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut path_core = PathCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+            true,
+            DropType::Softdrop,
+        );
+
+        let field =
+            field_factory::create_field_with_marks(String::new() + "____XXXXXX" + "____XXXXXX");
+        let generator = "*,*,*".parse::<PatternGenerator>().unwrap();
+        let result = path_core.run(field.as_ref(), generator.iter()).unwrap();
+
+        assert_eq!(result.get_unique().len(), 4);
+        for solution in result.get_unique() {
+            let piece = solution.get_operations()[0].get_piece();
+            let expected = result
+                .get_sequences()
+                .iter()
+                .filter(|sequence| sequence.iter().filter(|&&p| p == piece).count() >= 2)
+                .count();
+            assert_eq!(solution.get_coverage_count(), expected);
+        }
+
+        // a sequence of 3 pieces has at most one piece twice, so no sequence is covered twice
+        assert_eq!(result.get_minimal().len(), 4);
+        assert_eq!(
+            result.get_covered_count(),
+            result
+                .get_unique()
+                .iter()
+                .map(|solution| solution.get_coverage_count())
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn short_sequence() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut path_core = PathCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+            false,
+            DropType::Softdrop,
+        );
+
+        let field =
+            field_factory::create_field_with_marks(String::new() + "____XXXXXX" + "____XXXXXX");
+        assert!(path_core.run(field.as_ref(), [vec![Piece::O]]).is_err());
+    }
+}
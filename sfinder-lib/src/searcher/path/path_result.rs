@@ -0,0 +1,335 @@
+//! Porting note: replaces the unique and minimal outputs of the path entry.
+//! The minimal solutions are the smallest set of solutions that covers every sequence covered by
+//! any of the solutions. The set is chosen greedily first, and then a smaller set is searched
+//! exactly until the search runs out of its budget.
+
+use crate::{
+    common::datastore::full_operation_with_key::FullOperationWithKey,
+    sfinder_core::mino::piece::Piece,
+};
+use std::cmp::Reverse;
+
+// The number of states searched for a smaller set than the greedy one
+pub const DEFAULT_MINIMAL_BUDGET: usize = 1 << 20;
+
+#[derive(Debug, Clone)]
+pub struct PathSolution {
+    operations: Vec<FullOperationWithKey>,
+    // The indexes of the sequences that can build the solution, in ascending order
+    covered_sequences: Vec<usize>,
+}
+
+impl PathSolution {
+    pub fn new(operations: Vec<FullOperationWithKey>, covered_sequences: Vec<usize>) -> Self {
+        debug_assert!(covered_sequences.windows(2).all(|pair| pair[0] < pair[1]));
+        Self {
+            operations,
+            covered_sequences,
+        }
+    }
+
+    pub fn get_operations(&self) -> &[FullOperationWithKey] {
+        &self.operations
+    }
+
+    pub fn get_covered_sequences(&self) -> &[usize] {
+        &self.covered_sequences
+    }
+
+    pub fn get_coverage_count(&self) -> usize {
+        self.covered_sequences.len()
+    }
+}
+
+#[derive(Debug)]
+pub struct PathResult {
+    sequences: Vec<Vec<Piece>>,
+    solutions: Vec<PathSolution>,
+}
+
+// Porting note: a fixed size bitset over the sequences, used to search the minimal solutions
+#[derive(Clone, PartialEq)]
+struct SequenceSet(Vec<u64>);
+
+impl SequenceSet {
+    fn new(size: usize) -> Self {
+        Self(vec![0; size.div_ceil(64)])
+    }
+
+    fn from_indexes(size: usize, indexes: &[usize]) -> Self {
+        let mut set = Self::new(size);
+        for &index in indexes {
+            set.0[index / 64] |= 1 << (index % 64);
+        }
+        set
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn intersection_len(&self, other: &Self) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    fn remove_all(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a &= !b;
+        }
+    }
+
+    fn add_all(&mut self, other: &Self) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a |= b;
+        }
+    }
+}
+
+struct MinimalSearch<'a> {
+    sets: &'a [SequenceSet],
+    // The solutions that cover each sequence
+    covering: Vec<Vec<usize>>,
+    max_coverage: usize,
+    best: Vec<usize>,
+    // The number of states that can still be searched
+    budget: usize,
+}
+
+impl MinimalSearch<'_> {
+    // 最も多くの手順を網羅する解から順に選ぶ。同じときは番号が小さい解を選ぶ
+    fn search_greedy(&self, uncovered: &SequenceSet) -> Vec<usize> {
+        let mut uncovered = uncovered.clone();
+        let mut chosen = Vec::new();
+        while uncovered.len() != 0 {
+            let index = (0..self.sets.len())
+                .max_by_key(|&index| {
+                    (
+                        self.sets[index].intersection_len(&uncovered),
+                        Reverse(index),
+                    )
+                })
+                .unwrap();
+            uncovered.remove_all(&self.sets[index]);
+            chosen.push(index);
+        }
+
+        chosen.sort_unstable();
+        chosen
+    }
+
+    // 最良の組み合わせより少ない個数の組み合わせだけを探す
+    fn search(&mut self, uncovered: &SequenceSet, chosen: &mut Vec<usize>) {
+        let uncovered_count = uncovered.len();
+        if uncovered_count == 0 {
+            let mut candidate = chosen.clone();
+            candidate.sort_unstable();
+            if (candidate.len(), &candidate) < (self.best.len(), &self.best) {
+                self.best = candidate;
+            }
+            return;
+        }
+
+        if self.budget == 0 {
+            return;
+        }
+        self.budget -= 1;
+
+        // The number of solutions needed at least, if each covers as many as possible
+        let lower_bound = uncovered_count.div_ceil(self.max_coverage);
+        if self.best.len() <= chosen.len() + lower_bound {
+            return;
+        }
+
+        // 選べる解が最も少ない手順から探索する
+        let index = (0..self.covering.len())
+            .filter(|&index| uncovered.contains(index))
+            .min_by_key(|&index| self.covering[index].len())
+            .unwrap();
+
+        for solution in self.covering[index].clone() {
+            let mut next_uncovered = uncovered.clone();
+            next_uncovered.remove_all(&self.sets[solution]);
+
+            chosen.push(solution);
+            self.search(&next_uncovered, chosen);
+            chosen.pop();
+        }
+    }
+}
+
+impl PathResult {
+    pub fn new(sequences: Vec<Vec<Piece>>, solutions: Vec<PathSolution>) -> Self {
+        Self {
+            sequences,
+            solutions,
+        }
+    }
+
+    pub fn get_sequences(&self) -> &[Vec<Piece>] {
+        &self.sequences
+    }
+
+    // All distinct solutions, in the order they are found
+    pub fn get_unique(&self) -> &[PathSolution] {
+        &self.solutions
+    }
+
+    // The number of sequences that can build at least one of the solutions
+    pub fn get_covered_count(&self) -> usize {
+        let mut covered = SequenceSet::new(self.sequences.len());
+        for solution in &self.solutions {
+            covered.add_all(&SequenceSet::from_indexes(
+                self.sequences.len(),
+                &solution.covered_sequences,
+            ));
+        }
+        covered.len()
+    }
+
+    // Same as get_minimal_with_budget, with the default budget
+    pub fn get_minimal(&self) -> Vec<&PathSolution> {
+        self.get_minimal_with_budget(DEFAULT_MINIMAL_BUDGET)
+    }
+
+    // The smallest set of solutions that still covers every sequence covered by the unique solutions.
+    // The greedy set is returned when no smaller set is found within the budget,
+    // so the set may not be the smallest when the budget runs out.
+    // The solutions are in the same order as the unique solutions.
+    pub fn get_minimal_with_budget(&self, budget: usize) -> Vec<&PathSolution> {
+        let size = self.sequences.len();
+        let sets = self
+            .solutions
+            .iter()
+            .map(|solution| SequenceSet::from_indexes(size, &solution.covered_sequences))
+            .collect::<Vec<_>>();
+
+        let mut uncovered = SequenceSet::new(size);
+        let mut covering = vec![Vec::new(); size];
+        for (index, (solution, set)) in self.solutions.iter().zip(&sets).enumerate() {
+            // 同じ手順を網羅する解は、最初のものだけで十分
+            if sets[..index].contains(set) {
+                continue;
+            }
+
+            uncovered.add_all(set);
+            for &sequence in &solution.covered_sequences {
+                covering[sequence].push(index);
+            }
+        }
+
+        if uncovered.len() == 0 {
+            return Vec::new();
+        }
+
+        let mut search = MinimalSearch {
+            sets: &sets,
+            max_coverage: sets
+                .iter()
+                .map(|set| set.intersection_len(&uncovered))
+                .max()
+                .unwrap(),
+            covering,
+            best: Vec::new(),
+            budget,
+        };
+        search.best = search.search_greedy(&uncovered);
+        search.search(&uncovered, &mut Vec::new());
+
+        search
+            .best
+            .into_iter()
+            .map(|index| &self.solutions[index])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_result(sequence_count: usize, coverages: &[&[usize]]) -> PathResult {
+        PathResult::new(
+            vec![vec![Piece::T]; sequence_count],
+            coverages
+                .iter()
+                .map(|covered| PathSolution::new(Vec::new(), covered.to_vec()))
+                .collect(),
+        )
+    }
+
+    fn minimal_indexes(result: &PathResult) -> Vec<usize> {
+        result
+            .get_minimal()
+            .into_iter()
+            .map(|minimal| {
+                result
+                    .get_unique()
+                    .iter()
+                    .position(|solution| std::ptr::eq(solution, minimal))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn minimal_is_smallest() {
+        // the greedy choice of the largest solution first needs 3 solutions
+        let result = create_result(6, &[&[0, 1, 2, 3], &[0, 1, 4], &[2, 3, 5], &[4], &[5]]);
+        assert_eq!(minimal_indexes(&result), vec![1, 2]);
+        assert_eq!(result.get_covered_count(), 6);
+    }
+
+    #[test]
+    fn minimal_budget() {
+        let result = create_result(6, &[&[0, 1, 2, 3], &[0, 1, 4], &[2, 3, 5], &[4], &[5]]);
+
+        // the greedy solutions are returned when the search cannot start
+        let indexes = |minimal: Vec<&PathSolution>| {
+            minimal
+                .into_iter()
+                .map(|minimal| {
+                    result
+                        .get_unique()
+                        .iter()
+                        .position(|solution| std::ptr::eq(solution, minimal))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(indexes(result.get_minimal_with_budget(0)), vec![0, 1, 2]);
+        assert_eq!(indexes(result.get_minimal_with_budget(1 << 10)), vec![1, 2]);
+    }
+
+    #[test]
+    fn minimal_tie_break() {
+        let result = create_result(4, &[&[0, 1], &[2, 3], &[0, 2], &[1, 3], &[0, 1]]);
+        assert_eq!(minimal_indexes(&result), vec![0, 1]);
+    }
+
+    #[test]
+    fn uncovered_sequences() {
+        // sequence 3 is not covered by any solution, so it is ignored
+        let result = create_result(4, &[&[0], &[0, 1, 2], &[1]]);
+        assert_eq!(minimal_indexes(&result), vec![1]);
+        assert_eq!(result.get_covered_count(), 3);
+
+        let result = create_result(2, &[]);
+        assert!(result.get_minimal().is_empty());
+        assert_eq!(result.get_covered_count(), 0);
+    }
+
+    #[test]
+    fn coverage_count() {
+        let solution = PathSolution::new(Vec::new(), vec![1, 4, 5]);
+        assert_eq!(solution.get_coverage_count(), 3);
+        assert_eq!(solution.get_covered_sequences(), &[1, 4, 5]);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod percent_core;
+pub mod sequence_tree;
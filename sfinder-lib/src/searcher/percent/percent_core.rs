@@ -0,0 +1,588 @@
+//! Porting note: replaces PercentCore. The tree of the results is replaced by the counts of each first piece.
+
+use crate::{
+    common::datastore::action::minimal_action::MinimalAction,
+    searcher::{
+        checker::{
+            checker::Checker, checker_no_hold::CheckerNoHold, checker_using_hold::CheckerUsingHold,
+            tree_checker::TreeChecker,
+        },
+        common::{
+            parallel_executor::ParallelExecutor, validator::perfect_validator::PerfectValidator,
+        },
+        percent::sequence_tree::SequenceTree,
+    },
+    sfinder_core::{
+        action::{candidate::candidate::Candidate, drop_type::DropType},
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::mino_rotation::MinoRotation,
+    },
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SuccessCount {
+    success: usize,
+    total: usize,
+}
+
+impl SuccessCount {
+    pub fn new(success: usize, total: usize) -> Self {
+        debug_assert!(success <= total);
+        Self { success, total }
+    }
+
+    pub fn add(&mut self, is_success: bool) {
+        self.success += is_success as usize;
+        self.total += 1;
+    }
+
+    pub fn get_success(&self) -> usize {
+        self.success
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.total
+    }
+
+    // Returns 0 if there are no sequences
+    pub fn get_success_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.success as f64 / self.total as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PercentResult {
+    count: SuccessCount,
+    first_piece_counts: BTreeMap<Piece, SuccessCount>,
+    failed_sequences: Vec<Vec<Piece>>,
+}
+
+impl PercentResult {
+    fn add(&mut self, sequence: Vec<Piece>, is_success: bool) {
+        self.count.add(is_success);
+        self.first_piece_counts
+            .entry(sequence[0])
+            .or_default()
+            .add(is_success);
+
+        if !is_success {
+            self.failed_sequences.push(sequence);
+        }
+    }
+
+    pub fn get_count(&self) -> SuccessCount {
+        self.count
+    }
+
+    pub fn get_success_rate(&self) -> f64 {
+        self.count.get_success_rate()
+    }
+
+    // The counts of the sequences grouped by their first piece
+    pub fn get_first_piece_counts(&self) -> &BTreeMap<Piece, SuccessCount> {
+        &self.first_piece_counts
+    }
+
+    // In the order the sequences were given
+    pub fn get_failed_sequences(&self) -> &[Vec<Piece>] {
+        &self.failed_sequences
+    }
+}
+
+// Returns the number of pieces needed to fill the empty blocks below max_clear_line
+pub fn get_max_depth(field: &dyn Field, max_clear_line: u8) -> Result<u8, String> {
+    let block_count = (0..field.get_width())
+        .map(|x| field.get_block_count_in_column(x, max_clear_line))
+        .sum::<u32>();
+    let empty_count = max_clear_line as u32 * field.get_width() as u32 - block_count;
+
+    if empty_count == 0 {
+        return Err("Field should have empty blocks below the clear line".to_owned());
+    }
+
+    if !empty_count.is_multiple_of(4) {
+        return Err(format!(
+            "The number of empty blocks should be a multiple of 4: {empty_count}"
+        ));
+    }
+
+    Ok((empty_count / 4) as u8)
+}
+
+fn create_checker(
+    mino_factory: &MinoFactory,
+    is_using_hold: bool,
+) -> Box<dyn Checker<MinimalAction> + '_> {
+    if is_using_hold {
+        Box::new(CheckerUsingHold::new(mino_factory, PerfectValidator))
+    } else {
+        Box::new(CheckerNoHold::new(mino_factory, PerfectValidator))
+    }
+}
+
+pub struct PercentCore<'a> {
+    mino_factory: &'a MinoFactory,
+    mino_shifter: &'a MinoShifter,
+    mino_rotation: &'a dyn MinoRotation,
+    drop_type: DropType,
+    candidate: Box<dyn Candidate + 'a>,
+    max_clear_line: u8,
+    is_using_hold: bool,
+}
+
+impl<'a> PercentCore<'a> {
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_clear_line: u8,
+        is_using_hold: bool,
+        drop_type: DropType,
+    ) -> Self {
+        Self {
+            mino_factory,
+            mino_shifter,
+            mino_rotation,
+            drop_type,
+            candidate: drop_type.create_candidate(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_clear_line,
+            ),
+            max_clear_line,
+            is_using_hold,
+        }
+    }
+
+    // Sequences should have enough pieces to fill the field.
+    // The pieces that cannot be reached are ignored, i.e. after max_depth pieces (+1 with hold).
+    pub fn run(
+        &mut self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Result<PercentResult, String> {
+        let max_depth = get_max_depth(field, self.max_clear_line)?;
+        let used_length = max_depth as usize + self.is_using_hold as usize;
+
+        let mut checker = create_checker(self.mino_factory, self.is_using_hold);
+
+        // 同じミノ列になるときは、結果を使いまわす
+        let mut cache = HashMap::new();
+        let mut result = PercentResult::default();
+
+        for sequence in sequences {
+            if sequence.len() < max_depth as usize {
+                return Err(format!(
+                    "Sequence should have at least {max_depth} pieces: {}",
+                    sequence.len()
+                ));
+            }
+
+            let pieces = &sequence[..sequence.len().min(used_length)];
+            let is_success = match cache.get(pieces) {
+                Some(&is_success) => is_success,
+                None => {
+                    let is_success = checker
+                        .check(
+                            field,
+                            pieces,
+                            self.candidate.as_mut(),
+                            self.max_clear_line,
+                            max_depth,
+                        )
+                        .is_some();
+                    cache.insert(pieces.to_vec(), is_success);
+                    is_success
+                }
+            };
+
+            result.add(sequence, is_success);
+        }
+
+        Ok(result)
+    }
+
+    // Returns the max depth and the number of the pieces used in each sequence
+    fn check_sequences(
+        &self,
+        field: &dyn Field,
+        sequences: &[Vec<Piece>],
+    ) -> Result<(u8, usize), String> {
+        let max_depth = get_max_depth(field, self.max_clear_line)?;
+        let used_length = max_depth as usize + self.is_using_hold as usize;
+
+        if let Some(sequence) = sequences
+            .iter()
+            .find(|sequence| sequence.len() < max_depth as usize)
+        {
+            return Err(format!(
+                "Sequence should have at least {max_depth} pieces: {}",
+                sequence.len()
+            ));
+        }
+
+        Ok((max_depth, used_length))
+    }
+
+    // Porting note: evaluates the sequences as a tree, where the sequences that share a prefix
+    // or reach the same state are searched only once. The result is the same as `run`.
+    pub fn run_tree(
+        &mut self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Result<PercentResult, String> {
+        let sequences = sequences.into_iter().collect::<Vec<_>>();
+        let (max_depth, used_length) = self.check_sequences(field, &sequences)?;
+
+        let tree = SequenceTree::new(
+            sequences
+                .iter()
+                .map(|sequence| sequence[..sequence.len().min(used_length)].to_vec())
+                .collect(),
+        );
+
+        let mut checker = TreeChecker::new(self.mino_factory, PerfectValidator, self.is_using_hold);
+        let results = checker.check(
+            field,
+            &tree,
+            self.candidate.as_mut(),
+            self.max_clear_line,
+            max_depth,
+        );
+
+        let mut result = PercentResult::default();
+        for sequence in sequences {
+            let leaf = tree
+                .get_leaf_index(&sequence[..sequence.len().min(used_length)])
+                .unwrap();
+            result.add(sequence, results[leaf]);
+        }
+
+        Ok(result)
+    }
+
+    // Porting note: the same as `run_tree`, but the sequences are split into the subtrees of their
+    // prefixes, which are distributed to the workers of the executor. The prefixes are lengthened
+    // until there are at least as many subtrees as the workers.
+    // The subtrees can reach the same states with the hold (e.g. T,O,... and O,T,...), which are
+    // searched again in each subtree, since the checkers are not shared between the workers.
+    // Each worker has its own candidate and checker, and the result is in the order of the sequences.
+    pub fn run_parallel(
+        &self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+        executor: &ParallelExecutor,
+    ) -> Result<PercentResult, String> {
+        let sequences = sequences.into_iter().collect::<Vec<_>>();
+        let (max_depth, used_length) = self.check_sequences(field, &sequences)?;
+
+        let count_prefixes = |length: usize| {
+            sequences
+                .iter()
+                .map(|sequence| &sequence[..length])
+                .collect::<BTreeSet<_>>()
+                .len()
+        };
+        let mut prefix_length = 1;
+        while prefix_length < used_length && count_prefixes(prefix_length) < executor.get_threads()
+        {
+            prefix_length += 1;
+        }
+
+        let mut subtrees = BTreeMap::<Vec<Piece>, Vec<Vec<Piece>>>::new();
+        for sequence in &sequences {
+            subtrees
+                .entry(sequence[..prefix_length].to_vec())
+                .or_default()
+                .push(sequence[..sequence.len().min(used_length)].to_vec());
+        }
+        let (prefixes, trees): (Vec<_>, Vec<_>) = subtrees
+            .into_iter()
+            .map(|(prefix, sequences)| (prefix, SequenceTree::new(sequences)))
+            .unzip();
+
+        let (mino_factory, mino_shifter, mino_rotation) =
+            (self.mino_factory, self.mino_shifter, self.mino_rotation);
+        let (drop_type, max_clear_line, is_using_hold) =
+            (self.drop_type, self.max_clear_line, self.is_using_hold);
+        let results = executor.map(
+            &trees,
+            || {
+                let candidate = drop_type.create_candidate(
+                    mino_factory,
+                    mino_shifter,
+                    mino_rotation,
+                    max_clear_line,
+                );
+                let checker = TreeChecker::new(mino_factory, PerfectValidator, is_using_hold);
+                (candidate, checker)
+            },
+            |(candidate, checker), tree| {
+                checker.check(field, tree, candidate.as_mut(), max_clear_line, max_depth)
+            },
+        );
+
+        let mut result = PercentResult::default();
+        for sequence in sequences {
+            let index = prefixes
+                .binary_search_by(|prefix| prefix[..].cmp(&sequence[..prefix_length]))
+                .unwrap();
+            let leaf = trees[index]
+                .get_leaf_index(&sequence[..sequence.len().min(used_length)])
+                .unwrap();
+            result.add(sequence, results[index][leaf]);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use crate::{
+        common::pattern::pattern_generator::PatternGenerator,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::field::field_factory,
+    };
+
+    fn run(
+        marks: &str,
+        max_clear_line: u8,
+        is_using_hold: bool,
+        sequences: &[&str],
+    ) -> PercentResult {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut percent_core = PercentCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            max_clear_line,
+            is_using_hold,
+            DropType::Softdrop,
+        );
+
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        percent_core
+            .run(field.as_ref(), sequences.iter().map(|str| pieces(str)))
+            .unwrap()
+    }
+
+    #[test]
+    fn no_hold() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+        let result = run(&marks, 2, false, &["OO", "TT", "II", "LL", "LJ"]);
+
+        assert_eq!(result.get_count().get_success(), 3);
+        assert_eq!(result.get_count().get_total(), 5);
+        assert_eq!(result.get_success_rate(), 0.6);
+        assert_eq!(result.get_failed_sequences(), &[pieces("TT"), pieces("LJ")]);
+
+        let counts = result.get_first_piece_counts();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&Piece::L].get_success(), 1);
+        assert_eq!(counts[&Piece::L].get_total(), 2);
+        assert_eq!(counts[&Piece::T].get_success_rate(), 0.0);
+    }
+
+    #[test]
+    fn using_hold() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+        let result = run(&marks, 2, true, &["TOO", "OTO", "TTO", "OOT", "TTOO"]);
+
+        assert_eq!(result.get_count().get_success(), 3);
+        assert_eq!(
+            result.get_failed_sequences(),
+            &[pieces("TTO"), pieces("TTOO")]
+        );
+    }
+
+    #[test]
+    fn pattern() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____";
+        let field = field_factory::create_field_with_marks(marks);
+
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut percent_core = PercentCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+            false,
+            DropType::Softdrop,
+        );
+
+        let generator = "I,I,[IO]p2".parse::<PatternGenerator>().unwrap();
+        let result = percent_core.run(field.as_ref(), generator.iter()).unwrap();
+
+        // IIIO and IIOI cannot be cleared, as an O cannot fill the remaining 1x4 column
+        assert_eq!(result.get_count().get_total(), 2);
+        assert_eq!(result.get_count().get_success(), 0);
+
+        let generator = "I,I,I,I".parse::<PatternGenerator>().unwrap();
+        let result = percent_core.run(field.as_ref(), generator.iter()).unwrap();
+        assert_eq!(result.get_success_rate(), 1.0);
+    }
+
+    fn assert_same_as_run(marks: &str, max_clear_line: u8, is_using_hold: bool, pattern: &str) {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut percent_core = PercentCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            max_clear_line,
+            is_using_hold,
+            DropType::Softdrop,
+        );
+
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        let generator = pattern.parse::<PatternGenerator>().unwrap();
+        let expected = percent_core.run(field.as_ref(), generator.iter()).unwrap();
+        let tree = percent_core
+            .run_tree(field.as_ref(), generator.iter())
+            .unwrap();
+        let parallel = percent_core
+            .run_parallel(field.as_ref(), generator.iter(), &ParallelExecutor::new(3))
+            .unwrap();
+        // more threads than the first pieces, so the sequences are split by longer prefixes
+        let deep_parallel = percent_core
+            .run_parallel(field.as_ref(), generator.iter(), &ParallelExecutor::new(16))
+            .unwrap();
+
+        for actual in [tree, parallel, deep_parallel] {
+            assert_eq!(actual.get_count(), expected.get_count(), "{pattern}");
+            assert_eq!(
+                actual.get_first_piece_counts(),
+                expected.get_first_piece_counts()
+            );
+            assert_eq!(
+                actual.get_failed_sequences(),
+                expected.get_failed_sequences()
+            );
+        }
+    }
+
+    #[test]
+    fn tree_2x4() {
+        let marks = String::new() + "____XXXXXX" + "____XXXXXX";
+        assert_same_as_run(&marks, 2, false, "*p2");
+        assert_same_as_run(&marks, 2, true, "*p3");
+        assert_same_as_run(&marks, 2, true, "*p2");
+        assert_same_as_run(&marks, 2, true, "[OT]p2,*");
+    }
+
+    #[test]
+    fn tree_4x4() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____"
+            + "XXXXXX____";
+        assert_same_as_run(&marks, 4, false, "[IOLJ]p4");
+        assert_same_as_run(&marks, 4, true, "[IOLJ]p4,[IOLJ]");
+        assert_same_as_run(&marks, 4, true, "[IOSZLJ]p4");
+    }
+
+    #[test]
+    fn tree_3_lines() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXXXXXX___"
+            + "XXXXXX____"
+            + "XXXXX_____";
+        assert_same_as_run(&marks, 3, false, "*p3");
+        assert_same_as_run(&marks, 3, true, "*p4");
+    }
+
+    #[test]
+    fn invalid_field() {
+        let field = field_factory::create_field_with_marks(String::new() + "___XXXXXXX");
+        assert!(get_max_depth(field.as_ref(), 1).is_err());
+
+        let field = field_factory::create_field_with_marks(String::new() + "XXXXXXXXXX");
+        assert!(get_max_depth(field.as_ref(), 1).is_err());
+
+        let field = field_factory::create_field_with_marks(String::new() + "______XXXX");
+        assert_eq!(get_max_depth(field.as_ref(), 2), Ok(4));
+    }
+
+    #[test]
+    fn short_sequence() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut percent_core = PercentCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+            true,
+            DropType::Softdrop,
+        );
+
+        let field =
+            field_factory::create_field_with_marks(String::new() + "____XXXXXX" + "____XXXXXX");
+        assert!(percent_core.run(field.as_ref(), [pieces("O")]).is_err());
+        assert!(percent_core
+            .run_parallel(
+                field.as_ref(),
+                [pieces("OO"), pieces("O")],
+                &ParallelExecutor::new(2)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn variable_width_fields() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut percent_core = PercentCore::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            2,
+            false,
+            DropType::Softdrop,
+        );
+
+        for (width, marks) in [
+            (4, String::new() + "____" + "____"),
+            (12, String::new() + "XXXXXXXX____" + "XXXXXXXX____"),
+        ] {
+            let field = field_factory::create_variable_width_field_with_marks(width, marks);
+            let result = percent_core
+                .run(
+                    &field,
+                    ["OO", "TT", "II", "LL", "LJ"].iter().map(|str| pieces(str)),
+                )
+                .unwrap();
+
+            assert_eq!(
+                result.get_failed_sequences(),
+                &[pieces("TT"), pieces("LJ")],
+                "{width}"
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Porting note: replaces the tree of ObjTree. The sequences are stored as a trie,
+//! where the sequences in the subtree of a node are numbered consecutively.
+
+use crate::sfinder_core::mino::piece::Piece;
+use std::ops::Range;
+
+#[derive(Debug)]
+struct Node {
+    children: Vec<(Piece, usize)>,
+    // The index of the sequence that ends at this node
+    terminal: Option<usize>,
+    leaves: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct SequenceTree {
+    nodes: Vec<Node>,
+    sequences: Vec<Vec<Piece>>,
+}
+
+impl SequenceTree {
+    pub const ROOT: usize = 0;
+
+    // Duplicated sequences are merged into one leaf.
+    pub fn new(mut sequences: Vec<Vec<Piece>>) -> Self {
+        // 辞書順に並べると、部分木に含まれるミノ列が連続する
+        sequences.sort_unstable();
+        sequences.dedup();
+
+        let mut tree = Self {
+            nodes: Vec::new(),
+            sequences,
+        };
+        tree.add_node(0, 0..tree.sequences.len());
+        tree
+    }
+
+    // Adds the node of the sequences in `leaves`, which share the first `depth` pieces
+    fn add_node(&mut self, depth: usize, leaves: Range<usize>) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            children: Vec::new(),
+            terminal: None,
+            leaves: leaves.clone(),
+        });
+
+        let mut start = leaves.start;
+        if self
+            .sequences
+            .get(start)
+            .is_some_and(|sequence| sequence.len() == depth)
+        {
+            self.nodes[index].terminal = Some(start);
+            start += 1;
+        }
+
+        while start < leaves.end {
+            let piece = self.sequences[start][depth];
+            let end = (start..leaves.end)
+                .find(|&leaf| self.sequences[leaf][depth] != piece)
+                .unwrap_or(leaves.end);
+
+            let child = self.add_node(depth + 1, start..end);
+            self.nodes[index].children.push((piece, child));
+            start = end;
+        }
+
+        index
+    }
+
+    pub fn get_sequences(&self) -> &[Vec<Piece>] {
+        &self.sequences
+    }
+
+    pub fn get_children(&self, node: usize) -> &[(Piece, usize)] {
+        &self.nodes[node].children
+    }
+
+    pub fn get_terminal(&self, node: usize) -> Option<usize> {
+        self.nodes[node].terminal
+    }
+
+    // The sequences in the subtree of the node
+    pub fn get_leaves(&self, node: usize) -> Range<usize> {
+        self.nodes[node].leaves.clone()
+    }
+
+    pub fn get_leaf_index(&self, sequence: &[Piece]) -> Option<usize> {
+        self.sequences
+            .binary_search_by(|leaf| leaf.as_slice().cmp(sequence))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+
+    #[test]
+    fn shared_prefix() {
+        // sorted in the order of the pieces: TSZ, TSO, IT
+        let tree = SequenceTree::new(vec![
+            pieces("TSZ"),
+            pieces("TSO"),
+            pieces("IT"),
+            pieces("TSZ"),
+        ]);
+
+        assert_eq!(tree.get_sequences().len(), 3);
+        assert_eq!(tree.get_leaves(SequenceTree::ROOT), 0..3);
+
+        let children = tree.get_children(SequenceTree::ROOT);
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].0, Piece::T);
+        assert_eq!(tree.get_leaves(children[0].1), 0..2);
+        assert_eq!(children[1].0, Piece::I);
+        assert_eq!(tree.get_leaves(children[1].1), 2..3);
+
+        let (piece, node) = tree.get_children(children[0].1)[0];
+        assert_eq!(piece, Piece::S);
+        assert_eq!(tree.get_children(node).len(), 2);
+
+        assert_eq!(tree.get_leaf_index(&pieces("TSO")), Some(1));
+        assert_eq!(tree.get_leaf_index(&pieces("IT")), Some(2));
+        assert_eq!(tree.get_leaf_index(&pieces("TS")), None);
+    }
+
+    #[test]
+    fn terminal() {
+        let tree = SequenceTree::new(vec![pieces("TS"), pieces("T"), pieces("TZ")]);
+
+        let (_, node) = tree.get_children(SequenceTree::ROOT)[0];
+        assert_eq!(tree.get_terminal(node), Some(0));
+        assert_eq!(tree.get_leaves(node), 0..3);
+        assert_eq!(tree.get_children(node).len(), 2);
+        assert_eq!(tree.get_terminal(SequenceTree::ROOT), None);
+    }
+}
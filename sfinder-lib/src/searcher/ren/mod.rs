@@ -0,0 +1 @@
+pub mod ren_searcher;
@@ -0,0 +1,397 @@
+//! Porting note: replaces the search of the ren entry.
+//! The longest combo from each state is memoized first, and then only the orders that reach the
+//! longest combo are collected.
+
+use crate::{
+    common::datastore::{
+        action::action::Action, piece_counter::PieceCounter, simple_operation::SimpleOperation,
+    },
+    searcher::common::{
+        hold_choices,
+        transposition_table::{StateKey, TranspositionTable},
+    },
+    sfinder_core::{
+        action::candidate::candidate::Candidate,
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+    },
+};
+#[derive(Debug)]
+pub struct RenResult {
+    // Every operation clears at least one row, in the coordinates of the field when it is placed
+    operations: Vec<SimpleOperation>,
+    // The rows cleared by each operation
+    delete_keys: Vec<u64>,
+}
+
+impl RenResult {
+    pub fn get_operations(&self) -> &[SimpleOperation] {
+        &self.operations
+    }
+
+    pub fn get_delete_keys(&self) -> &[u64] {
+        &self.delete_keys
+    }
+
+    // The number of consecutive line clears
+    pub fn get_ren(&self) -> usize {
+        self.operations.len()
+    }
+}
+
+pub struct RenSearcher<'a> {
+    mino_factory: &'a MinoFactory,
+    height: u8,
+    is_using_hold: bool,
+}
+
+struct Search<'a, 'b, T: Action> {
+    mino_factory: &'a MinoFactory,
+    candidate: &'b mut dyn Candidate<T>,
+    pieces: &'b [Piece],
+    height: u8,
+    is_using_hold: bool,
+    // The longest combo from each state
+    table: &'b mut TranspositionTable<usize>,
+}
+
+impl<T: Action> Search<'_, '_, T> {
+    // (使うミノ, 次のホールド, 次のミノの位置)
+    fn get_choices(&self, hold: Option<Piece>, index: usize) -> Vec<(Piece, Option<Piece>, usize)> {
+        hold_choices::get_choices_from_index(hold, self.pieces, index, self.is_using_hold)
+    }
+
+    // Returns the placements of the piece that clear rows, with the field after clearing them
+    fn get_clears(
+        &mut self,
+        field: &dyn Field,
+        piece: Piece,
+    ) -> Vec<(SimpleOperation, Box<dyn Field>, u64)> {
+        self.candidate
+            .search(field, piece, self.height)
+            .into_iter()
+            .filter_map(|action| {
+                let mino = self.mino_factory.get(piece, action.get_rotate());
+                let mut next_field = field.prune(self.height);
+                next_field.put(mino, action.get_x(), action.get_y());
+
+                let delete_key = next_field.clear_filled_rows_return_key();
+                (delete_key != 0).then(|| {
+                    let operation = SimpleOperation::new(
+                        piece,
+                        action.get_rotate(),
+                        action.get_x(),
+                        action.get_y(),
+                    );
+                    (operation, next_field, delete_key)
+                })
+            })
+            .collect()
+    }
+
+    // The longest combo from the state
+    fn get_max_ren(&mut self, field: &dyn Field, hold: Option<Piece>, index: usize) -> usize {
+        // The remaining pieces are a suffix of the sequence, so their counts decide the index
+        let rest = self.pieces.get(index..).unwrap_or_default();
+        let key = StateKey::new(field, PieceCounter::from(rest.iter().copied()), hold);
+        if let Some(&max_ren) = self.table.get(&key) {
+            return max_ren;
+        }
+
+        let mut max_ren = 0;
+        for (piece, next_hold, next_index) in self.get_choices(hold, index) {
+            for (_, next_field, _) in self.get_clears(field, piece) {
+                max_ren =
+                    max_ren.max(1 + self.get_max_ren(next_field.as_ref(), next_hold, next_index));
+            }
+        }
+
+        let depth = u8::try_from(rest.len() + hold.is_some() as usize).unwrap_or(u8::MAX);
+        self.table.insert(key, depth, max_ren);
+        max_ren
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn collect(
+        &mut self,
+        field: &dyn Field,
+        hold: Option<Piece>,
+        index: usize,
+        left_ren: usize,
+        operations: &mut Vec<SimpleOperation>,
+        delete_keys: &mut Vec<u64>,
+        results: &mut Vec<RenResult>,
+    ) {
+        if left_ren == 0 {
+            results.push(RenResult {
+                operations: operations.clone(),
+                delete_keys: delete_keys.clone(),
+            });
+            return;
+        }
+
+        for (piece, next_hold, next_index) in self.get_choices(hold, index) {
+            for (operation, next_field, delete_key) in self.get_clears(field, piece) {
+                if self.get_max_ren(next_field.as_ref(), next_hold, next_index) + 1 != left_ren {
+                    continue;
+                }
+
+                operations.push(operation);
+                delete_keys.push(delete_key);
+                self.collect(
+                    next_field.as_ref(),
+                    next_hold,
+                    next_index,
+                    left_ren - 1,
+                    operations,
+                    delete_keys,
+                    results,
+                );
+                delete_keys.pop();
+                operations.pop();
+            }
+        }
+    }
+}
+
+impl<'a> RenSearcher<'a> {
+    pub fn new(mino_factory: &'a MinoFactory, height: u8, is_using_hold: bool) -> Self {
+        Self {
+            mino_factory,
+            height,
+            is_using_hold,
+        }
+    }
+
+    // Returns all orders of placements that reach the longest combo.
+    // Returns no results if the first piece cannot clear a row.
+    pub fn search<T: Action>(
+        &self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+    ) -> Vec<RenResult> {
+        self.search_with_table(field, pieces, candidate, &mut TranspositionTable::default())
+    }
+
+    // Porting note: the same as `search`, but the states are memoized in the given table,
+    // e.g. to limit its memory or to see its statistics
+    pub fn search_with_table<T: Action>(
+        &self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        candidate: &mut dyn Candidate<T>,
+        table: &mut TranspositionTable<usize>,
+    ) -> Vec<RenResult> {
+        table.new_search();
+
+        let mut search = Search {
+            mino_factory: self.mino_factory,
+            candidate,
+            pieces,
+            height: self.height,
+            is_using_hold: self.is_using_hold,
+            table,
+        };
+
+        let max_ren = search.get_max_ren(field, None, 0);
+        let mut results = Vec::new();
+        if max_ren != 0 {
+            search.collect(
+                field,
+                None,
+                0,
+                max_ren,
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut results,
+            );
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::datastore::operation::Operation,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::common::transposition_table::{ReplacementPolicy, DEFAULT_MAX_BYTES},
+        sfinder_core::{
+            action::candidate::candidate_facade, field::field_factory,
+            mino::mino_shifter::MinoShifter,
+        },
+    };
+
+    fn search(marks: String, pieces: &str, is_using_hold: bool) -> Vec<RenResult> {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            8,
+        );
+
+        let pieces = pieces
+            .chars()
+            .map(|c| c.to_string().parse().unwrap())
+            .collect::<Vec<Piece>>();
+        let field = field_factory::create_field_with_marks(marks);
+        RenSearcher::new(&mino_factory, 8, is_using_hold).search(
+            field.as_ref(),
+            &pieces,
+            candidate.as_mut(),
+        )
+    }
+
+    // Follows the longest combo without memoization
+    fn max_ren_by_brute_force(
+        mino_factory: &MinoFactory,
+        candidate: &mut dyn Candidate,
+        field: &dyn Field,
+        pieces: &[Piece],
+    ) -> usize {
+        let Some((&piece, rest)) = pieces.split_first() else {
+            return 0;
+        };
+
+        candidate
+            .search(field, piece, 8)
+            .into_iter()
+            .filter_map(|action| {
+                let mut next_field = field.prune(8);
+                next_field.put(
+                    mino_factory.get(piece, action.get_rotate()),
+                    action.get_x(),
+                    action.get_y(),
+                );
+                (next_field.clear_filled_rows() != 0).then(|| {
+                    1 + max_ren_by_brute_force(mino_factory, candidate, next_field.as_ref(), rest)
+                })
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn four_wide_with_i() {
+        let marks = "XXX____XXX".repeat(4);
+
+        let results = search(marks.clone(), "IIII", false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].get_ren(), 4);
+        assert!(results[0]
+            .get_operations()
+            .iter()
+            .all(|operation| operation.get_x() == 4 && operation.get_y() == 0));
+        assert!(results[0].get_delete_keys().iter().all(|&key| key == 1));
+
+        // the O cannot clear a row
+        let results = search(marks.clone(), "IOII", false);
+        assert_eq!(results[0].get_ren(), 1);
+        assert!(search(marks.clone(), "OIII", false).is_empty());
+
+        // the O is held
+        let results = search(marks, "OIIII", true);
+        assert_eq!(results[0].get_ren(), 4);
+        assert!(results.iter().all(|result| result
+            .get_operations()
+            .iter()
+            .all(|op| op.get_piece() == Piece::I)));
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXX____XXX"
+            + "XXX____XXX"
+            + "XXX____XXX"
+            + "XXX____XXX"
+            + "XXXX___XXX";
+
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            8,
+        );
+        let field = field_factory::create_field_with_marks(marks.clone());
+
+        for pieces in ["TLJSZ", "IOTSZ", "LJLJL"] {
+            let results = search(marks.clone(), pieces, false);
+            let expected = max_ren_by_brute_force(
+                &mino_factory,
+                candidate.as_mut(),
+                field.as_ref(),
+                &pieces
+                    .chars()
+                    .map(|c| c.to_string().parse().unwrap())
+                    .collect::<Vec<_>>(),
+            );
+            assert!(expected > 0);
+            assert!(results.iter().all(|result| result.get_ren() == expected));
+        }
+    }
+
+    #[test]
+    fn shared_table() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "XXX____XXX"
+            + "XXX____XXX"
+            + "XXX____XXX"
+            + "XXXX___XXX";
+
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = candidate_facade::create_90_locked(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            8,
+        );
+        let field = field_factory::create_field_with_marks(marks.clone());
+        let searcher = RenSearcher::new(&mino_factory, 8, true);
+
+        // 同じテーブルで別のミノ列を探索しても、結果は変わらない
+        for (max_bytes, policy) in [
+            (DEFAULT_MAX_BYTES, ReplacementPolicy::DepthPreferred),
+            (1 << 8, ReplacementPolicy::DepthPreferred),
+            (1 << 8, ReplacementPolicy::AlwaysReplace),
+        ] {
+            let mut table = TranspositionTable::new(max_bytes, policy);
+            for pieces in ["TLJSZ", "ZSJLT", "LJLJL"] {
+                let expected = search(marks.clone(), pieces, true);
+                let results = searcher.search_with_table(
+                    field.as_ref(),
+                    &pieces
+                        .chars()
+                        .map(|c| c.to_string().parse().unwrap())
+                        .collect::<Vec<_>>(),
+                    candidate.as_mut(),
+                    &mut table,
+                );
+
+                assert_eq!(
+                    results
+                        .iter()
+                        .map(|result| result.get_operations())
+                        .collect::<Vec<_>>(),
+                    expected
+                        .iter()
+                        .map(|result| result.get_operations())
+                        .collect::<Vec<_>>(),
+                );
+            }
+            assert!(0 < table.get_stats().get_hits());
+        }
+    }
+}
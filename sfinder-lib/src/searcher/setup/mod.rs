@@ -0,0 +1,2 @@
+pub mod setup_filter;
+pub mod setup_searcher;
@@ -0,0 +1,207 @@
+//! Porting note: replaces the exclude options of the setup entry, with rules that can be combined.
+//! The rules are tested on the field after all placements of the setup are merged and the filled
+//! rows are cleared.
+
+use crate::{
+    searcher::spins::spin_commons,
+    sfinder_core::{
+        field::{field::Field, field_constants::FIELD_WIDTH},
+        mino::{mino_factory::MinoFactory, piece::Piece},
+        srs::rotate::Rotate,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupFilter {
+    // 空白のまわりがブロックで囲まれていない（上から繋がっている）
+    NoHoles,
+    // すべての列で、一番上のブロックより下が埋まっている
+    Strict,
+    // Tスピンになる位置にTを置いて、ラインを消去できる
+    // Porting note: the reachability of the T is not checked
+    TSpinSlot,
+    And(Vec<SetupFilter>),
+    Or(Vec<SetupFilter>),
+}
+
+impl SetupFilter {
+    pub fn and(self, other: SetupFilter) -> Self {
+        match self {
+            Self::And(mut filters) => {
+                filters.push(other);
+                Self::And(filters)
+            }
+            filter => Self::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: SetupFilter) -> Self {
+        match self {
+            Self::Or(mut filters) => {
+                filters.push(other);
+                Self::Or(filters)
+            }
+            filter => Self::Or(vec![filter, other]),
+        }
+    }
+
+    pub fn test(&self, mino_factory: &MinoFactory, field: &dyn Field, height: u8) -> bool {
+        match self {
+            Self::NoHoles => !exists_enclosed_hole(field, height),
+            Self::Strict => !exists_column_hole(field, height),
+            Self::TSpinSlot => exists_t_spin_slot(mino_factory, field, height),
+            Self::And(filters) => filters
+                .iter()
+                .all(|filter| filter.test(mino_factory, field, height)),
+            Self::Or(filters) => filters
+                .iter()
+                .any(|filter| filter.test(mino_factory, field, height)),
+        }
+    }
+}
+
+// ブロックの下に空白がある列が存在する
+fn exists_column_hole(field: &dyn Field, height: u8) -> bool {
+    (0..FIELD_WIDTH).any(|x| {
+        let top_y = (0..height)
+            .rev()
+            .find(|&y| field.exists_block(x, y))
+            .map_or(0, |y| y + 1);
+        !field.is_filled_in_column(x, top_y)
+    })
+}
+
+// 一番上の行から空白をたどって到達できない空白が存在する
+fn exists_enclosed_hole(field: &dyn Field, height: u8) -> bool {
+    let mut visited = vec![[false; FIELD_WIDTH as usize]; height as usize];
+    let mut stack = (0..FIELD_WIDTH)
+        .filter(|&x| field.is_empty_block(x, height - 1))
+        .map(|x| (x, height - 1))
+        .collect::<Vec<_>>();
+
+    while let Some((x, y)) = stack.pop() {
+        if visited[y as usize][x as usize] {
+            continue;
+        }
+        visited[y as usize][x as usize] = true;
+
+        let neighbors = [
+            (x.checked_sub(1), Some(y)),
+            ((x + 1 < FIELD_WIDTH).then_some(x + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), (y + 1 < height).then_some(y + 1)),
+        ];
+        for (nx, ny) in neighbors {
+            if let (Some(nx), Some(ny)) = (nx, ny) {
+                if field.is_empty_block(nx, ny) && !visited[ny as usize][nx as usize] {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+    }
+
+    (0..height).any(|y| {
+        (0..FIELD_WIDTH).any(|x| field.is_empty_block(x, y) && !visited[y as usize][x as usize])
+    })
+}
+
+// Tを置いたときに、3つ以上の角が埋まっていて、ラインが消去される位置が存在する
+fn exists_t_spin_slot(mino_factory: &MinoFactory, field: &dyn Field, height: u8) -> bool {
+    Rotate::value_list().iter().any(|&rotate| {
+        let mino = mino_factory.get(Piece::T, rotate);
+        let x_range = (-mino.get_min_x()) as u8..(FIELD_WIDTH as i8 - mino.get_max_x()) as u8;
+        let y_range = (-mino.get_min_y()) as u8..(height as i8 - mino.get_max_y()).max(0) as u8;
+
+        x_range.clone().any(|x| {
+            y_range.clone().any(|y| {
+                if !field.can_put(mino, x, y)
+                    || !field.is_on_ground(mino, x, y)
+                    || !spin_commons::can_t_spin(field, x, y)
+                {
+                    return false;
+                }
+
+                let mut freeze = field.prune(height);
+                freeze.put(mino, x, y);
+                freeze.clear_filled_rows() > 0
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::field::field_factory;
+
+    fn test(filter: &SetupFilter, marks: &str) -> bool {
+        let field = field_factory::create_field_with_marks(marks.to_owned());
+        filter.test(&MinoFactory::new(), field.as_ref(), 4)
+    }
+
+    #[test]
+    fn holes() {
+        #[rustfmt::skip]
+        let overhang = String::new()
+            + "XXXX______"
+            + "X_________";
+        assert!(test(&SetupFilter::NoHoles, &overhang));
+        assert!(!test(&SetupFilter::Strict, &overhang));
+
+        #[rustfmt::skip]
+        let enclosed = String::new()
+            + "XXX_______"
+            + "X_X_______";
+        assert!(!test(&SetupFilter::NoHoles, &enclosed));
+        assert!(!test(&SetupFilter::Strict, &enclosed));
+
+        #[rustfmt::skip]
+        let flat = String::new()
+            + "XX________"
+            + "XXXX______";
+        assert!(test(&SetupFilter::NoHoles, &flat));
+        assert!(test(&SetupFilter::Strict, &flat));
+    }
+
+    #[test]
+    fn t_spin_slot() {
+        #[rustfmt::skip]
+        let tsd = String::new()
+            + "XX________"
+            + "X___XXXXXX"
+            + "XX_XXXXXXX";
+        assert!(test(&SetupFilter::TSpinSlot, &tsd));
+
+        // the corners are filled, but no rows are cleared
+        #[rustfmt::skip]
+        let no_clear = String::new()
+            + "XX________"
+            + "X___XXXXX_"
+            + "XX_XXXXXX_";
+        assert!(!test(&SetupFilter::TSpinSlot, &no_clear));
+
+        assert!(!test(&SetupFilter::TSpinSlot, "XXXXXXXX__"));
+    }
+
+    #[test]
+    fn combine() {
+        #[rustfmt::skip]
+        let tsd = String::new()
+            + "XX________"
+            + "X___XXXXXX"
+            + "XX_XXXXXXX";
+
+        let filter = SetupFilter::NoHoles.and(SetupFilter::TSpinSlot);
+        assert!(test(&filter, &tsd));
+        assert_eq!(
+            filter.clone().and(SetupFilter::Strict),
+            SetupFilter::And(vec![
+                SetupFilter::NoHoles,
+                SetupFilter::TSpinSlot,
+                SetupFilter::Strict
+            ])
+        );
+        assert!(!test(&filter.and(SetupFilter::Strict), &tsd));
+        assert!(test(&SetupFilter::Strict.or(SetupFilter::TSpinSlot), &tsd));
+    }
+}
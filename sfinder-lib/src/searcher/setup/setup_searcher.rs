@@ -0,0 +1,569 @@
+//! Porting note: replaces the search of the setup entry.
+//! The placements are found by covering the lowest cell that must be filled, from the left,
+//! with each mino that fits in the cells that may be filled. Every placement covers at least one
+//! cell that must be filled, so placements that only support other placements are not found.
+//! The rows are not cleared while searching. The pieces of the placements are checked against the
+//! sequences as each placement is chosen, and the buildability is checked with BuildUp as soon as
+//! the cells are filled, since a placement can be supported by a placement chosen after it.
+//! The target is kept as fields rather than a BlockField, since the cells to fill do not belong to
+//! any piece. The solutions are converted to BlockFields instead.
+
+use super::setup_filter::SetupFilter;
+use crate::{
+    common::{
+        buildup::build_up,
+        datastore::{
+            block_field::BlockField, full_operation_with_key::FullOperationWithKey,
+            mino_operation_with_key::MinoOperationWithKey, operation::Operation,
+            piece_counter::PieceCounter,
+        },
+        parser::operation_transform,
+    },
+    sfinder_core::{
+        action::{drop_type::DropType, reachable::reachable::Reachable},
+        field::{field::Field, field_constants::FIELD_WIDTH, field_factory},
+        mino::{
+            mino_factory::MinoFactory,
+            mino_shifter::{IMinoShifter, MinoShifter},
+            piece::Piece,
+        },
+        srs::mino_rotation::MinoRotation,
+    },
+};
+use std::{collections::HashSet, mem};
+
+// The number of placements tried while searching the setups
+pub const DEFAULT_PLACEMENT_BUDGET: usize = 1 << 22;
+
+// The shape to build on the field
+pub struct SetupTarget {
+    need_filled: Box<dyn Field>,
+    // The cells that the placements can use, including need_filled
+    allowed: Box<dyn Field>,
+}
+
+impl SetupTarget {
+    // When may_filled is None, the placements can stick out of need_filled anywhere.
+    // The cells of not_filled are always kept empty.
+    pub fn new(
+        need_filled: &dyn Field,
+        may_filled: Option<&dyn Field>,
+        not_filled: Option<&dyn Field>,
+        height: u8,
+    ) -> Result<Self, String> {
+        let need_filled = need_filled.prune(height);
+
+        let mut allowed = match may_filled {
+            Some(may_filled) => {
+                let mut allowed = may_filled.prune(height);
+                allowed.merge(need_filled.as_ref());
+                allowed
+            }
+            None => {
+                let mut allowed = field_factory::create_field(height);
+                allowed.invert();
+                allowed
+            }
+        };
+
+        if let Some(not_filled) = not_filled {
+            if !need_filled.can_merge(not_filled) {
+                return Err("Cells cannot be both filled and kept empty".into());
+            }
+            allowed.reduce(not_filled);
+        }
+
+        Ok(Self {
+            need_filled,
+            allowed,
+        })
+    }
+
+    pub fn get_need_filled(&self) -> &dyn Field {
+        self.need_filled.as_ref()
+    }
+
+    pub fn get_allowed(&self) -> &dyn Field {
+        self.allowed.as_ref()
+    }
+}
+
+#[derive(Debug)]
+pub struct SetupSolution {
+    operations: Vec<FullOperationWithKey>,
+    // The indexes of the sequences that can build the setup, in ascending order
+    covered_sequences: Vec<usize>,
+}
+
+impl SetupSolution {
+    pub fn get_operations(&self) -> &[FullOperationWithKey] {
+        &self.operations
+    }
+
+    pub fn get_covered_sequences(&self) -> &[usize] {
+        &self.covered_sequences
+    }
+
+    pub fn to_block_field(&self, height: u8) -> BlockField {
+        operation_transform::parse_to_block_field(&self.operations, height)
+    }
+
+    // The field after all placements are merged and the filled rows are cleared
+    pub fn create_merged_field(&self, field: &dyn Field, height: u8) -> Box<dyn Field> {
+        let mut merged = field.prune(height);
+        for operation in &self.operations {
+            merged.merge(operation.create_mino_field(height).as_ref());
+        }
+        merged.clear_filled_rows();
+        merged
+    }
+}
+
+#[derive(Debug)]
+pub struct SetupResult {
+    sequences: Vec<Vec<Piece>>,
+    solutions: Vec<SetupSolution>,
+}
+
+impl SetupResult {
+    pub fn get_sequences(&self) -> &[Vec<Piece>] {
+        &self.sequences
+    }
+
+    pub fn get_solutions(&self) -> &[SetupSolution] {
+        &self.solutions
+    }
+
+    // Keeps the setups that satisfy the filter
+    pub fn retain(
+        &mut self,
+        mino_factory: &MinoFactory,
+        field: &dyn Field,
+        height: u8,
+        filter: &SetupFilter,
+    ) {
+        self.solutions.retain(|solution| {
+            filter.test(
+                mino_factory,
+                solution.create_merged_field(field, height).as_ref(),
+                height,
+            )
+        });
+    }
+}
+
+// The state of the search of the placements
+struct SetupSearch<'s> {
+    target: &'s SetupTarget,
+    sequences: &'s [Vec<Piece>],
+    // The distinct pieces of the sequences
+    available: Vec<PieceCounter>,
+    max_pieces: usize,
+    // The number of placements that can still be tried
+    budget: usize,
+    // The pieces of the used placements
+    counter: PieceCounter,
+    used: Vec<FullOperationWithKey>,
+    solutions: Vec<SetupSolution>,
+}
+
+pub struct SetupSearcher<'a> {
+    mino_factory: &'a MinoFactory,
+    mino_shifter: &'a MinoShifter,
+    reachable: Box<dyn Reachable + 'a>,
+    height: u8,
+    is_using_hold: bool,
+}
+
+impl<'a> SetupSearcher<'a> {
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        height: u8,
+        is_using_hold: bool,
+        drop_type: DropType,
+    ) -> Self {
+        Self {
+            mino_factory,
+            mino_shifter,
+            reachable: drop_type.create_reachable(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                height,
+            ),
+            height,
+            is_using_hold,
+        }
+    }
+
+    // Places a mino on the lowest cell that must be filled, until all the cells are filled.
+    // Returns an error when the budget runs out.
+    fn search_placements(
+        &mut self,
+        field_origin: &dyn Field,
+        field: &dyn Field,
+        search: &mut SetupSearch,
+    ) -> Result<(), String> {
+        // まだ埋まっていない、埋める必要があるブロック
+        let mut remaining = search.target.need_filled.prune(self.height);
+        let mut empty = field.prune(self.height);
+        empty.invert();
+        remaining.mask(empty.as_ref());
+
+        if remaining.is_empty() {
+            let covered_sequences = (0..search.sequences.len())
+                .filter(|&index| {
+                    self.can_build(field_origin, &search.used, &search.sequences[index])
+                })
+                .collect::<Vec<_>>();
+            if !covered_sequences.is_empty() {
+                search.solutions.push(SetupSolution {
+                    operations: search.used.clone(),
+                    covered_sequences,
+                });
+            }
+            return Ok(());
+        }
+
+        let remaining_pieces = search.max_pieces - search.used.len();
+        if (remaining.get_num_of_all_blocks() as usize).div_ceil(4) > remaining_pieces {
+            return Ok(());
+        }
+
+        let (target_x, target_y) = (0..self.height)
+            .flat_map(|y| (0..FIELD_WIDTH).map(move |x| (x, y)))
+            .find(|&(x, y)| remaining.exists_block(x, y))
+            .unwrap();
+
+        for &piece in Piece::value_list() {
+            // 使用するミノをすべて含むミノ列があること
+            let next_counter = search.counter.add_piece(piece);
+            if !search
+                .available
+                .iter()
+                .any(|available| available.contains_all(&next_counter))
+            {
+                continue;
+            }
+
+            for rotate in self.mino_shifter.get_unique_rotates(piece) {
+                let mino = self.mino_factory.get(piece, rotate);
+                for position in mino.get_positions() {
+                    let x = target_x as i8 - position.x;
+                    let y = target_y as i8 - position.y;
+                    if x + mino.get_min_x() < 0
+                        || FIELD_WIDTH as i8 <= x + mino.get_max_x()
+                        || y + mino.get_min_y() < 0
+                        || self.height as i8 <= y + mino.get_max_y()
+                    {
+                        continue;
+                    }
+
+                    let (x, y) = (x as u8, y as u8);
+                    let mut mino_field = field_factory::create_field(self.height);
+                    mino_field.put(mino, x, y);
+                    if !search.target.allowed.contains(mino_field.as_ref())
+                        || !field.can_merge(mino_field.as_ref())
+                    {
+                        continue;
+                    }
+
+                    if search.budget == 0 {
+                        return Err("Too many placements to search the setups".into());
+                    }
+                    search.budget -= 1;
+
+                    let mut next_field = field.prune(self.height);
+                    next_field.merge(mino_field.as_ref());
+
+                    let counter = mem::replace(&mut search.counter, next_counter.clone());
+                    search
+                        .used
+                        .push(FullOperationWithKey::create(mino, x, y, 0, self.height));
+                    let result = self.search_placements(field_origin, next_field.as_ref(), search);
+                    search.used.pop();
+                    search.counter = counter;
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Whether the placements can be built in the order of the sequence
+    fn can_build(
+        &mut self,
+        field: &dyn Field,
+        operations: &[FullOperationWithKey],
+        pieces: &[Piece],
+    ) -> bool {
+        let counter = PieceCounter::from(operations.iter().map(|op| op.get_piece()));
+
+        if self.is_using_hold {
+            let pieces = &pieces[..pieces.len().min(operations.len() + 1)];
+            PieceCounter::from(pieces.iter().copied()).contains_all(&counter)
+                && build_up::exists_valid_by_order_with_hold(
+                    self.mino_factory,
+                    field,
+                    operations,
+                    pieces,
+                    self.height,
+                    self.reachable.as_mut(),
+                )
+        } else {
+            let pieces = &pieces[..pieces.len().min(operations.len())];
+            PieceCounter::from(pieces.iter().copied()) == counter
+                && build_up::exists_valid_by_order(
+                    self.mino_factory,
+                    field,
+                    operations,
+                    pieces,
+                    self.height,
+                    self.reachable.as_mut(),
+                )
+        }
+    }
+
+    // Same as run_with_budget, with the default budget
+    pub fn run(
+        &mut self,
+        field: &dyn Field,
+        target: &SetupTarget,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+    ) -> Result<SetupResult, String> {
+        self.run_with_budget(field, target, sequences, DEFAULT_PLACEMENT_BUDGET)
+    }
+
+    // Returns the setups that can be built by at least one of the sequences,
+    // with the distinct sequences that can build each of them.
+    // Returns an error when more placements than the budget are tried.
+    pub fn run_with_budget(
+        &mut self,
+        field: &dyn Field,
+        target: &SetupTarget,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+        budget: usize,
+    ) -> Result<SetupResult, String> {
+        let mut visited = HashSet::new();
+        let sequences = sequences
+            .into_iter()
+            .filter(|sequence| visited.insert(sequence.clone()))
+            .collect::<Vec<_>>();
+
+        let max_pieces = sequences
+            .iter()
+            .map(|sequence| sequence.len())
+            .max()
+            .ok_or("Sequences should not be empty")?;
+
+        // Every sequence is at most max_pieces long, so all its pieces can be used, even with the hold
+        let mut available = Vec::new();
+        for sequence in &sequences {
+            let counter = PieceCounter::from(sequence.iter().copied());
+            if !available.contains(&counter) {
+                available.push(counter);
+            }
+        }
+
+        let mut search = SetupSearch {
+            target,
+            sequences: &sequences,
+            available,
+            max_pieces,
+            budget,
+            counter: PieceCounter::new(),
+            used: Vec::new(),
+            solutions: Vec::new(),
+        };
+        let field = field.prune(self.height);
+        self.search_placements(field.as_ref(), field.as_ref(), &mut search)?;
+        let solutions = search.solutions;
+
+        Ok(SetupResult {
+            sequences,
+            solutions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::pattern::pattern_generator::PatternGenerator,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+    };
+
+    fn run(
+        field: &str,
+        need_filled: &str,
+        may_filled: Option<&str>,
+        pattern: &str,
+        is_using_hold: bool,
+    ) -> SetupResult {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SetupSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+            is_using_hold,
+            DropType::Softdrop,
+        );
+
+        let field = field_factory::create_field_with_marks(field.to_owned());
+        let need_filled = field_factory::create_field_with_marks(need_filled.to_owned());
+        let may_filled =
+            may_filled.map(|marks| field_factory::create_field_with_marks(marks.to_owned()));
+        let target =
+            SetupTarget::new(need_filled.as_ref(), may_filled.as_deref(), None, 4).unwrap();
+
+        let generator = pattern.parse::<PatternGenerator>().unwrap();
+        searcher
+            .run(field.as_ref(), &target, generator.iter())
+            .unwrap()
+    }
+
+    #[test]
+    fn exact_shape() {
+        let field = String::new() + "__________";
+        #[rustfmt::skip]
+        let need_filled = String::new()
+            + "XX________"
+            + "XX________"
+            + "XXXX______";
+
+        // the O and the I, or two Js
+        let result = run(&field, &need_filled, Some(&need_filled), "*,*", false);
+        assert_eq!(result.get_solutions().len(), 2);
+        let result = run(&field, &need_filled, Some(&need_filled), "*p2", false);
+        assert_eq!(result.get_solutions().len(), 1);
+
+        for solution in result.get_solutions() {
+            let block_field = solution.to_block_field(4);
+            let mut filled = field_factory::create_field(4);
+            for &piece in Piece::value_list() {
+                if let Some(piece_field) = block_field.get(piece) {
+                    filled.merge(piece_field);
+                }
+            }
+            let need_filled = field_factory::create_field_with_marks(need_filled.clone());
+            assert!(filled.contains(need_filled.as_ref()));
+            assert!(need_filled.contains(filled.as_ref()));
+        }
+    }
+
+    #[test]
+    fn covered_sequences() {
+        let field = String::new() + "__________";
+        #[rustfmt::skip]
+        let need_filled = String::new()
+            + "XX________"
+            + "XX________"
+            + "XXXX______";
+
+        // the I must be placed before the O
+        let result = run(&field, &need_filled, Some(&need_filled), "[IO]p2", false);
+        assert_eq!(result.get_solutions().len(), 1);
+        let solution = &result.get_solutions()[0];
+        let sequences = solution
+            .get_covered_sequences()
+            .iter()
+            .map(|&index| result.get_sequences()[index].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(sequences, vec![vec![Piece::I, Piece::O]]);
+
+        // the hold swaps the O and the I
+        let result = run(&field, &need_filled, Some(&need_filled), "O,I", true);
+        assert_eq!(result.get_solutions().len(), 1);
+    }
+
+    #[test]
+    fn may_filled() {
+        let field = String::new() + "XXXXXXX___";
+        let need_filled = String::new() + "_______XXX";
+
+        // no mino fits in the 3 cells
+        let result = run(&field, &need_filled, Some(&need_filled), "*p1", false);
+        assert!(result.get_solutions().is_empty());
+
+        // the T, the L and the J stick out upwards
+        let result = run(&field, &need_filled, None, "*p1", false);
+        let pieces = result
+            .get_solutions()
+            .iter()
+            .map(|solution| solution.get_operations()[0].get_piece())
+            .collect::<HashSet<_>>();
+        assert_eq!(pieces, HashSet::from([Piece::T, Piece::L, Piece::J]));
+    }
+
+    #[test]
+    fn filter() {
+        let field = String::new() + "__________";
+        #[rustfmt::skip]
+        let need_filled = String::new()
+            + "XX________"
+            + "XX________"
+            + "XXXX______";
+        let mut result = run(&field, &need_filled, Some(&need_filled), "*,*", false);
+        assert_eq!(result.get_solutions().len(), 2);
+
+        // both setups leave no holes, but no T-spin slot
+        let field = field_factory::create_field_with_marks(field);
+        let mino_factory = MinoFactory::new();
+        result.retain(&mino_factory, field.as_ref(), 4, &SetupFilter::Strict);
+        assert_eq!(result.get_solutions().len(), 2);
+        result.retain(&mino_factory, field.as_ref(), 4, &SetupFilter::TSpinSlot);
+        assert!(result.get_solutions().is_empty());
+    }
+
+    #[test]
+    fn budget() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SetupSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+            false,
+            DropType::Softdrop,
+        );
+
+        let field = field_factory::create_field(4);
+        #[rustfmt::skip]
+        let need_filled = field_factory::create_field_with_marks(
+            String::new()
+                + "XX________"
+                + "XX________"
+                + "XXXX______",
+        );
+        let target =
+            SetupTarget::new(need_filled.as_ref(), Some(need_filled.as_ref()), None, 4).unwrap();
+        let sequences = || vec![vec![Piece::I, Piece::O], vec![Piece::J, Piece::J]];
+
+        // the O and the I, or the two Js
+        let result = searcher
+            .run_with_budget(field.as_ref(), &target, sequences(), 1 << 10)
+            .unwrap();
+        assert_eq!(result.get_solutions().len(), 2);
+        assert!(searcher
+            .run_with_budget(field.as_ref(), &target, sequences(), 1)
+            .is_err());
+    }
+
+    #[test]
+    fn conflicting_target() {
+        let need_filled = field_factory::create_field_with_marks(String::new() + "X_________");
+        assert!(SetupTarget::new(need_filled.as_ref(), None, None, 4).is_ok());
+        assert!(
+            SetupTarget::new(need_filled.as_ref(), None, Some(need_filled.as_ref()), 4).is_err()
+        );
+    }
+}
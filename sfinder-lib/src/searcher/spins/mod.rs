@@ -1,4 +1,6 @@
 pub mod line;
 pub mod spin;
 pub mod spin_commons;
+pub mod spin_filter;
+pub mod spin_searcher;
 pub mod wall;
@@ -1,7 +1,7 @@
 use crate::sfinder_core::srs::{rotate::Rotate, rotate_direction::RotateDirection};
 
 // Porting note: struct members are ordered so that the Ord implementation implements SpinDefaultPriority
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Spin {
     pub cleared_rows: ClearedRows,
     pub spin: TSpins,
@@ -23,7 +23,7 @@ impl Spin {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TSpins {
     Regular,
     Mini,
@@ -45,7 +45,7 @@ impl TSpins {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TSpinNames {
     NoName,
     Fin,
@@ -76,13 +76,15 @@ impl TSpinNames {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
 pub enum ClearedRows {
     Zero = 0,
     Single,
     Double,
     Triple,
+    // Porting note: only cleared by the I in all-spin
+    Quadruple,
 }
 
 impl From<u8> for ClearedRows {
@@ -92,6 +94,7 @@ impl From<u8> for ClearedRows {
             1 => ClearedRows::Single,
             2 => ClearedRows::Double,
             3 => ClearedRows::Triple,
+            4 => ClearedRows::Quadruple,
             _ => panic!("invalid number of cleared rows: {value}"),
         }
     }
@@ -6,7 +6,9 @@ use crate::{
         action::action::Action, mino_operation::MinoOperation, operation_with_key::OperationWithKey,
     },
     sfinder_core::{
+        action::common::can_put_mino_in_field,
         field::{field::Field, field_constants::FIELD_WIDTH, key_operators},
+        mino::mino::Mino,
         neighbor::simple_original_piece::SimpleOriginalPiece,
         srs::{rotate::Rotate, spin_result::SpinResult},
     },
@@ -97,10 +99,32 @@ pub fn can_t_spin(field: &dyn Field, x: u8, y: u8) -> bool {
         >= 3
 }
 
+// 上下左右のどの方向にも移動できない
+pub fn is_immobile(field: &dyn Field, mino: &Mino, x: u8, y: u8) -> bool {
+    // フィールドより上には移動できる
+    if field.get_max_field_height() as i8 <= y as i8 + mino.get_max_y() + 1 {
+        return false;
+    }
+
+    [
+        (x as i8 - 1, y as i8),
+        (x as i8 + 1, y as i8),
+        (x as i8, y as i8 - 1),
+        (x as i8, y as i8 + 1),
+    ]
+    .into_iter()
+    .all(|(x, y)| {
+        !(u8::try_from(x)
+            .is_ok_and(|x| u8::try_from(y).is_ok_and(|y| can_put_mino_in_field(field, mino, x, y))))
+    })
+}
+
 // out of bounds or exists block
+// Porting note: the cells above the field are regarded as empty, instead of overflowing the board
 fn is_block(field: &dyn Field, x: i8, y: i8) -> bool {
     const RIGHT_BOUND: i8 = FIELD_WIDTH as i8 - 1;
     match (x, y) {
+        (0..=RIGHT_BOUND, y) if field.get_max_field_height() as i8 <= y => false,
         (x @ 0..=RIGHT_BOUND, y @ 0..) => field.exists_block(x as u8, y as u8),
         _ => true,
     }
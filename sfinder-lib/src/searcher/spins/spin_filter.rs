@@ -0,0 +1,210 @@
+//! Porting note: replaces the filter options of the spin entry, with rules that can be combined.
+//! The rules are tested on each solution with the field before the fill pieces are placed.
+
+use super::{
+    spin::{ClearedRows, TSpinNames, TSpins},
+    spin_searcher::SpinSolution,
+};
+use crate::{
+    common::datastore::{action::action::Action, operation::Operation},
+    sfinder_core::{field::field::Field, mino::mino_factory::MinoFactory},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpinFilter {
+    // RegularかMiniか
+    Spin(TSpins),
+    Name(TSpinNames),
+    // 消去するライン数がこれ以上
+    MinClearedRows(ClearedRows),
+    // 穴埋めのミノがすべてこの高さより下にある
+    FillMaxHeight(u8),
+    // Tの各ブロックより下が埋まっている
+    NoHolesBelowT,
+    And(Vec<SpinFilter>),
+    Or(Vec<SpinFilter>),
+    Not(Box<SpinFilter>),
+}
+
+impl SpinFilter {
+    pub fn and(self, other: SpinFilter) -> Self {
+        match self {
+            Self::And(mut filters) => {
+                filters.push(other);
+                Self::And(filters)
+            }
+            filter => Self::And(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: SpinFilter) -> Self {
+        match self {
+            Self::Or(mut filters) => {
+                filters.push(other);
+                Self::Or(filters)
+            }
+            filter => Self::Or(vec![filter, other]),
+        }
+    }
+
+    pub fn test(
+        &self,
+        mino_factory: &MinoFactory,
+        field: &dyn Field,
+        height: u8,
+        solution: &SpinSolution,
+    ) -> bool {
+        let spin = solution.get_spin();
+        match self {
+            Self::Spin(t_spin) => spin.spin == *t_spin,
+            Self::Name(name) => spin.name == *name,
+            Self::MinClearedRows(cleared_rows) => *cleared_rows <= spin.cleared_rows,
+            Self::FillMaxHeight(max_height) => {
+                solution.get_fill_operations().iter().all(|operation| {
+                    let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+                    (operation.get_y() as i8 + mino.get_max_y()) < *max_height as i8
+                })
+            }
+            Self::NoHolesBelowT => !exists_hole_below_t(mino_factory, field, height, solution),
+            Self::And(filters) => filters
+                .iter()
+                .all(|filter| filter.test(mino_factory, field, height, solution)),
+            Self::Or(filters) => filters
+                .iter()
+                .any(|filter| filter.test(mino_factory, field, height, solution)),
+            Self::Not(filter) => !filter.test(mino_factory, field, height, solution),
+        }
+    }
+}
+
+impl std::ops::Not for SpinFilter {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+// Tを置いたときに、Tのブロックより下に空白がある列が存在する
+fn exists_hole_below_t(
+    mino_factory: &MinoFactory,
+    field: &dyn Field,
+    height: u8,
+    solution: &SpinSolution,
+) -> bool {
+    let mut freeze = field.prune(height);
+    for operation in solution.get_fill_operations() {
+        let mino = mino_factory.get(operation.get_piece(), operation.get_rotate());
+        freeze.put(mino, operation.get_x(), operation.get_y());
+        freeze.clear_filled_rows();
+    }
+
+    let t = solution.get_spin_operation();
+    let mino = mino_factory.get(t.get_piece(), t.get_rotate());
+    freeze.put(mino, t.get_x(), t.get_y());
+    mino.get_positions().iter().any(|position| {
+        let x = (t.get_x() as i8 + position.x) as u8;
+        let y = (t.get_y() as i8 + position.y) as u8;
+        !freeze.is_filled_in_column(x, y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::spin_checker::SpinRule,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::spins::spin_searcher::SpinSearcher,
+        sfinder_core::{
+            action::drop_type::DropType,
+            field::field_factory,
+            mino::{mino_shifter::MinoShifter, piece::Piece},
+        },
+    };
+
+    // Returns the number of the solutions that satisfy each filter, with all of the solutions
+    fn count(marks: String, pieces: &[Piece], filters: &[SpinFilter]) -> (Vec<usize>, usize) {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SpinSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            6,
+            4,
+            false,
+            DropType::Softdrop,
+            SpinRule::TSpin,
+        );
+
+        let field = field_factory::create_field_with_marks(marks);
+        let solutions = searcher.search(field.as_ref(), pieces, 0).unwrap();
+        let counts = filters
+            .iter()
+            .map(|filter| {
+                solutions
+                    .iter()
+                    .filter(|solution| filter.test(&mino_factory, field.as_ref(), 6, solution))
+                    .count()
+            })
+            .collect();
+        (counts, solutions.len())
+    }
+
+    #[test]
+    fn spin_and_cleared_rows() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "X__XX_X___"
+            + "X___XXXXXX"
+            + "XX_XXXXXXX";
+
+        let regular_double =
+            SpinFilter::Spin(TSpins::Regular).and(SpinFilter::MinClearedRows(ClearedRows::Double));
+        let (counts, total) = count(
+            marks,
+            &[Piece::T],
+            &[
+                SpinFilter::Spin(TSpins::Regular),
+                SpinFilter::Spin(TSpins::Mini),
+                SpinFilter::MinClearedRows(ClearedRows::Single),
+                regular_double.clone(),
+                regular_double.and(!SpinFilter::Name(TSpinNames::Neo)),
+                SpinFilter::Name(TSpinNames::Neo).or(SpinFilter::Name(TSpinNames::Fin)),
+            ],
+        );
+        assert_eq!(counts[0] + counts[1], total);
+        assert_eq!(counts[2], total);
+        assert_eq!(counts[3], 1);
+        assert_eq!(counts[4], 1);
+        assert_eq!(counts[5], 0);
+    }
+
+    #[test]
+    fn fill_height_and_holes() {
+        #[rustfmt::skip]
+        let marks = String::new()
+            + "X__XX_____"
+            + "X___XX____"
+            + "XX_XXXXXXX";
+
+        let (counts, total) = count(
+            marks,
+            &[Piece::I, Piece::T],
+            &[
+                SpinFilter::FillMaxHeight(4),
+                SpinFilter::FillMaxHeight(2),
+                SpinFilter::NoHolesBelowT,
+                SpinFilter::MinClearedRows(ClearedRows::Double),
+                SpinFilter::MinClearedRows(ClearedRows::Double).and(SpinFilter::NoHolesBelowT),
+            ],
+        );
+        assert_eq!(counts[0], total);
+        assert!(counts[1] < total);
+        assert!(counts[2] < total);
+        assert_eq!(counts[3], 1);
+        assert_eq!(counts[4], 1);
+    }
+}
@@ -0,0 +1,743 @@
+//! Porting note: replaces the search of the spin entry.
+//! The fill pieces are placed one by one at the reachable positions, and the spin piece is placed last.
+//! The spin piece is the T, or any piece when the spin rule is AllSpin.
+//! The rows filled by the fill pieces are cleared before the next piece is placed, so each operation
+//! is in the coordinates of the field when it is placed, as the pages of a tetfu.
+//! The sequences are searched together on a SequenceTree, and the spins found from a state are
+//! memoized per shape of the subtree, so that the orders of the same pieces share the states.
+//! The fill pieces are placed only while the rest of the pieces can fill the rows to clear, which are
+//! counted with SlidedField and RemainderField, and the T is put only at the centers whose corners
+//! are filled as in SpinMaskFields. The search returns an error when more fill placements than
+//! the budget are tried.
+//! Only the placements of the spin piece that are detected by SpinChecker and clear at least the
+//! target number of rows are kept.
+
+use super::{
+    line::{next::remainder_field_runner, slided_field::SlidedField},
+    spin::Spin,
+    spin_filter::SpinFilter,
+    wall::spin_mask_fields::SpinMaskFields,
+};
+use crate::{
+    common::{
+        datastore::{
+            action::action::Action, piece_counter::PieceCounter, simple_operation::SimpleOperation,
+        },
+        spin_checker::{SpinChecker, SpinRule},
+    },
+    searcher::{
+        common::{
+            hold_choices,
+            transposition_table::{StateKey, TranspositionTable},
+        },
+        percent::sequence_tree::SequenceTree,
+    },
+    sfinder_core::{
+        action::{
+            candidate::candidate::Candidate, drop_type::DropType, reachable::reachable_facade,
+        },
+        field::{field::Field, field_constants::FIELD_WIDTH},
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::{
+            mino_rotation::MinoRotation, mino_rotation_detail::MinoRotationDetail, rotate::Rotate,
+        },
+    },
+};
+use std::{collections::HashMap, ops::Range, rc::Rc};
+
+// The number of fill placements tried while searching the spins
+pub const DEFAULT_PLACEMENT_BUDGET: usize = 1 << 23;
+
+// SlidedField moves the lowest row to clear to this row
+const SLIDED_Y: u8 = 3;
+
+// (field after the fill pieces, piece, rotate, x, y of the spin piece)
+type SolutionKey = (Box<dyn Field>, Piece, Rotate, u8, u8);
+
+#[derive(Debug)]
+pub struct SpinSolution {
+    // The fill pieces in the order of placement, and the spin piece at last
+    operations: Vec<SimpleOperation>,
+    spin: Spin,
+    // The indexes of the sequences that can build the solution, in ascending order
+    covered_sequences: Vec<usize>,
+}
+
+impl SpinSolution {
+    pub fn get_operations(&self) -> &[SimpleOperation] {
+        &self.operations
+    }
+
+    pub fn get_fill_operations(&self) -> &[SimpleOperation] {
+        &self.operations[..self.operations.len() - 1]
+    }
+
+    pub fn get_spin_operation(&self) -> &SimpleOperation {
+        self.operations.last().unwrap()
+    }
+
+    pub fn get_spin(&self) -> &Spin {
+        &self.spin
+    }
+
+    pub fn get_covered_sequences(&self) -> &[usize] {
+        &self.covered_sequences
+    }
+}
+
+#[derive(Debug)]
+pub struct SpinResult {
+    sequences: Vec<Vec<Piece>>,
+    solutions: Vec<SpinSolution>,
+}
+
+impl SpinResult {
+    pub fn get_sequences(&self) -> &[Vec<Piece>] {
+        &self.sequences
+    }
+
+    pub fn get_solutions(&self) -> &[SpinSolution] {
+        &self.solutions
+    }
+
+    // Keeps the solutions that satisfy the filter
+    pub fn retain(
+        &mut self,
+        mino_factory: &MinoFactory,
+        field: &dyn Field,
+        height: u8,
+        filter: &SpinFilter,
+    ) {
+        self.solutions
+            .retain(|solution| filter.test(mino_factory, field, height, solution));
+    }
+
+    // Sorts the solutions from the highest priority of the spin, keeping the order when tied
+    pub fn sort_by_priority(&mut self) {
+        self.solutions
+            .sort_by(|a, b| b.get_spin().cmp(a.get_spin()));
+    }
+}
+
+pub struct SpinSearcher<'a> {
+    mino_factory: &'a MinoFactory,
+    candidate: Box<dyn Candidate + 'a>,
+    spin_checker: SpinChecker<'a>,
+    spin_mask_fields: SpinMaskFields,
+    height: u8,
+    // The fill pieces are placed below this height of the field when they are placed
+    allow_fill_max_height: u8,
+    is_using_hold: bool,
+}
+
+// The spins found from a state, with the leaves of the sequences that can do them,
+// relative to the first leaf of the node
+type Found = Rc<[(usize, Range<usize>)]>;
+
+struct Search<'s, 'a> {
+    searcher: &'s mut SpinSearcher<'a>,
+    tree: &'s SequenceTree,
+    // The shape of the subtree of each node and the length of its longest sequence.
+    // The nodes of the same shape, such as after I,T and T,I of a permutation, share the states
+    shapes: Vec<(usize, usize)>,
+    min_cleared_rows: u8,
+    // The number of fill placements that can still be tried
+    budget: usize,
+    visited: TranspositionTable<Found>,
+    indexes: HashMap<SolutionKey, usize>,
+    solutions: Vec<(Vec<SimpleOperation>, Spin)>,
+}
+
+impl Search<'_, '_> {
+    fn search(
+        &mut self,
+        field: &dyn Field,
+        hold: Option<Piece>,
+        node: usize,
+        operations: &mut Vec<SimpleOperation>,
+    ) -> Result<Found, String> {
+        let height = self.searcher.height;
+        let (shape, length) = self.shapes[node];
+        let key = StateKey::new(field, PieceCounter::new(), hold).with_extra(shape as u64);
+        if let Some(found) = self.visited.get(&key) {
+            return Ok(found.clone());
+        }
+
+        let start = self.tree.get_leaves(node).start;
+        let mut found = Vec::new();
+
+        // スピンのミノ以外で埋める必要があるブロック
+        let min_fill_cells = self
+            .searcher
+            .get_min_fill_cells(field, self.min_cleared_rows);
+
+        // ミノ列が終わっているときは、ホールドのミノしか使えない
+        if let (Some(terminal), Some(hold)) = (self.tree.get_terminal(node), hold) {
+            if min_fill_cells == 0 && self.searcher.spin_checker.is_spin_target(hold) {
+                let leaves = terminal - start..terminal - start + 1;
+                self.put_spin(field, hold, operations, leaves, &mut found);
+            }
+        }
+
+        for &(piece, child) in self.tree.get_children(node) {
+            let choices = hold_choices::get_choices(
+                Some(piece),
+                hold,
+                child,
+                self.tree.get_children(child).iter().copied(),
+                self.searcher.is_using_hold,
+            );
+
+            for (piece, next_hold, next_node) in choices {
+                let offset = self.tree.get_leaves(next_node).start - start;
+                if min_fill_cells == 0 && self.searcher.spin_checker.is_spin_target(piece) {
+                    let leaves = self.tree.get_leaves(next_node);
+                    let leaves = offset..offset + leaves.len();
+                    self.put_spin(field, piece, operations, leaves, &mut found);
+                }
+
+                // 置いた後に、スピンのミノと残りのブロックを埋めるミノが残っていること
+                let next_left_pieces = self.shapes[next_node].1 + next_hold.is_some() as usize;
+                if next_left_pieces == 0 || 4 * next_left_pieces < min_fill_cells as usize {
+                    continue;
+                }
+
+                let actions = self.searcher.candidate.search(field, piece, height);
+                for action in actions {
+                    let mino = self.searcher.mino_factory.get(piece, action.get_rotate());
+                    let (x, y) = (action.get_x(), action.get_y());
+                    if self.searcher.allow_fill_max_height as i8 <= y as i8 + mino.get_max_y() {
+                        continue;
+                    }
+
+                    if self.budget == 0 {
+                        return Err("Too many placements to search the spins".into());
+                    }
+                    self.budget -= 1;
+
+                    let mut next_field = field.prune(height);
+                    next_field.put(mino, x, y);
+                    next_field.clear_filled_rows();
+
+                    operations.push(SimpleOperation::new(piece, action.get_rotate(), x, y));
+                    let result = self.search(next_field.as_ref(), next_hold, next_node, operations);
+                    operations.pop();
+
+                    found.extend(result?.iter().map(|(index, leaves)| {
+                        (*index, leaves.start + offset..leaves.end + offset)
+                    }));
+                }
+            }
+        }
+
+        // 同じ解の葉の範囲をまとめる
+        found.sort_unstable_by_key(|(index, leaves)| (*index, leaves.start));
+        let mut merged = Vec::<(usize, Range<usize>)>::with_capacity(found.len());
+        for (index, leaves) in found {
+            match merged.last_mut() {
+                Some((last, range)) if *last == index && leaves.start <= range.end => {
+                    range.end = range.end.max(leaves.end);
+                }
+                _ => merged.push((index, leaves)),
+            }
+        }
+
+        let found = Found::from(merged);
+        self.visited
+            .insert(key, (length + hold.is_some() as usize) as u8, found.clone());
+        Ok(found)
+    }
+
+    fn put_spin(
+        &mut self,
+        field: &dyn Field,
+        piece: Piece,
+        operations: &[SimpleOperation],
+        leaves: Range<usize>,
+        found: &mut Vec<(usize, Range<usize>)>,
+    ) {
+        let height = self.searcher.height;
+
+        // Tは3つ以上の角が埋まっている中心にしか置けない
+        let t_centers = (piece == Piece::T).then(|| self.searcher.get_t_spin_centers(field));
+        if t_centers.as_ref().is_some_and(Vec::is_empty) {
+            return;
+        }
+
+        let actions = self.searcher.candidate.search(field, piece, height);
+        for action in actions {
+            let (rotate, x, y) = (action.get_rotate(), action.get_x(), action.get_y());
+            if t_centers
+                .as_ref()
+                .is_some_and(|centers| !centers.contains(&(x, y)))
+            {
+                continue;
+            }
+
+            let mut freeze = field.prune(height);
+            freeze.put(self.searcher.mino_factory.get(piece, rotate), x, y);
+            let cleared_rows = freeze.clear_filled_rows() as u8;
+            if cleared_rows < self.min_cleared_rows {
+                continue;
+            }
+
+            let operation = SimpleOperation::new(piece, rotate, x, y);
+            let Some(spin) =
+                self.searcher
+                    .spin_checker
+                    .check(field, &operation, height, cleared_rows)
+            else {
+                continue;
+            };
+
+            let key = (field.prune(height), piece, rotate, x, y);
+            let index = *self.indexes.entry(key).or_insert_with(|| {
+                let mut solution = operations.to_vec();
+                solution.push(operation);
+                self.solutions.push((solution, spin));
+                self.solutions.len() - 1
+            });
+            found.push((index, leaves.clone()));
+        }
+    }
+}
+
+// (whether a sequence ends at the node, the pieces and the shapes of the children)
+type ShapeKey = (bool, Vec<(Piece, usize)>);
+
+// Numbers the subtrees of the same shape with the same id, and returns the length of the longest sequence
+fn add_shape(
+    tree: &SequenceTree,
+    node: usize,
+    ids: &mut HashMap<ShapeKey, usize>,
+    shapes: &mut Vec<(usize, usize)>,
+) -> (usize, usize) {
+    let children = tree
+        .get_children(node)
+        .iter()
+        .map(|&(piece, child)| (piece, add_shape(tree, child, ids, shapes)))
+        .collect::<Vec<_>>();
+    let length = children
+        .iter()
+        .map(|(_, (_, length))| length + 1)
+        .max()
+        .unwrap_or(0);
+
+    let key = (
+        tree.get_terminal(node).is_some(),
+        children
+            .iter()
+            .map(|&(piece, (id, _))| (piece, id))
+            .collect(),
+    );
+    let next_id = ids.len();
+    let id = *ids.entry(key).or_insert(next_id);
+
+    if shapes.len() <= node {
+        shapes.resize(node + 1, (0, 0));
+    }
+    shapes[node] = (id, length);
+    (id, length)
+}
+
+impl<'a> SpinSearcher<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        height: u8,
+        allow_fill_max_height: u8,
+        is_using_hold: bool,
+        drop_type: DropType,
+        spin_rule: SpinRule,
+    ) -> Self {
+        Self {
+            mino_factory,
+            candidate: drop_type.create_candidate(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                height,
+            ),
+            // スピンの判定は、ドロップの種類によらず回転で入れられるかで判断する
+            spin_checker: SpinChecker::new(
+                mino_factory,
+                MinoRotationDetail::new(mino_factory, mino_rotation),
+                reachable_facade::create_locked(
+                    mino_factory,
+                    mino_shifter,
+                    mino_rotation,
+                    height,
+                    drop_type.use_180_rotation(),
+                ),
+                drop_type.use_180_rotation(),
+                spin_rule,
+            ),
+            // Tの中心は、上の角がフィールドに収まる高さまで
+            spin_mask_fields: SpinMaskFields::new(height.saturating_sub(2), height),
+            height,
+            allow_fill_max_height,
+            is_using_hold,
+        }
+    }
+
+    // The least number of cells that the fill pieces must fill before the spin piece clears
+    // min_cleared_rows rows. The rows between the cleared rows can be cleared by the fill pieces,
+    // so at least as many consecutive rows are filled, and the spin piece fills at most 4 cells of them.
+    fn get_min_fill_cells(&self, field: &dyn Field, min_cleared_rows: u8) -> u32 {
+        if min_cleared_rows == 0 {
+            return 0;
+        }
+
+        let filled_rows = field.get_filled_rows_key();
+        (0..=self.height.saturating_sub(min_cleared_rows))
+            .map(|y| {
+                let slided = SlidedField::new(field.prune(self.height), y, filled_rows);
+                (SLIDED_Y..SLIDED_Y + min_cleared_rows)
+                    .flat_map(|row| remainder_field_runner::extract(slided.get_field(), row))
+                    .map(|remainder| remainder.target_block_count as u32)
+                    .sum::<u32>()
+            })
+            .min()
+            .unwrap()
+            .saturating_sub(4)
+    }
+
+    // The centers of the T that have at least 3 corners filled
+    fn get_t_spin_centers(&self, field: &dyn Field) -> Vec<(u8, u8)> {
+        (0..self.height.saturating_sub(1))
+            .flat_map(|y| (0..FIELD_WIDTH).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                field.is_empty_block(x, y)
+                    && self
+                        .spin_mask_fields
+                        .get_with_delete_key(x, y, 0)
+                        .any(|mask_field| field.contains(mask_field.rest.as_ref()))
+            })
+            .collect()
+    }
+
+    // Returns the spins that can be done by the sequence, clearing at least min_cleared_rows.
+    // The spin piece can be placed after any number of fill pieces,
+    // and the rest of the pieces are unused.
+    pub fn search(
+        &mut self,
+        field: &dyn Field,
+        pieces: &[Piece],
+        min_cleared_rows: u8,
+    ) -> Result<Vec<SpinSolution>, String> {
+        self.run(field, [pieces.to_vec()], min_cleared_rows)
+            .map(|result| result.solutions)
+    }
+
+    // Same as run_with_budget, with the default budget
+    pub fn run(
+        &mut self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+        min_cleared_rows: u8,
+    ) -> Result<SpinResult, String> {
+        self.run_with_budget(field, sequences, min_cleared_rows, DEFAULT_PLACEMENT_BUDGET)
+    }
+
+    // Returns the spins that can be done by at least one of the sequences,
+    // with the distinct sequences that can do each of them, as the indexes in the sorted sequences.
+    // The same fill and spin piece placed in different orders are regarded as one solution.
+    // Returns an error when more fill placements than the budget are tried.
+    pub fn run_with_budget(
+        &mut self,
+        field: &dyn Field,
+        sequences: impl IntoIterator<Item = Vec<Piece>>,
+        min_cleared_rows: u8,
+        budget: usize,
+    ) -> Result<SpinResult, String> {
+        let tree = SequenceTree::new(sequences.into_iter().collect());
+        let mut shapes = Vec::new();
+        add_shape(&tree, SequenceTree::ROOT, &mut HashMap::new(), &mut shapes);
+
+        let mut search = Search {
+            searcher: self,
+            tree: &tree,
+            shapes,
+            min_cleared_rows,
+            budget,
+            visited: TranspositionTable::default(),
+            indexes: HashMap::new(),
+            solutions: Vec::new(),
+        };
+        let found = search.search(field, None, SequenceTree::ROOT, &mut Vec::new())?;
+
+        let mut covered_sequences = vec![Vec::new(); search.solutions.len()];
+        for (index, leaves) in found.iter() {
+            covered_sequences[*index].extend(leaves.clone());
+        }
+
+        let solutions = search
+            .solutions
+            .into_iter()
+            .zip(covered_sequences)
+            .map(|((operations, spin), covered_sequences)| SpinSolution {
+                operations,
+                spin,
+                covered_sequences,
+            })
+            .collect();
+
+        Ok(SpinResult {
+            sequences: tree.get_sequences().to_vec(),
+            solutions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extras::test_functions::pieces;
+    use crate::{
+        common::datastore::operation::Operation,
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        searcher::spins::spin::{ClearedRows, TSpins},
+        sfinder_core::field::field_factory,
+    };
+
+    fn field() -> Box<dyn Field> {
+        // the I fills the right of the second row, and the T is put in the slot for a T-spin double
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "X__XX_____"
+                + "X___XX____"
+                + "XX_XXXXXXX",
+        );
+        field
+    }
+
+    fn run(sequences: &[&str], is_using_hold: bool, min_cleared_rows: u8) -> SpinResult {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SpinSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            6,
+            3,
+            is_using_hold,
+            DropType::Softdrop,
+            SpinRule::TSpin,
+        );
+
+        searcher
+            .run(
+                field().as_ref(),
+                sequences.iter().map(|sequence| pieces(sequence)),
+                min_cleared_rows,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn t_spin_double() {
+        let result = run(&["IT"], false, 2);
+        assert!(!result.get_solutions().is_empty());
+        for solution in result.get_solutions() {
+            assert_eq!(solution.get_spin().spin, TSpins::Regular);
+            assert_eq!(solution.get_spin().cleared_rows, ClearedRows::Double);
+            assert_eq!(solution.get_fill_operations()[0].get_piece(), Piece::I);
+
+            let t = solution.get_spin_operation();
+            assert_eq!(
+                (t.get_piece(), t.get_rotate(), t.get_x(), t.get_y()),
+                (Piece::T, Rotate::Reverse, 2, 1)
+            );
+        }
+
+        // only the bottom row is cleared without the I
+        assert!(run(&["T"], false, 2).get_solutions().is_empty());
+        assert!(!run(&["T"], false, 1).get_solutions().is_empty());
+    }
+
+    #[test]
+    fn hold() {
+        assert!(run(&["TI"], false, 2).get_solutions().is_empty());
+        assert!(!run(&["TI"], true, 2).get_solutions().is_empty());
+    }
+
+    #[test]
+    fn fill_clears_rows() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SpinSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            6,
+            4,
+            false,
+            DropType::Softdrop,
+            SpinRule::TSpin,
+        );
+
+        // the I clears the bottom row, and the L fills the right of the second row after it
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "X__XX_____"
+                + "X___XX____"
+                + "XX_XXXXXX_"
+                + "XXXXXXXXX_",
+        );
+        let result = searcher.run(field.as_ref(), [pieces("ILT")], 2).unwrap();
+
+        let solution = result
+            .get_solutions()
+            .iter()
+            .find(|solution| solution.get_spin().spin == TSpins::Regular)
+            .unwrap();
+        assert_eq!(solution.get_spin().cleared_rows, ClearedRows::Double);
+
+        let operations = solution
+            .get_operations()
+            .iter()
+            .map(|operation| {
+                (
+                    operation.get_piece(),
+                    operation.get_rotate(),
+                    operation.get_x(),
+                    operation.get_y(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operations,
+            vec![
+                (Piece::I, Rotate::Left, 9, 1),
+                (Piece::L, Rotate::Spawn, 7, 1),
+                (Piece::T, Rotate::Reverse, 2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_spin() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let create = |spin_rule| {
+            SpinSearcher::new(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation.as_ref(),
+                6,
+                6,
+                false,
+                DropType::Softdrop,
+                spin_rule,
+            )
+        };
+
+        // the Z is rotated into the slot and clears the second row
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXX___XXXX"
+                + "XXXXX__XXX"
+                + "XXXX__XXXX",
+        );
+
+        assert!(create(SpinRule::TSpin)
+            .search(field.as_ref(), &pieces("Z"), 1)
+            .unwrap()
+            .is_empty());
+
+        let solutions = create(SpinRule::AllSpin)
+            .search(field.as_ref(), &pieces("Z"), 1)
+            .unwrap();
+        assert!(!solutions.is_empty());
+        for solution in &solutions {
+            assert!(solution.get_fill_operations().is_empty());
+            assert_eq!(solution.get_spin_operation().get_piece(), Piece::Z);
+            assert_eq!(solution.get_spin().spin, TSpins::Mini);
+            assert_eq!(solution.get_spin().cleared_rows, ClearedRows::Single);
+        }
+    }
+
+    #[test]
+    fn covered_sequences() {
+        let result = run(&["IT", "TI", "ITO", "IT"], true, 2);
+        assert_eq!(result.get_sequences().len(), 3);
+
+        let solution = result
+            .get_solutions()
+            .iter()
+            .find(|solution| {
+                let i = &solution.get_fill_operations()[0];
+                (i.get_rotate(), i.get_x(), i.get_y()) == (Rotate::Spawn, 7, 1)
+            })
+            .unwrap();
+        assert_eq!(solution.get_covered_sequences(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn budget() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut searcher = SpinSearcher::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            6,
+            3,
+            false,
+            DropType::Softdrop,
+            SpinRule::TSpin,
+        );
+
+        assert!(searcher
+            .run_with_budget(field().as_ref(), [pieces("IT")], 2, 1 << 10)
+            .is_ok());
+        assert!(searcher
+            .run_with_budget(field().as_ref(), [pieces("IT")], 2, 1)
+            .is_err());
+
+        // 4段を消すには、4つのミノでは足りない
+        let empty = field_factory::create_field(6);
+        let result = searcher
+            .run_with_budget(empty.as_ref(), [pieces("ISZO")], 4, 0)
+            .unwrap();
+        assert!(result.get_solutions().is_empty());
+    }
+
+    #[test]
+    fn retain_and_sort() {
+        let mino_factory = MinoFactory::new();
+        let mut result = run(&["IT"], false, 0);
+        let total = result.get_solutions().len();
+
+        result.sort_by_priority();
+        assert_eq!(
+            result.get_solutions()[0].get_spin().cleared_rows,
+            ClearedRows::Double
+        );
+        assert!(result
+            .get_solutions()
+            .windows(2)
+            .all(|pair| pair[0].get_spin() >= pair[1].get_spin()));
+
+        result.retain(
+            &mino_factory,
+            field().as_ref(),
+            6,
+            &SpinFilter::Spin(TSpins::Regular),
+        );
+        assert!(!result.get_solutions().is_empty() && result.get_solutions().len() < total);
+        assert!(result
+            .get_solutions()
+            .iter()
+            .all(|solution| solution.get_spin().spin == TSpins::Regular));
+    }
+}
@@ -0,0 +1,85 @@
+//! Porting note: the candidate of DeepdropReachable, which places a mino at any position on the ground
+//! even if it cannot be moved there.
+
+use super::candidate::Candidate;
+use crate::{
+    common::datastore::action::minimal_action::MinimalAction,
+    sfinder_core::{
+        field::field::Field,
+        mino::{
+            mino_factory::MinoFactory,
+            mino_shifter::{IMinoShifter, MinoShifter},
+            piece::Piece,
+        },
+    },
+    sfinder_lib::coordinate_walker::get_ranges,
+};
+use nohash::{BuildNoHashHasher, IntSet};
+
+pub struct DeepdropCandidate<'a> {
+    mino_factory: &'a MinoFactory,
+    mino_shifter: &'a MinoShifter,
+}
+
+impl<'a> DeepdropCandidate<'a> {
+    pub fn new(mino_factory: &'a MinoFactory, mino_shifter: &'a MinoShifter) -> Self {
+        Self {
+            mino_factory,
+            mino_shifter,
+        }
+    }
+}
+
+impl Candidate for DeepdropCandidate<'_> {
+    fn search(
+        &mut self,
+        field: &dyn Field,
+        piece: Piece,
+        valid_height: u8,
+    ) -> IntSet<MinimalAction> {
+        let mut actions = IntSet::with_hasher(BuildNoHashHasher::default());
+
+        for rotate in self.mino_shifter.get_unique_rotates(piece) {
+            let mino = self.mino_factory.get(piece, rotate);
+
+            let (x_range, y_range) = get_ranges(mino, field.get_width(), valid_height);
+
+            for x in x_range {
+                for y in y_range.clone() {
+                    if field.can_put(mino, x, y) && field.is_on_ground(mino, x, y) {
+                        actions.insert(MinimalAction::new(x, y, rotate));
+                    }
+                }
+            }
+        }
+
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::{field::field_factory, srs::rotate::Rotate};
+
+    #[test]
+    fn search() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mut candidate = DeepdropCandidate::new(&mino_factory, &mino_shifter);
+        // 閉じた穴にも置くことができる
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXXXXXXXXX"
+                + "XXXX____XX"
+                + "XXXXXXXXXX",
+        );
+
+        let actions = candidate.search(field.as_ref(), Piece::I, 3);
+        assert_eq!(
+            actions.into_iter().collect::<Vec<_>>(),
+            [MinimalAction::new(5, 1, Rotate::Spawn)]
+        );
+    }
+}
@@ -39,7 +39,7 @@ impl Candidate for HarddropCandidate<'_> {
         for rotate in self.mino_shifter.get_unique_rotates(piece) {
             let mino = self.mino_factory.get(piece, rotate);
 
-            let (x_range, y_range) = get_ranges(mino, valid_height);
+            let (x_range, y_range) = get_ranges(mino, field.get_width(), valid_height);
 
             let y = valid_height + y_range.start;
             let max_y = y_range.end;
@@ -3,7 +3,7 @@ use crate::{
     common::datastore::action::{cache::locked_cache::LockedCache, minimal_action::MinimalAction},
     sfinder_core::{
         action::common::{can_put_mino_in_field, FromDirection},
-        field::{field::Field, field_constants::FIELD_WIDTH},
+        field::field::Field,
         mino::{
             mino::Mino,
             mino_factory::MinoFactory,
@@ -85,7 +85,7 @@ impl<'a> Locked180Candidate<'a> {
                     let right_x = x + 1;
 
                     direction != FromDirection::Right
-                        && (right_x as i8) < FIELD_WIDTH as i8 - mino.get_max_x()
+                        && (right_x as i8) < field.get_width() as i8 - mino.get_max_x()
                         && field.can_put(mino, right_x, y)
                         && self.check(field, mino, right_x, y, FromDirection::Left)
                 }
@@ -156,13 +156,13 @@ impl Candidate for Locked180Candidate<'_> {
         valid_height: u8,
     ) -> IntSet<MinimalAction> {
         self.appear_y = valid_height;
-        self.locked_cache.clear();
+        self.locked_cache.clear_with_width(field.get_width());
 
         let mut actions = IntSet::with_hasher(BuildNoHashHasher::default());
 
         for &rotate in Rotate::value_list() {
             let mino = self.mino_factory.get(piece, rotate);
-            let (x_range, y_range) = get_ranges(mino, valid_height);
+            let (x_range, y_range) = get_ranges(mino, field.get_width(), valid_height);
 
             for x in x_range {
                 for y in y_range.clone().rev() {
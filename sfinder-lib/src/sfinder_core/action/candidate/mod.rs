@@ -1,6 +1,8 @@
 pub mod candidate;
 pub mod candidate_facade;
+pub mod deepdrop_candidate;
 pub mod harddrop_candidate;
 pub mod locked_180_candidate;
 pub mod locked_candidate;
 pub mod softdrop_t_only_candidate;
+pub mod t_spin_or_harddrop_candidate;
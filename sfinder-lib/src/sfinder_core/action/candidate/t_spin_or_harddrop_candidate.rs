@@ -0,0 +1,186 @@
+use super::{
+    candidate::{Candidate, ILockedCandidate},
+    candidate_facade,
+    harddrop_candidate::HarddropCandidate,
+};
+use crate::{
+    common::{
+        datastore::action::{action::Action, minimal_action::MinimalAction},
+        spin_checker::SpinRule,
+    },
+    sfinder_core::{
+        action::reachable::{
+            reachable::Reachable, t_spin_or_harddrop_reachable::TSpinOrHarddropReachable,
+        },
+        field::field::Field,
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter, piece::Piece},
+        srs::mino_rotation::MinoRotation,
+    },
+};
+use nohash::IntSet;
+
+/// Porting note: the candidate counterpart of TSpinOrHarddropReachable.
+/// T is placed only if it makes a T-spin that clears at least min_cleared_rows rows,
+/// and the other pieces are harddropped, or also placed by such a spin when the spin rule is AllSpin.
+pub struct TSpinOrHarddropCandidate<'a> {
+    mino_factory: &'a MinoFactory,
+    harddrop_candidate: HarddropCandidate<'a>,
+    locked_candidate: Box<dyn ILockedCandidate + 'a>,
+    t_spin_reachable: TSpinOrHarddropReachable<'a>,
+    spin_rule: SpinRule,
+}
+
+impl<'a> TSpinOrHarddropCandidate<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_y: u8,
+        min_cleared_rows: u8,
+        regular_only: bool,
+        use_180_rotation: bool,
+        spin_rule: SpinRule,
+    ) -> Self {
+        Self {
+            mino_factory,
+            harddrop_candidate: HarddropCandidate::new(mino_factory, mino_shifter),
+            locked_candidate: candidate_facade::create_locked(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                use_180_rotation,
+            ),
+            t_spin_reachable: TSpinOrHarddropReachable::new(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                min_cleared_rows,
+                regular_only,
+                use_180_rotation,
+                spin_rule,
+            ),
+            spin_rule,
+        }
+    }
+}
+
+impl Candidate for TSpinOrHarddropCandidate<'_> {
+    fn search(
+        &mut self,
+        field: &dyn Field,
+        piece: Piece,
+        valid_height: u8,
+    ) -> IntSet<MinimalAction> {
+        if piece == Piece::T || self.spin_rule == SpinRule::AllSpin {
+            let mut actions = self.locked_candidate.search(field, piece, valid_height);
+            actions.retain(|action| {
+                self.t_spin_reachable.checks(
+                    field,
+                    self.mino_factory.get(piece, action.get_rotate()),
+                    action.get_x(),
+                    action.get_y(),
+                    valid_height,
+                )
+            });
+            actions
+        } else {
+            self.harddrop_candidate.search(field, piece, valid_height)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{field::field_factory, srs::rotate::Rotate},
+    };
+
+    #[test]
+    fn search() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let mut candidate = TSpinOrHarddropCandidate::new(
+            &mino_factory,
+            &mino_shifter,
+            mino_rotation.as_ref(),
+            4,
+            2,
+            true,
+            false,
+            SpinRule::TSpin,
+        );
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "X__XX_____"
+                + "X___XXXXXX"
+                + "XX_XXXXXXX"
+        );
+
+        // TSDのみ
+        let actions = candidate.search(field.as_ref(), Piece::T, 4);
+        assert_eq!(
+            actions.into_iter().collect::<Vec<_>>(),
+            [MinimalAction::new(2, 1, Rotate::Reverse)]
+        );
+
+        // T以外はハードドロップのみ
+        assert_eq!(
+            candidate.search(field.as_ref(), Piece::I, 4),
+            HarddropCandidate::new(&mino_factory, &mino_shifter).search(
+                field.as_ref(),
+                Piece::I,
+                4
+            )
+        );
+    }
+
+    #[test]
+    fn all_spin() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let create = |spin_rule| {
+            TSpinOrHarddropCandidate::new(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation.as_ref(),
+                6,
+                1,
+                false,
+                false,
+                spin_rule,
+            )
+        };
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXX___XXXX"
+                + "XXXXX__XXX"
+                + "XXXX__XXXX"
+        );
+
+        // the Z spin is added to the harddrops
+        let harddrops = HarddropCandidate::new(&mino_factory, &mino_shifter).search(
+            field.as_ref(),
+            Piece::Z,
+            6,
+        );
+        assert_eq!(
+            create(SpinRule::TSpin).search(field.as_ref(), Piece::Z, 6),
+            harddrops
+        );
+
+        let actions = create(SpinRule::AllSpin).search(field.as_ref(), Piece::Z, 6);
+        assert!(harddrops.is_subset(&actions));
+        assert!(actions.contains(&MinimalAction::new(5, 1, Rotate::Spawn)));
+    }
+}
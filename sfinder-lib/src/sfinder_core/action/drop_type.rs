@@ -0,0 +1,280 @@
+//! Porting note: replaces DropType, and also creates the candidates and reachables of each drop,
+//! instead of each caller choosing between the facades.
+
+use super::{
+    candidate::{
+        candidate::Candidate, candidate_facade, deepdrop_candidate::DeepdropCandidate,
+        harddrop_candidate::HarddropCandidate, locked_candidate::LockedCandidate,
+        softdrop_t_only_candidate::SoftdropTOnlyCandidate,
+        t_spin_or_harddrop_candidate::TSpinOrHarddropCandidate,
+    },
+    reachable::{
+        deepdrop_reachable::DeepdropReachable, harddrop_reachable::HarddropReachable,
+        reachable::Reachable, reachable_facade, softdrop_t_only_reachable::SoftdropTOnlyReachable,
+        t_spin_or_harddrop_reachable::TSpinOrHarddropReachable,
+    },
+};
+use crate::{
+    common::spin_checker::SpinRule,
+    sfinder_core::{
+        mino::{mino_factory::MinoFactory, mino_shifter::MinoShifter},
+        srs::mino_rotation::MinoRotation,
+    },
+};
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropType {
+    #[default]
+    Softdrop,
+    Harddrop,
+    Rotation180,
+    // Tだけソフトドロップを許可する
+    TSoftdrop,
+    // TはTスピンでのみ置くことができ、それ以外はハードドロップ
+    TSpin {
+        min_cleared_rows: u8,
+        regular_only: bool,
+    },
+    // 地面に接していれば、移動できない位置にも置くことができる
+    Deepdrop,
+}
+
+impl DropType {
+    pub fn use_180_rotation(&self) -> bool {
+        matches!(self, Self::Rotation180)
+    }
+
+    pub fn create_candidate<'a>(
+        &self,
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_y: u8,
+    ) -> Box<dyn Candidate + 'a> {
+        match *self {
+            Self::Softdrop | Self::Rotation180 => candidate_facade::create_locked(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                self.use_180_rotation(),
+            ),
+            Self::Harddrop => Box::new(HarddropCandidate::new(mino_factory, mino_shifter)),
+            Self::TSoftdrop => Box::new(SoftdropTOnlyCandidate::new(
+                HarddropCandidate::new(mino_factory, mino_shifter),
+                LockedCandidate::new(mino_factory, mino_shifter, mino_rotation, max_y),
+            )),
+            Self::TSpin {
+                min_cleared_rows,
+                regular_only,
+            } => Box::new(TSpinOrHarddropCandidate::new(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                min_cleared_rows,
+                regular_only,
+                false,
+                SpinRule::TSpin,
+            )),
+            Self::Deepdrop => Box::new(DeepdropCandidate::new(mino_factory, mino_shifter)),
+        }
+    }
+
+    pub fn create_reachable<'a>(
+        &self,
+        mino_factory: &'a MinoFactory,
+        mino_shifter: &'a MinoShifter,
+        mino_rotation: &'a dyn MinoRotation,
+        max_y: u8,
+    ) -> Box<dyn Reachable + 'a> {
+        match *self {
+            Self::Softdrop | Self::Rotation180 => reachable_facade::create_locked(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                self.use_180_rotation(),
+            ),
+            Self::Harddrop => Box::new(HarddropReachable::new(max_y)),
+            Self::TSoftdrop => Box::new(SoftdropTOnlyReachable::new(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                false,
+            )),
+            Self::TSpin {
+                min_cleared_rows,
+                regular_only,
+            } => Box::new(TSpinOrHarddropReachable::new(
+                mino_factory,
+                mino_shifter,
+                mino_rotation,
+                max_y,
+                min_cleared_rows,
+                regular_only,
+                false,
+                SpinRule::TSpin,
+            )),
+            Self::Deepdrop => Box::new(DeepdropReachable {}),
+        }
+    }
+}
+
+impl FromStr for DropType {
+    type Err = String;
+
+    // e.g. softdrop, harddrop, 180, tsoftdrop, tspin2, tspin2-regular, deepdrop
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "softdrop" | "soft" => Ok(Self::Softdrop),
+            "harddrop" | "hard" => Ok(Self::Harddrop),
+            "180" => Ok(Self::Rotation180),
+            "tsoftdrop" | "t-softdrop" => Ok(Self::TSoftdrop),
+            "deepdrop" => Ok(Self::Deepdrop),
+            _ => {
+                let (rows, regular_only) = match value.strip_suffix("-regular") {
+                    Some(rows) => (rows, true),
+                    None => (value, false),
+                };
+
+                match rows
+                    .strip_prefix("tspin")
+                    .and_then(|rows| rows.parse::<u8>().ok())
+                {
+                    Some(min_cleared_rows @ 0..=3) => Ok(Self::TSpin {
+                        min_cleared_rows,
+                        regular_only,
+                    }),
+                    _ => Err(format!("Unsupported drop: {value}")),
+                }
+            }
+        }
+    }
+}
+
+impl Display for DropType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Softdrop => write!(f, "softdrop"),
+            Self::Harddrop => write!(f, "harddrop"),
+            Self::Rotation180 => write!(f, "180"),
+            Self::TSoftdrop => write!(f, "tsoftdrop"),
+            Self::TSpin {
+                min_cleared_rows,
+                regular_only,
+            } => {
+                write!(f, "tspin{min_cleared_rows}")?;
+                if *regular_only {
+                    write!(f, "-regular")?;
+                }
+                Ok(())
+            }
+            Self::Deepdrop => write!(f, "deepdrop"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entry::common::kicks::factory::srs_mino_rotation_factory,
+        sfinder_core::{field::field_factory, mino::piece::Piece, srs::rotate::Rotate},
+    };
+
+    #[test]
+    fn parse() {
+        for name in [
+            "softdrop",
+            "harddrop",
+            "180",
+            "tsoftdrop",
+            "tspin0",
+            "tspin1",
+            "tspin2",
+            "tspin3-regular",
+            "deepdrop",
+        ] {
+            let drop_type = name.parse::<DropType>().unwrap();
+            assert_eq!(drop_type.to_string(), name);
+        }
+
+        assert_eq!("soft".parse(), Ok(DropType::Softdrop));
+        assert_eq!(
+            "tspin2-regular".parse(),
+            Ok(DropType::TSpin {
+                min_cleared_rows: 2,
+                regular_only: true
+            })
+        );
+
+        for name in ["", "tspin", "tspin4", "tspin-1", "180-regular", "Softdrop"] {
+            assert!(name.parse::<DropType>().is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn candidates() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXXX______"
+                + "XXXX______"
+                + "__________"
+                + "XXXXXXX___"
+        );
+
+        let count = |drop_type: DropType, piece| {
+            drop_type
+                .create_candidate(&mino_factory, &mino_shifter, mino_rotation.as_ref(), 4)
+                .search(field.as_ref(), piece, 4)
+                .len()
+        };
+
+        // 屋根の下にはソフトドロップでのみ置くことができる
+        assert!(count(DropType::Harddrop, Piece::I) < count(DropType::Softdrop, Piece::I));
+        assert_eq!(
+            count(DropType::TSoftdrop, Piece::I),
+            count(DropType::Harddrop, Piece::I)
+        );
+        assert_eq!(
+            count(DropType::TSoftdrop, Piece::T),
+            count(DropType::Softdrop, Piece::T)
+        );
+    }
+
+    #[test]
+    fn reachables() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXXX______"
+                + "XXXX______"
+                + "__________"
+                + "XXXXXXX___"
+        );
+        let mino = mino_factory.get(Piece::I, Rotate::Spawn);
+
+        let checks = |drop_type: DropType| {
+            drop_type
+                .create_reachable(&mino_factory, &mino_shifter, mino_rotation.as_ref(), 4)
+                .checks(field.as_ref(), mino, 1, 1, 4)
+        };
+
+        assert!(checks(DropType::Softdrop));
+        assert!(!checks(DropType::Harddrop));
+        assert!(!checks(DropType::TSoftdrop));
+        assert!(checks(DropType::Deepdrop));
+    }
+}
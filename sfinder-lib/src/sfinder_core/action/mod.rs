@@ -1,3 +1,4 @@
 pub mod candidate;
 pub mod common;
+pub mod drop_type;
 pub mod reachable;
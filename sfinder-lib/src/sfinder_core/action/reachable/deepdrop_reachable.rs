@@ -1,4 +1,4 @@
-//! Only used by SequenceFumenParser
+//! Porting note: used by DropType::Deepdrop, since SequenceFumenParser is not ported
 
 use super::reachable::Reachable;
 use crate::sfinder_core::{field::field::Field, mino::mino::Mino};
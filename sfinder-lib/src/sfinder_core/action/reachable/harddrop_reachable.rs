@@ -53,7 +53,7 @@ mod tests {
     use crate::{
         common::datastore::action::action::Action,
         sfinder_core::{
-            field::field_factory,
+            field::{field_constants::FIELD_WIDTH, field_factory},
             mino::{
                 mino_factory::MinoFactory,
                 mino_shifter::{IMinoShifter, MinoShifter},
@@ -99,7 +99,7 @@ mod tests {
         let rotate = randoms::gen_rotate(&mut rngs);
         let mino = mino_factory.get(piece, rotate);
 
-        let (x_range, y_range) = get_ranges(mino, 4);
+        let (x_range, y_range) = get_ranges(mino, FIELD_WIDTH, 4);
 
         for x in x_range {
             for y in y_range.clone() {
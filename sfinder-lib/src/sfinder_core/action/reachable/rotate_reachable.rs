@@ -3,7 +3,7 @@ use crate::{
     common::datastore::action::{action::Action, cache::minimal_locked_cache::MinimalLockedCache},
     sfinder_core::{
         action::common::{can_put_mino_in_field, FromDirection},
-        field::{field::Field, field_constants::FIELD_WIDTH},
+        field::field::Field,
         mino::{mino::Mino, mino_factory::MinoFactory, mino_shifter::IMinoShifter},
         srs::{mino_rotation::MinoRotation, rotate_direction::RotateDirection},
     },
@@ -90,7 +90,7 @@ impl<'a> RotateReachable<'a> {
 
         let right_x = x + 1;
         if direction != FromDirection::Right
-            && (right_x as i8) < FIELD_WIDTH as i8 - mino.get_max_x()
+            && (right_x as i8) < field.get_width() as i8 - mino.get_max_x()
             && field.can_put(mino, right_x, y)
             && self.check_inner(field, mino, right_x, y, FromDirection::Left)
         {
@@ -160,7 +160,7 @@ impl Reachable for RotateReachable<'_> {
         assert!(field.can_put(mino, x, y));
 
         self.appear_y = valid_height;
-        self.locked_cache.clear();
+        self.locked_cache.clear_with_width(field.get_width());
 
         let piece = mino.get_piece();
         let rotate = mino.get_rotate();
@@ -189,7 +189,7 @@ impl Reachable for RotateReachable<'_> {
         debug_assert!(field.can_put(mino, x, y));
 
         self.appear_y = valid_height;
-        self.locked_cache.clear();
+        self.locked_cache.clear_with_width(field.get_width());
 
         self.first_check(field, mino, x, y)
     }
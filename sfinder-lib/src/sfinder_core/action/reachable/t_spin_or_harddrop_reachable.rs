@@ -1,6 +1,9 @@
 use super::{harddrop_reachable::HarddropReachable, reachable::Reachable, reachable_facade};
 use crate::{
-    common::{datastore::simple_mino_operation::SimpleMinoOperation, spin_checker::SpinChecker},
+    common::{
+        datastore::simple_mino_operation::SimpleMinoOperation,
+        spin_checker::{SpinChecker, SpinRule},
+    },
     searcher::spins::spin::TSpins,
     sfinder_core::{
         field::field::Field,
@@ -17,6 +20,7 @@ pub struct TSpinOrHarddropReachable<'a> {
 }
 
 impl<'a> TSpinOrHarddropReachable<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         mino_factory: &'a MinoFactory,
         mino_shifter: &'a MinoShifter,
@@ -25,6 +29,7 @@ impl<'a> TSpinOrHarddropReachable<'a> {
         min_cleared_rows: u8,
         regular_only: bool,
         use_180_rotation: bool,
+        spin_rule: SpinRule,
     ) -> Self {
         Self {
             harddrop_reachable: HarddropReachable::new(max_y),
@@ -41,6 +46,7 @@ impl<'a> TSpinOrHarddropReachable<'a> {
                 ),
                 // TODO: it seems like we should be able to just set use_180_rotation of create_locked from the constructor of SpinChecker?
                 use_180_rotation,
+                spin_rule,
             ),
             regular_only,
         }
@@ -59,26 +65,35 @@ impl Reachable for TSpinOrHarddropReachable<'_> {
         debug_assert!(field.can_put(mino, x, y));
         assert_eq!(field.get_filled_rows_key(), 0);
 
-        if mino.get_piece() == Piece::T {
-            let mut freeze = dyn_clone::clone_box(field);
-            freeze.put(mino, x, y);
-            let cleared_rows = freeze.clear_filled_rows() as u8;
-
-            (cleared_rows >= self.min_cleared_rows)
-                && match self.spin_checker.check(
-                    field,
-                    &SimpleMinoOperation::new(mino, x, y),
-                    valid_height,
-                    cleared_rows,
-                ) {
-                    Some(spin) if self.regular_only && spin.spin == TSpins::Mini => false,
-                    Some(_) => true,
-                    None => false,
-                }
-        } else {
-            self.harddrop_reachable
+        let piece = mino.get_piece();
+        if piece != Piece::T
+            && self
+                .harddrop_reachable
                 .checks(field, mino, x, y, valid_height)
+        {
+            return true;
+        }
+
+        // T以外のミノは、AllSpinのときだけスピンでも置ける
+        if !self.spin_checker.is_spin_target(piece) {
+            return false;
         }
+
+        let mut freeze = dyn_clone::clone_box(field);
+        freeze.put(mino, x, y);
+        let cleared_rows = freeze.clear_filled_rows() as u8;
+
+        (cleared_rows >= self.min_cleared_rows)
+            && match self.spin_checker.check(
+                field,
+                &SimpleMinoOperation::new(mino, x, y),
+                valid_height,
+                cleared_rows,
+            ) {
+                Some(spin) if self.regular_only && spin.spin == TSpins::Mini => false,
+                Some(_) => true,
+                None => false,
+            }
     }
 
     fn check(
@@ -120,6 +135,62 @@ mod tests {
         reachable.checks(field, MinoFactory::new().get(piece, rotate), x, y, MAX_Y)
     }
 
+    #[test]
+    fn all_spin() {
+        let mino_factory = MinoFactory::new();
+        let mino_shifter = MinoShifter::new();
+        let mino_rotation = srs_mino_rotation_factory::create();
+        let create = |spin_rule| {
+            TSpinOrHarddropReachable::new(
+                &mino_factory,
+                &mino_shifter,
+                mino_rotation.as_ref(),
+                MAX_Y,
+                1,
+                false,
+                false,
+                spin_rule,
+            )
+        };
+
+        #[rustfmt::skip]
+        let field = field_factory::create_field_with_marks(
+            String::new()
+                + "XXX___XXXX"
+                + "XXXXX__XXX"
+                + "XXXX__XXXX"
+        );
+
+        // the Z can only be rotated into the slot
+        assert!(!reachable_wrapper(
+            &mut create(SpinRule::TSpin),
+            field.as_ref(),
+            Z,
+            Spawn,
+            5,
+            1
+        ));
+        assert!(reachable_wrapper(
+            &mut create(SpinRule::AllSpin),
+            field.as_ref(),
+            Z,
+            Spawn,
+            5,
+            1
+        ));
+
+        // the other pieces can still be harddropped
+        let field = field_factory::create_field_with_marks("XXXX______".to_owned());
+        assert!(reachable_wrapper(
+            &mut create(SpinRule::AllSpin),
+            field.as_ref(),
+            I,
+            Spawn,
+            6,
+            0
+        ));
+    }
+
     #[test]
     fn visualize() {
         let mut field = field_factory::create_field(2);
@@ -157,6 +228,7 @@ mod tests {
                 required_rows,
                 true,
                 false,
+                SpinRule::TSpin,
             );
 
             #[rustfmt::skip]
@@ -212,6 +284,7 @@ mod tests {
                 required_rows,
                 true,
                 false,
+                SpinRule::TSpin,
             );
 
             #[rustfmt::skip]
@@ -267,6 +340,7 @@ mod tests {
                 required_rows,
                 true,
                 false,
+                SpinRule::TSpin,
             );
 
             #[rustfmt::skip]
@@ -321,6 +395,7 @@ mod tests {
                 required_rows,
                 true,
                 false,
+                SpinRule::TSpin,
             );
 
             #[rustfmt::skip]
@@ -405,6 +480,7 @@ mod tests {
             min_rows_cleared,
             regular_only,
             false,
+            SpinRule::TSpin,
         );
 
         // Mini tests
@@ -0,0 +1,787 @@
+//! Porting note: a field of 8 boards (48 rows), for fields higher than LargeField such as garbage-heavy positions.
+//! The boards are kept in an array and handled in loops instead of being unrolled like the other fields.
+//! The keys of the rows use the same layout as the other fields, where the boards are in the columns 0 to 7.
+
+use super::{
+    bit_operators,
+    field::{Field, FieldHelper},
+    field_constants::{BoardCount, BOARD_HEIGHT, FIELD_WIDTH, VALID_BOARD_RANGE},
+    key_operators,
+    large_field::{LargeField, MAX_FIELD_HEIGHT as LARGE_MAX},
+    long_board_map,
+    middle_field::{MiddleField, MAX_FIELD_HEIGHT as MIDDLE_MAX},
+    small_field::{SmallField, MAX_FIELD_HEIGHT as SMALL_MAX},
+    zobrist,
+};
+use crate::sfinder_core::mino::mino::Mino;
+use std::fmt::Debug;
+
+const BOARD_COUNT: usize = BoardCount::ExtraLarge as usize;
+pub const MAX_FIELD_HEIGHT: u8 = BOARD_HEIGHT * BOARD_COUNT as u8;
+
+// Porting note: the second value is the fingerprint of the boards
+#[derive(Clone)]
+pub struct ExtraLargeField([u64; BOARD_COUNT], u64);
+
+impl ExtraLargeField {
+    fn get_boards(&self) -> [u64; BOARD_COUNT] {
+        self.0
+    }
+
+    // Porting note: called at the end of the functions that change the boards
+    fn update_fingerprint(&mut self, before: [u64; BOARD_COUNT]) {
+        self.1 ^= zobrist::get_changed_hash(&before, &self.0);
+    }
+
+    /// returns the index of the board, and the adjusted y position such that it is less than BOARD_HEIGHT
+    fn select(y: u8) -> (usize, u8) {
+        ((y / BOARD_HEIGHT) as usize, y % BOARD_HEIGHT)
+    }
+
+    // The number of rows of the board that are below max_y
+    fn get_rows_below(max_y: u8, index: usize) -> u8 {
+        max_y
+            .saturating_sub(index as u8 * BOARD_HEIGHT)
+            .min(BOARD_HEIGHT)
+    }
+
+    // The masks of the mino on each board it overlaps.
+    // Unlike the other fields, the unused bits are masked off so that they stay unset.
+    fn get_mino_masks(mino: &Mino, x: u8, y: u8) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let (index, y_off) = Self::select(y);
+
+        [-1, 0, 1].into_iter().filter_map(move |offset: i8| {
+            let board_index = index as i8 + offset;
+            let board_y = y_off as i8 - offset * BOARD_HEIGHT as i8;
+
+            (0 <= board_index
+                && board_index < BOARD_COUNT as i8
+                && board_y + mino.get_min_y() < BOARD_HEIGHT as i8
+                && 0 <= board_y + mino.get_max_y())
+            .then(|| {
+                (
+                    board_index as usize,
+                    mino.get_mask(x, board_y) & VALID_BOARD_RANGE,
+                )
+            })
+        })
+    }
+
+    // The 6 rows starting from y
+    fn get_rows_from(&self, y: u8) -> u64 {
+        let (index, y_off) = Self::select(y);
+        let low = bit_operators::board_shr(self.get_board(index as u8) & VALID_BOARD_RANGE, y_off);
+
+        if y_off == 0 {
+            low
+        } else {
+            low | bit_operators::board_shl(
+                self.get_board(index as u8 + 1) & VALID_BOARD_RANGE,
+                BOARD_HEIGHT - y_off,
+            ) & VALID_BOARD_RANGE
+        }
+    }
+
+    fn combine_keys(keys: [u64; BOARD_COUNT]) -> u64 {
+        keys.iter()
+            .enumerate()
+            .fold(0, |key, (index, board_key)| key | board_key << index)
+    }
+
+    fn delete_row(&mut self, delete_keys: [u64; BOARD_COUNT]) {
+        let before = self.get_boards();
+
+        let mut boards = [0; BOARD_COUNT];
+        // the number of rows that are already placed in the new boards
+        let mut left_rows = 0;
+
+        for (board, delete_key) in self.0.iter().zip(delete_keys) {
+            let new_board = long_board_map::delete_row(*board, delete_key);
+            let (index, y_off) = Self::select(left_rows);
+
+            boards[index] |= bit_operators::board_shl(new_board, y_off) & VALID_BOARD_RANGE;
+            if y_off != 0 && index + 1 < BOARD_COUNT {
+                boards[index + 1] |= bit_operators::board_shr(new_board, BOARD_HEIGHT - y_off);
+            }
+
+            left_rows += BOARD_HEIGHT - delete_key.count_ones() as u8;
+        }
+
+        self.0 = boards;
+
+        self.update_fingerprint(before);
+    }
+
+    fn fill_all(&mut self) {
+        let before = self.get_boards();
+
+        self.0 = [VALID_BOARD_RANGE; BOARD_COUNT];
+
+        self.update_fingerprint(before);
+    }
+
+    // row_fill_fn is used to factor out the two calls of this function that differ only by this argument.
+    fn insert_row_with_key(&mut self, delete_key: u64, row_fill_fn: fn(u64, u64) -> u64) {
+        let before = self.get_boards();
+
+        // the row of the current field that becomes the bottom row of the next board
+        let mut y = 0;
+
+        let boards = std::array::from_fn(|index| {
+            let delete_key = <dyn Field>::extract_delete_key(delete_key, index as u8);
+            let left_row = BOARD_HEIGHT - delete_key.count_ones() as u8;
+
+            let board = if y < MAX_FIELD_HEIGHT {
+                self.get_rows_from(y) & bit_operators::get_row_mask_below_y(left_row)
+            } else {
+                0
+            };
+            y += left_row;
+
+            row_fill_fn(board, delete_key) & VALID_BOARD_RANGE
+        });
+
+        self.0 = boards;
+
+        self.update_fingerprint(before);
+    }
+}
+
+impl Field for ExtraLargeField {
+    fn new() -> Self {
+        Self([0; BOARD_COUNT], 0)
+    }
+
+    fn get_max_field_height(&self) -> u8 {
+        MAX_FIELD_HEIGHT
+    }
+
+    fn get_board_count(&self) -> BoardCount {
+        BoardCount::ExtraLarge
+    }
+
+    fn set_block(&mut self, x: u8, y: u8) {
+        if self.is_empty_block(x, y) {
+            self.1 ^= zobrist::get_block_hash(x, y);
+        }
+
+        let (index, y_off) = Self::select(y);
+        self.0[index] |= bit_operators::get_x_mask(x, y_off);
+    }
+
+    fn remove_block(&mut self, x: u8, y: u8) {
+        if !self.is_empty_block(x, y) {
+            self.1 ^= zobrist::get_block_hash(x, y);
+        }
+
+        let (index, y_off) = Self::select(y);
+        self.0[index] &= !bit_operators::get_x_mask(x, y_off);
+    }
+
+    fn clear_all(&mut self) {
+        self.0 = [0; BOARD_COUNT];
+        self.1 = 0;
+    }
+
+    fn put(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
+        for (index, mask) in Self::get_mino_masks(mino, x, y) {
+            self.0[index] |= mask;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn can_put(&self, mino: &Mino, x: u8, y: u8) -> bool {
+        Self::get_mino_masks(mino, x, y).all(|(index, mask)| self.0[index] & mask == 0)
+    }
+
+    fn remove(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
+        for (index, mask) in Self::get_mino_masks(mino, x, y) {
+            self.0[index] &= !mask;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn can_reach_on_harddrop(&self, mino: &Mino, x: u8, start_y: u8) -> bool {
+        self._can_reach_on_harddrop(mino, x, start_y, MAX_FIELD_HEIGHT)
+    }
+
+    fn is_empty_block(&self, x: u8, y: u8) -> bool {
+        let (index, y_off) = Self::select(y);
+        self.0[index] & bit_operators::get_x_mask(x, y_off) == 0
+    }
+
+    fn exists_above_row(&self, y: u8) -> bool {
+        if y >= MAX_FIELD_HEIGHT {
+            return false;
+        }
+
+        let (index, y_off) = Self::select(y);
+        self.0[index] & <dyn Field>::get_valid_mask(y_off) != 0
+            || self.0[index + 1..].iter().any(|&board| board != 0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&board| board == 0)
+    }
+
+    fn is_filled_in_column(&self, x: u8, max_y: u8) -> bool {
+        self.0.iter().enumerate().all(|(index, board)| {
+            let mask = bit_operators::get_column_mask(Self::get_rows_below(max_y, index), x);
+            board & mask == mask
+        })
+    }
+
+    fn is_wall_between_left(&self, x: u8, max_y: u8) -> bool {
+        self.0.iter().enumerate().all(|(index, &board)| {
+            bit_operators::is_wall_between_left(x, Self::get_rows_below(max_y, index), board)
+        })
+    }
+
+    fn get_block_count_in_column(&self, x: u8, max_y: u8) -> u32 {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, board)| {
+                let mask = bit_operators::get_column_mask(Self::get_rows_below(max_y, index), x);
+                (board & mask).count_ones()
+            })
+            .sum()
+    }
+
+    fn get_block_count_in_row(&self, y: u8) -> u32 {
+        let (index, y_off) = Self::select(y);
+        (self.0[index] & bit_operators::get_row_mask(y_off)).count_ones()
+    }
+
+    fn exists_block_in_row(&self, y: u8) -> bool {
+        let (index, y_off) = Self::select(y);
+        self.0[index] & bit_operators::get_row_mask(y_off) != 0
+    }
+
+    fn get_num_of_all_blocks(&self) -> u32 {
+        self.0.iter().map(|board| board.count_ones()).sum()
+    }
+
+    fn clear_filled_rows_return_key(&mut self) -> u64 {
+        let delete_keys = self.0.map(key_operators::get_delete_key);
+
+        self.delete_row(delete_keys);
+
+        Self::combine_keys(delete_keys)
+    }
+
+    fn get_filled_rows_key(&self) -> u64 {
+        Self::combine_keys(self.0.map(key_operators::get_delete_key))
+    }
+
+    fn get_using_key(&self) -> u64 {
+        Self::combine_keys(self.0.map(key_operators::get_using_key))
+    }
+
+    fn insert_filled_row_with_key(&mut self, delete_key: u64) {
+        self.insert_row_with_key(delete_key, long_board_map::insert_filled_row);
+    }
+
+    fn insert_blank_row_with_key(&mut self, delete_key: u64) {
+        self.insert_row_with_key(delete_key, long_board_map::insert_blank_row);
+    }
+
+    fn delete_rows_with_key(&mut self, delete_key: u64) {
+        self.delete_row(std::array::from_fn(|index| {
+            <dyn Field>::extract_delete_key(delete_key, index as u8)
+        }));
+    }
+
+    fn fill_row(&mut self, y: u8) {
+        let before = self.get_boards();
+
+        let (index, y_off) = Self::select(y);
+        self.0[index] |= bit_operators::get_row_mask(y_off);
+
+        self.update_fingerprint(before);
+    }
+
+    fn get_board(&self, index: u8) -> u64 {
+        self.0.get(index as usize).copied().unwrap_or(0)
+    }
+
+    fn prune(&self, max_height: u8) -> Box<dyn Field> {
+        if max_height <= SMALL_MAX {
+            Box::new(SmallField::from(self.0[0]))
+        } else if max_height <= MIDDLE_MAX {
+            Box::new(MiddleField::from_parts(self.0[0], self.0[1]))
+        } else if max_height <= LARGE_MAX {
+            Box::new(LargeField::from_parts(
+                self.0[0], self.0[1], self.0[2], self.0[3],
+            ))
+        } else {
+            Box::new(self.clone())
+        }
+    }
+
+    fn merge(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
+        for (index, board) in self.0.iter_mut().enumerate() {
+            *board |= other.get_board(index as u8);
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn can_merge(&self, other: &dyn Field) -> bool {
+        self.0
+            .iter()
+            .enumerate()
+            .all(|(index, board)| board & other.get_board(index as u8) == 0)
+    }
+
+    fn reduce(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
+        for (index, board) in self.0.iter_mut().enumerate() {
+            *board &= !other.get_board(index as u8);
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn get_upper_y_with_4_blocks(&self) -> u8 {
+        assert_eq!(self.get_num_of_all_blocks(), 4);
+
+        let index = self.0.iter().rposition(|&board| board != 0).unwrap();
+        bit_operators::get_highest_y(self.0[index]) + index as u8 * BOARD_HEIGHT
+    }
+
+    fn get_min_x(&self) -> Option<u8> {
+        bit_operators::try_get_lowest_x(self.0.iter().fold(0, |acc, board| acc | board))
+    }
+
+    fn get_min_y(&self) -> Option<u8> {
+        self.0.iter().enumerate().find_map(|(index, &board)| {
+            bit_operators::try_get_lowest_y(board).map(|min_y| min_y + index as u8 * BOARD_HEIGHT)
+        })
+    }
+
+    fn slide_left(&mut self, slide: u8) {
+        let before = self.get_boards();
+
+        let mask = bit_operators::get_column_mask_right_of_row(slide);
+        for board in self.0.iter_mut() {
+            *board = (*board & mask) >> slide;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn slide_right(&mut self, slide: u8) {
+        let before = self.get_boards();
+
+        let mask = bit_operators::get_column_mask_left_of_row(FIELD_WIDTH - slide);
+        for board in self.0.iter_mut() {
+            *board = (*board & mask) << slide;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn slide_down_one(&mut self) {
+        let before = self.get_boards();
+
+        for index in 0..BOARD_COUNT {
+            self.0[index] = (bit_operators::board_shr(self.0[index] & VALID_BOARD_RANGE, 1)
+                | bit_operators::board_shl(self.get_board(index as u8 + 1), BOARD_HEIGHT - 1))
+                & VALID_BOARD_RANGE;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn slide_down(&mut self, slide: u8) {
+        if slide < MAX_FIELD_HEIGHT {
+            self.delete_rows_with_key(key_operators::get_mask_for_key_below_y(slide));
+        } else {
+            self.clear_all();
+        }
+    }
+
+    fn slide_up_with_empty_row(&mut self, slide: u8) {
+        if slide < MAX_FIELD_HEIGHT {
+            self.insert_blank_row_with_key(key_operators::get_mask_for_key_below_y(slide));
+        } else {
+            self.clear_all();
+        }
+    }
+
+    fn slide_up_with_filled_row(&mut self, slide: u8) {
+        if slide < MAX_FIELD_HEIGHT {
+            self.insert_filled_row_with_key(key_operators::get_mask_for_key_below_y(slide));
+        } else {
+            self.fill_all();
+        }
+    }
+
+    fn contains(&self, child: &dyn Field) -> bool {
+        (0..child.get_board_count() as u8).all(|index| {
+            let child_board = child.get_board(index);
+            self.get_board(index) & child_board == child_board
+        })
+    }
+
+    fn invert(&mut self) {
+        let before = self.get_boards();
+
+        for board in self.0.iter_mut() {
+            *board = !*board & VALID_BOARD_RANGE;
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn mirror(&mut self) {
+        let before = self.get_boards();
+
+        for board in self.0.iter_mut() {
+            *board = key_operators::mirror(*board);
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn mask(&mut self, mask_field: &dyn Field) {
+        let before = self.get_boards();
+
+        for (index, board) in self.0.iter_mut().enumerate() {
+            *board &= mask_field.get_board(index as u8);
+        }
+
+        self.update_fingerprint(before);
+    }
+
+    fn fingerprint(&self) -> u64 {
+        self.1
+    }
+}
+
+impl Debug for ExtraLargeField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ExtraLargeField")?;
+        for board in self.0 {
+            write!(f, " {board:#062b}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for ExtraLargeField {
+    fn eq(&self, other: &Self) -> bool {
+        <dyn Field>::eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::{
+        field::field_factory,
+        mino::{mino_factory::MinoFactory, piece::Piece},
+        srs::rotate::Rotate,
+    };
+    use rand::{rngs::ThreadRng, thread_rng, Rng};
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    const FULL_ROW: u16 = (1 << FIELD_WIDTH) - 1;
+
+    // Porting note: the rows are used as the expected values, since there is no field to compare with above 24 rows
+    fn to_rows(field: &dyn Field) -> Vec<u16> {
+        (0..MAX_FIELD_HEIGHT)
+            .map(|y| {
+                (0..FIELD_WIDTH)
+                    .filter(|&x| field.exists_block(x, y))
+                    .fold(0, |row, x| row | 1 << x)
+            })
+            .collect()
+    }
+
+    fn from_rows(rows: &[u16]) -> ExtraLargeField {
+        let mut field = ExtraLargeField::new();
+        for (y, row) in rows.iter().enumerate() {
+            for x in (0..FIELD_WIDTH).filter(|x| row & 1 << x != 0) {
+                field.set_block(x, y as u8);
+            }
+        }
+        field
+    }
+
+    fn gen_rows(rngs: &mut ThreadRng) -> Vec<u16> {
+        (0..MAX_FIELD_HEIGHT)
+            .map(|_| {
+                if rngs.gen_bool(0.3) {
+                    FULL_ROW
+                } else {
+                    rngs.gen_range(0..FULL_ROW)
+                }
+            })
+            .collect()
+    }
+
+    fn get_bit_keys(rows: &[u16], predicate: fn(u16) -> bool) -> u64 {
+        (0..MAX_FIELD_HEIGHT)
+            .filter(|&y| predicate(rows[y as usize]))
+            .map(key_operators::get_bit_key)
+            .fold(0, std::ops::BitOr::bitor)
+    }
+
+    #[test]
+    fn block() {
+        let mut field = field_factory::create_extra_large_field();
+
+        for index in 0..MAX_FIELD_HEIGHT {
+            field.set_block(index % FIELD_WIDTH, index);
+        }
+
+        assert_eq!(field.get_num_of_all_blocks(), MAX_FIELD_HEIGHT as u32);
+        assert_eq!(field.get_min_y(), Some(0));
+        assert!(field.exists_above_row(MAX_FIELD_HEIGHT - 1));
+
+        for y in 0..MAX_FIELD_HEIGHT {
+            for x in 0..FIELD_WIDTH {
+                assert_eq!(field.is_empty_block(x, y), x != y % FIELD_WIDTH);
+            }
+            assert_eq!(field.get_block_count_in_row(y), 1);
+        }
+
+        for index in 0..MAX_FIELD_HEIGHT {
+            field.remove_block(index % FIELD_WIDTH, index);
+        }
+
+        assert!(field.is_empty());
+    }
+
+    #[test]
+    fn put_and_remove() {
+        let mino_factory = MinoFactory::new();
+        let mut field = ExtraLargeField::new();
+
+        for piece in Piece::value_list() {
+            for rotate in Rotate::value_list() {
+                let mino = mino_factory.get(*piece, *rotate);
+
+                for y in -mino.get_min_y() as u8..MAX_FIELD_HEIGHT - mino.get_max_y() as u8 {
+                    for x in -mino.get_min_x() as u8..FIELD_WIDTH - mino.get_max_x() as u8 {
+                        assert!(field.can_put(mino, x, y));
+                        field.put(mino, x, y);
+
+                        assert_eq!(field.get_num_of_all_blocks(), 4);
+                        for position in mino.get_positions() {
+                            assert!(field.exists_block(
+                                (x as i8 + position.x) as u8,
+                                (y as i8 + position.y) as u8
+                            ));
+                        }
+                        assert!(!field.can_put(mino, x, y));
+                        assert_eq!(
+                            field.get_upper_y_with_4_blocks(),
+                            (y as i8 + mino.get_max_y()) as u8
+                        );
+
+                        field.remove(mino, x, y);
+                        assert!(field.is_empty());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_y_on_harddrop() {
+        let mino_factory = MinoFactory::new();
+        let mut field = ExtraLargeField::new();
+        field.fill_row(30);
+
+        let mino = mino_factory.get(Piece::I, Rotate::Left);
+        let y = (31 - mino.get_min_y()) as u8;
+        assert_eq!(field.get_y_on_harddrop(mino, 4, MAX_FIELD_HEIGHT), y);
+        assert!(field.can_reach_on_harddrop(mino, 4, y));
+        assert!(!field.can_reach_on_harddrop(mino, 4, 2));
+        assert!(field.is_on_ground(mino, 4, y));
+    }
+
+    #[test]
+    fn column_random() {
+        let mut rngs = thread_rng();
+
+        for _ in 0..100 {
+            let rows = gen_rows(&mut rngs);
+            let field = from_rows(&rows);
+
+            let x = rngs.gen_range(1..FIELD_WIDTH);
+            let max_y = rngs.gen_range(0..=MAX_FIELD_HEIGHT);
+            let below = &rows[..max_y as usize];
+
+            assert_eq!(
+                field.get_block_count_in_column(x, max_y),
+                below.iter().filter(|&row| row & 1 << x != 0).count() as u32
+            );
+            assert_eq!(
+                field.is_filled_in_column(x, max_y),
+                below.iter().all(|&row| row & 1 << x != 0)
+            );
+            assert_eq!(
+                field.is_wall_between_left(x, max_y),
+                below.iter().all(|&row| row & (0b11 << (x - 1)) != 0)
+            );
+        }
+    }
+
+    #[test]
+    fn clear_filled_rows_random() {
+        let mut rngs = thread_rng();
+
+        for _ in 0..1000 {
+            let rows = gen_rows(&mut rngs);
+            let mut field = from_rows(&rows);
+            let original = field.clone();
+
+            let delete_key = get_bit_keys(&rows, |row| row == FULL_ROW);
+            assert_eq!(field.get_filled_rows_key(), delete_key);
+            assert_eq!(field.get_using_key(), get_bit_keys(&rows, |row| row != 0));
+
+            assert_eq!(field.clear_filled_rows_return_key(), delete_key);
+            let mut expected = rows
+                .iter()
+                .copied()
+                .filter(|&row| row != FULL_ROW)
+                .collect::<Vec<_>>();
+            expected.resize(MAX_FIELD_HEIGHT as usize, 0);
+            assert_eq!(to_rows(&field), expected);
+
+            field.insert_filled_row_with_key(delete_key);
+            assert_eq!(field, original);
+
+            field.delete_rows_with_key(delete_key);
+            field.insert_blank_row_with_key(delete_key);
+            assert_eq!(
+                to_rows(&field),
+                rows.iter()
+                    .map(|&row| if row == FULL_ROW { 0 } else { row })
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn slide_random() {
+        let mut rngs = thread_rng();
+
+        for _ in 0..1000 {
+            let rows = gen_rows(&mut rngs);
+            let field = from_rows(&rows);
+            let slide = rngs.gen_range(0..=MAX_FIELD_HEIGHT);
+
+            let mut down = field.clone();
+            down.slide_down(slide);
+            let mut expected = rows[slide as usize..].to_vec();
+            expected.resize(MAX_FIELD_HEIGHT as usize, 0);
+            assert_eq!(to_rows(&down), expected);
+
+            let mut up = field.clone();
+            up.slide_up_with_filled_row(slide);
+            let mut expected = vec![FULL_ROW; slide as usize];
+            expected.extend(&rows[..(MAX_FIELD_HEIGHT - slide) as usize]);
+            assert_eq!(to_rows(&up), expected);
+
+            let mut up = field.clone();
+            up.slide_up_with_empty_row(slide);
+            let mut expected = vec![0; slide as usize];
+            expected.extend(&rows[..(MAX_FIELD_HEIGHT - slide) as usize]);
+            assert_eq!(to_rows(&up), expected);
+
+            let mut down_one = field.clone();
+            down_one.slide_down_one();
+            let mut expected = rows[1..].to_vec();
+            expected.push(0);
+            assert_eq!(to_rows(&down_one), expected);
+        }
+    }
+
+    #[test]
+    fn slide_horizontal_and_mirror() {
+        let mut rngs = thread_rng();
+
+        for _ in 0..100 {
+            let rows = gen_rows(&mut rngs);
+            let slide = rngs.gen_range(0..=FIELD_WIDTH);
+
+            let mut field = from_rows(&rows);
+            field.slide_left(slide);
+            assert_eq!(
+                to_rows(&field),
+                rows.iter().map(|row| row >> slide).collect::<Vec<_>>()
+            );
+
+            let mut field = from_rows(&rows);
+            field.slide_right(slide);
+            assert_eq!(
+                to_rows(&field),
+                rows.iter()
+                    .map(|row| (row << slide) & FULL_ROW)
+                    .collect::<Vec<_>>()
+            );
+
+            let mut field = from_rows(&rows);
+            field.mirror();
+            assert_eq!(
+                to_rows(&field),
+                rows.iter()
+                    .map(|row| row.reverse_bits() >> (16 - FIELD_WIDTH))
+                    .collect::<Vec<_>>()
+            );
+
+            let mut field = from_rows(&rows);
+            field.invert();
+            assert_eq!(
+                to_rows(&field),
+                rows.iter().map(|row| !row & FULL_ROW).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn compare_with_large_field() {
+        let hash = |field: &dyn Field| {
+            let mut hasher = DefaultHasher::new();
+            field.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let marks = String::new() + "X_________" + "XX________";
+        let large = field_factory::create_large_field_with_marks(marks.clone());
+        let mut field = field_factory::create_extra_large_field_with_marks(marks);
+
+        assert!(&large as &dyn Field == &field as &dyn Field);
+        assert_eq!(hash(&large), hash(&field));
+        assert!(field.contains(&large));
+        assert!(large.contains(&field));
+        assert!(field.prune(LARGE_MAX).as_ref() == &large as &dyn Field);
+
+        field.set_block(0, 40);
+        assert!(&large as &dyn Field != &field as &dyn Field);
+        assert!(!large.contains(&field));
+        assert!(!field.can_merge(&large));
+
+        field.reduce(&large);
+        assert_eq!(field.get_num_of_all_blocks(), 1);
+        assert_eq!(field.get_min_x(), Some(0));
+        assert_eq!(field.get_min_y(), Some(40));
+    }
+}
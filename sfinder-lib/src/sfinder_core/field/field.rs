@@ -1,6 +1,7 @@
 use super::{
     bit_operators,
     field_constants::{BoardCount, BOARD_HEIGHT, FIELD_WIDTH, VALID_BOARD_RANGE},
+    zobrist,
 };
 use crate::{
     extras::hash_code::HashCode,
@@ -14,7 +15,8 @@ use std::fmt::Debug;
 // Each field is split into multiple bitboards in its internal representation.
 // Rather than keeping the unused bits in a board unset, it is at an unknown state and must be masked off before being shifted into VALID_BOARD_RANGE.
 // THe only function in the Field interface that could alter the unused bits is put.
-pub trait Field: Debug + DynClone /* + PartialOrd */ {
+// Porting note: fields are Send and Sync, so that they can be shared between the workers of a parallel search.
+pub trait Field: Debug + DynClone + Send + Sync /* + PartialOrd */ {
     fn new() -> Self
     where
         Self: Sized;
@@ -22,6 +24,11 @@ pub trait Field: Debug + DynClone /* + PartialOrd */ {
     // フィールドの最大高さを返却
     fn get_max_field_height(&self) -> u8;
 
+    // Porting note: only VariableWidthField has a width other than FIELD_WIDTH
+    fn get_width(&self) -> u8 {
+        FIELD_WIDTH
+    }
+
     // Porting note: This function is used in conjunction with getBoard to be able to access
     // the other Field's data even when they may be different concrete types
     // 6列分のフィールドを表現するボードの個数を返却
@@ -229,6 +236,15 @@ pub trait Field: Debug + DynClone /* + PartialOrd */ {
 
     // `maskField` のブロックだけマスクする
     fn mask(&mut self, mask_field: &dyn Field);
+
+    // Porting note: a Zobrist hash of the blocks, which is kept up to date by the fields on each change.
+    // Fields with the same blocks have the same fingerprint, regardless of the board count.
+    // This default recomputes it from the boards.
+    fn fingerprint(&self) -> u64 {
+        (0..self.get_board_count() as u8).fold(0, |hash, index| {
+            hash ^ zobrist::get_board_hash(self.get_board(index), index)
+        })
+    }
 }
 
 // Porting note: This collection of helper functions is distinct from the FieldHelper class, which is dropped.
@@ -250,7 +266,7 @@ pub trait FieldHelper {
 
     #[inline]
     fn extract_delete_key(delete_key: u64, index: u8) -> u64 {
-        assert!(index <= 4);
+        assert!(index < BoardCount::ExtraLarge as u8);
         (delete_key >> index) & bit_operators::get_column_one_row_below_y(BOARD_HEIGHT)
     }
 
@@ -288,10 +304,50 @@ pub trait FieldHelper {
 
 impl FieldHelper for dyn Field {}
 
+// The boards only contain the columns below FIELD_WIDTH, so the other columns are compared by rows
+fn get_outer_rows(field: &dyn Field) -> Vec<u64> {
+    if field.get_width() <= FIELD_WIDTH {
+        return Vec::new();
+    }
+
+    (0..field.get_max_field_height())
+        .map(|y| {
+            (FIELD_WIDTH..field.get_width())
+                .filter(|&x| field.exists_block(x, y))
+                .fold(0, |row, x| row | 1 << (x - FIELD_WIDTH))
+        })
+        .collect()
+}
+
 impl std::cmp::PartialEq for dyn Field + '_ {
     fn eq(&self, other: &Self) -> bool {
         let largest_board_count = self.get_board_count().max(other.get_board_count());
-        (0..largest_board_count as u8).all(|index| self.get_board(index) == other.get_board(index))
+        self.get_width() == other.get_width()
+            && (0..largest_board_count as u8)
+                .all(|index| self.get_board(index) == other.get_board(index))
+            && get_outer_rows(self) == get_outer_rows(other)
+    }
+}
+
+impl Eq for dyn Field + '_ {}
+
+// Porting note: empty boards at the top are skipped,
+// so that equal fields with different board counts have the same hash
+impl std::hash::Hash for dyn Field + '_ {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let board_count = (0..self.get_board_count() as u8)
+            .rev()
+            .find(|&index| self.get_board(index) != 0)
+            .map_or(0, |index| index + 1);
+
+        for index in 0..board_count {
+            state.write_u64(self.get_board(index));
+        }
+
+        if self.get_width() != FIELD_WIDTH {
+            state.write_u8(self.get_width());
+            std::hash::Hash::hash(&get_outer_rows(self), state);
+        }
     }
 }
 
@@ -321,7 +377,11 @@ impl std::cmp::PartialOrd for dyn Field + '_ {
             }
         }
 
-        Some(std::cmp::Ordering::Equal)
+        Some(
+            self.get_width()
+                .cmp(&other.get_width())
+                .then_with(|| get_outer_rows(self).cmp(&get_outer_rows(other))),
+        )
     }
 }
 
@@ -330,8 +390,16 @@ dyn_clone::clone_trait_object!(Field);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sfinder_core::field::key_operators;
-    use rand::{thread_rng, Rng};
+    use crate::sfinder_core::{
+        field::{field_factory, key_operators},
+        mino::{mino_factory::MinoFactory, piece::Piece},
+        srs::rotate::Rotate,
+    };
+    use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng, Rng};
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
 
     fn legacy_extract_delete_key(delete_key: u64, index: u8) -> u64 {
         assert!(index <= 4);
@@ -357,4 +425,147 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        let hash = |field: &dyn Field| {
+            let mut hasher = DefaultHasher::new();
+            field.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let marks = String::new() + "X_________" + "XX________";
+        let small = field_factory::create_small_field_with_marks(marks.clone());
+        let middle = field_factory::create_middle_field_with_marks(marks.clone());
+        let large = field_factory::create_large_field_with_marks(marks);
+
+        assert!(&small as &dyn Field == &middle as &dyn Field);
+        assert_eq!(hash(&small), hash(&middle));
+        assert_eq!(hash(&small), hash(&large));
+
+        let other = field_factory::create_small_field_with_marks(String::new() + "_X________");
+        assert_ne!(hash(&small), hash(&other));
+    }
+
+    fn recompute_fingerprint(field: &dyn Field) -> u64 {
+        (0..field.get_max_field_height())
+            .flat_map(|y| (0..FIELD_WIDTH).map(move |x| (x, y)))
+            .filter(|&(x, y)| field.exists_block(x, y))
+            .fold(0, |hash, (x, y)| hash ^ zobrist::get_block_hash(x, y))
+    }
+
+    fn gen_key(rngs: &mut ThreadRng, height: u8) -> u64 {
+        (0..height)
+            .filter(|_| rngs.gen_bool(0.2))
+            .map(key_operators::get_bit_key)
+            .fold(0, std::ops::BitOr::bitor)
+    }
+
+    // Applies a random change to the field
+    fn change_randomly(
+        rngs: &mut ThreadRng,
+        mino_factory: &MinoFactory,
+        field: &mut dyn Field,
+        other: &dyn Field,
+    ) {
+        let height = field.get_max_field_height();
+        let x = rngs.gen_range(0..FIELD_WIDTH);
+        let y = rngs.gen_range(0..height);
+
+        match rngs.gen_range(0..20) {
+            0..=3 => field.set_block(x, y),
+            4 => field.remove_block(x, y),
+            5..=7 => {
+                let mino = mino_factory.get(
+                    *Piece::value_list().choose(rngs).unwrap(),
+                    *Rotate::value_list().choose(rngs).unwrap(),
+                );
+                let x = rngs.gen_range(-mino.get_min_x()..FIELD_WIDTH as i8 - mino.get_max_x());
+                let y = rngs.gen_range(-mino.get_min_y()..height as i8 - mino.get_max_y());
+                if rngs.gen_bool(0.7) {
+                    field.put(mino, x as u8, y as u8);
+                } else {
+                    field.remove(mino, x as u8, y as u8);
+                }
+            }
+            8 => field.fill_row(y),
+            9 => {
+                field.clear_filled_rows_return_key();
+            }
+            10 => field.insert_filled_row_with_key(gen_key(rngs, height)),
+            11 => field.insert_blank_row_with_key(gen_key(rngs, height)),
+            12 => field.delete_rows_with_key(gen_key(rngs, height)),
+            13 => field.slide_left(rngs.gen_range(0..=3)),
+            14 => field.slide_right(rngs.gen_range(0..=3)),
+            15 => match rngs.gen_range(0..4) {
+                0 => field.slide_down_one(),
+                1 => field.slide_down(rngs.gen_range(0..=height)),
+                2 => field.slide_up_with_empty_row(rngs.gen_range(0..=height)),
+                _ => field.slide_up_with_filled_row(rngs.gen_range(0..=height)),
+            },
+            16 => field.mirror(),
+            17 => match rngs.gen_range(0..3) {
+                0 => field.merge(other),
+                1 => field.reduce(other),
+                _ => field.mask(other),
+            },
+            18 => field.invert(),
+            _ => {
+                if rngs.gen_bool(0.1) {
+                    field.clear_all()
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fingerprint_agrees_with_recomputation() {
+        let mut rngs = thread_rng();
+        let mino_factory = MinoFactory::new();
+
+        for height in [6, 12, 24, 48] {
+            let mut field = field_factory::create_field(height);
+            let mut other = field_factory::create_field(height);
+
+            for _ in 0..5000 {
+                change_randomly(&mut rngs, &mino_factory, field.as_mut(), other.as_ref());
+                assert_eq!(
+                    field.fingerprint(),
+                    recompute_fingerprint(field.as_ref()),
+                    "{field:?}"
+                );
+
+                if rngs.gen_bool(0.1) {
+                    std::mem::swap(&mut field, &mut other);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fingerprint_agrees_with_eq() {
+        let marks = String::new() + "X_________" + "XX________";
+        let small = field_factory::create_small_field_with_marks(marks.clone());
+        let large = field_factory::create_large_field_with_marks(marks.clone());
+        let extra_large = field_factory::create_extra_large_field_with_marks(marks.clone());
+        let variable = field_factory::create_variable_width_field_with_marks(10, marks);
+
+        assert_eq!(small.fingerprint(), large.fingerprint());
+        assert_eq!(small.fingerprint(), extra_large.fingerprint());
+        assert_eq!(small.fingerprint(), variable.fingerprint());
+        assert_eq!(
+            small.fingerprint(),
+            (&small as &dyn Field).prune(6).fingerprint()
+        );
+
+        // 同じブロックは同じフィンガープリントになる
+        let mut field = field_factory::create_middle_field();
+        field.set_block(1, 0);
+        field.set_block(0, 0);
+        field.set_block(0, 1);
+        assert_eq!(field.fingerprint(), small.fingerprint());
+
+        let other = field_factory::create_small_field_with_marks(String::new() + "_X________");
+        assert_ne!(small.fingerprint(), other.fingerprint());
+    }
 }
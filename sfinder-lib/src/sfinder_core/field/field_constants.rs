@@ -5,6 +5,7 @@ pub enum BoardCount {
     Small = 1,
     Middle = 2,
     Large = 4,
+    ExtraLarge = 8,
 }
 
 pub const FIELD_WIDTH: u8 = 10;
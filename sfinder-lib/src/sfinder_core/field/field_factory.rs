@@ -1,8 +1,12 @@
 use super::{
+    extra_large_field::{ExtraLargeField, MAX_FIELD_HEIGHT as EXTRA_LARGE_MAX},
     field::Field,
     large_field::{LargeField, MAX_FIELD_HEIGHT as LARGE_MAX},
     middle_field::{MiddleField, MAX_FIELD_HEIGHT as MIDDLE_MAX},
     small_field::{SmallField, MAX_FIELD_HEIGHT as SMALL_MAX},
+    variable_width_field::{
+        VariableWidthField, MAX_FIELD_HEIGHT as VARIABLE_WIDTH_MAX, MAX_FIELD_WIDTH,
+    },
 };
 use crate::{
     common::tetfu::{common::color_type::ColorType, field::colored_field::ColoredField},
@@ -14,7 +18,10 @@ pub fn create_field(max_height: u8) -> Box<dyn Field> {
         ..=SMALL_MAX => Box::new(SmallField::new()),
         ..=MIDDLE_MAX => Box::new(MiddleField::new()),
         ..=LARGE_MAX => Box::new(LargeField::new()),
-        _ => panic!("Field height should be equal or less than {LARGE_MAX}: height={max_height}"),
+        ..=EXTRA_LARGE_MAX => Box::new(ExtraLargeField::new()),
+        _ => panic!(
+            "Field height should be equal or less than {EXTRA_LARGE_MAX}: height={max_height}"
+        ),
     }
 }
 
@@ -48,20 +55,21 @@ pub fn create_small_field_with_marks(marks: String) -> SmallField {
 /// This function does not guarentee that the marks can be set to the field and needs to be checked.
 fn set_marks_to_field(marks: String, field: &mut dyn Field, is_block: bool) {
     assert!(marks.is_ascii());
-    let max_y = marks.len() as u8 / FIELD_WIDTH;
+    let width = field.get_width() as usize;
+    let max_y = marks.len() / width;
     let marks = marks.into_bytes();
 
     for y in 0..max_y {
-        for x in 0..FIELD_WIDTH {
-            match marks[((max_y - y - 1) * FIELD_WIDTH + x) as usize] {
+        for x in 0..width {
+            match marks[(max_y - y - 1) * width + x] {
                 b' ' | b'_' => {
                     if !is_block {
-                        field.set_block(x, y)
+                        field.set_block(x as u8, y as u8)
                     }
                 }
                 _ => {
                     if is_block {
-                        field.set_block(x, y)
+                        field.set_block(x as u8, y as u8)
                     }
                 }
             }
@@ -131,6 +139,52 @@ pub fn create_large_field_with_marks(marks: String) -> LargeField {
     create_large_field_with_marks_and_block(marks, true)
 }
 
+pub fn create_extra_large_field() -> ExtraLargeField {
+    ExtraLargeField::new()
+}
+
+pub fn create_extra_large_field_with_marks(marks: String) -> ExtraLargeField {
+    assert!(
+        marks.len() <= EXTRA_LARGE_MAX as usize * FIELD_WIDTH as usize,
+        "marks is too long for ExtraLargeField"
+    );
+    assert_eq!(
+        marks.len() % FIELD_WIDTH as usize,
+        0,
+        "length of marks should be multiple of {FIELD_WIDTH}"
+    );
+
+    let mut field = create_extra_large_field();
+
+    set_marks_to_field(marks, &mut field, true);
+
+    field
+}
+
+pub fn create_variable_width_field(width: u8) -> VariableWidthField {
+    VariableWidthField::with_width(width)
+}
+
+// Only the tests create a VariableWidthField from marks
+#[cfg(test)]
+pub fn create_variable_width_field_with_marks(width: u8, marks: String) -> VariableWidthField {
+    assert_eq!(
+        marks.len() % width as usize,
+        0,
+        "length of marks should be multiple of {width}"
+    );
+    assert!(
+        marks.len() / width as usize <= VARIABLE_WIDTH_MAX as usize,
+        "marks is too long for VariableWidthField"
+    );
+
+    let mut field = create_variable_width_field(width);
+
+    set_marks_to_field(marks, &mut field, true);
+
+    field
+}
+
 // TODO: niche use
 pub fn create_inverse_field(marks: String) -> Box<dyn Field> {
     assert_eq!(
@@ -146,10 +200,11 @@ pub fn create_inverse_field(marks: String) -> Box<dyn Field> {
     }
 }
 
+// The rows above the colored field are empty
 pub fn from_colored_field(colored_field: &dyn ColoredField, height: u8) -> Box<dyn Field> {
     let mut field = create_field(height);
 
-    for y in 0..height {
+    for y in 0..height.min(colored_field.get_max_height() as u8) {
         for x in 0..FIELD_WIDTH {
             if colored_field.get_color(x, y) != ColorType::Empty {
                 field.set_block(x, y);
@@ -160,6 +215,34 @@ pub fn from_colored_field(colored_field: &dyn ColoredField, height: u8) -> Box<d
     field
 }
 
+// Same as from_colored_field, but a VariableWidthField is created when the width is not FIELD_WIDTH.
+// The columns of the colored field at or right of the width are dropped.
+pub fn from_colored_field_with_width(
+    colored_field: &dyn ColoredField,
+    height: u8,
+    width: u8,
+) -> Box<dyn Field> {
+    if width == FIELD_WIDTH {
+        return from_colored_field(colored_field, height);
+    }
+    assert!(
+        (1..=MAX_FIELD_WIDTH).contains(&width) && height <= VARIABLE_WIDTH_MAX,
+        "Field should be 1 <= width <= {MAX_FIELD_WIDTH} and height <= {VARIABLE_WIDTH_MAX}: width={width}, height={height}"
+    );
+
+    let mut field = create_variable_width_field(width);
+
+    for y in 0..height.min(colored_field.get_max_height() as u8) {
+        for x in 0..width.min(FIELD_WIDTH) {
+            if colored_field.get_color(x, y) != ColorType::Empty {
+                field.set_block(x, y);
+            }
+        }
+    }
+
+    Box::new(field)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sfinder_core::field::field_constants::BoardCount;
@@ -215,6 +298,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_field_by_height() {
+        for (height, board_count) in [
+            (1, BoardCount::Small),
+            (SMALL_MAX, BoardCount::Small),
+            (SMALL_MAX + 1, BoardCount::Middle),
+            (MIDDLE_MAX + 1, BoardCount::Large),
+            (LARGE_MAX, BoardCount::Large),
+            (LARGE_MAX + 1, BoardCount::ExtraLarge),
+            (EXTRA_LARGE_MAX, BoardCount::ExtraLarge),
+        ] {
+            let field = create_field(height);
+            assert_eq!(field.get_board_count(), board_count, "{height}");
+            assert!(height <= field.get_max_field_height());
+        }
+    }
+
+    #[test]
+    fn colored_field_with_width() {
+        use crate::common::tetfu::field::colored_field_factory;
+
+        let colored_field = colored_field_factory::create_colored_field(
+            String::new() + "XX________" + "XXXXXX_XXX",
+        );
+
+        let field = from_colored_field_with_width(&colored_field, 2, 4);
+        assert_eq!(field.get_width(), 4);
+        assert_eq!(
+            field.as_ref(),
+            &create_variable_width_field_with_marks(4, String::new() + "XX__" + "XXXX")
+                as &dyn Field
+        );
+
+        let field = from_colored_field_with_width(&colored_field, 2, 12);
+        assert_eq!(
+            field.as_ref(),
+            &create_variable_width_field_with_marks(
+                12,
+                String::new() + "XX__________" + "XXXXXX_XXX__"
+            ) as &dyn Field
+        );
+
+        let field = from_colored_field_with_width(&colored_field, 2, FIELD_WIDTH);
+        assert_eq!(field.get_width(), FIELD_WIDTH);
+        assert_eq!(field.get_board_count(), BoardCount::Small);
+    }
+
     #[test]
     fn test_random() {
         let mut rngs = thread_rng();
@@ -1,4 +1,4 @@
-use crate::sfinder_core::field::field_constants::{BOARD_HEIGHT, FIELD_WIDTH};
+use crate::sfinder_core::field::field_constants::BOARD_HEIGHT;
 
 use super::field::Field;
 
@@ -15,7 +15,7 @@ pub fn to_string_with_height(field: &dyn Field, max_field_height: u8) -> String
     (0..max_field_height)
         .rev()
         .map(|y| {
-            (0..FIELD_WIDTH)
+            (0..field.get_width())
                 .map(|x| {
                     if field.is_empty_block(x, y) {
                         EMPTY
@@ -36,7 +36,7 @@ pub fn to_reduced_string(field: &dyn Field) -> String {
     (0..max_field_height)
         .rev()
         .map(|y| {
-            (0..FIELD_WIDTH)
+            (0..field.get_width())
                 .map(|x| {
                     if field.is_empty_block(x, y) {
                         EMPTY
@@ -46,7 +46,7 @@ pub fn to_reduced_string(field: &dyn Field) -> String {
                 })
                 .collect::<String>()
         })
-        .skip_while(|line| line == "__________")
+        .skip_while(|line| !line.contains(EXISTS))
         .collect::<Vec<String>>()
         .join("\n")
 }
@@ -54,7 +54,7 @@ pub fn to_reduced_string(field: &dyn Field) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sfinder_core::field::field_factory;
+    use crate::sfinder_core::field::{field_constants::FIELD_WIDTH, field_factory};
     use rand::{thread_rng, Rng};
 
     #[test]
@@ -1,7 +1,15 @@
-use crate::sfinder_core::field::{bit_operators, field_constants::BOARD_HEIGHT};
+use crate::sfinder_core::field::{
+    bit_operators,
+    field_constants::{BoardCount, BOARD_HEIGHT},
+};
 
 use super::bit_operators::repeat_rows;
 
+// Porting note: the keys cover the rows of ExtraLargeField, a column of the key for each board
+const KEY_COLUMN_COUNT: u8 = BoardCount::ExtraLarge as u8;
+const MAX_KEY_HEIGHT: u8 = KEY_COLUMN_COUNT * BOARD_HEIGHT;
+const VALID_KEY_RANGE: u64 = repeat_rows((1 << KEY_COLUMN_COUNT) - 1);
+
 /// Folds each row in the board to signify which rows are completely filled.
 pub const fn get_delete_key(board: u64) -> u64 {
     let b0101010101 = (board & repeat_rows(0b1010101010)) >> 1 & board;
@@ -10,7 +18,7 @@ pub const fn get_delete_key(board: u64) -> u64 {
     (b0000000101 & repeat_rows(0b0000000100)) >> 2 & b0000000101
 }
 
-const KEY_MASKS: [u64; 25] = [
+const KEY_MASKS: [u64; 49] = [
     0b0000000000_0000000000_0000000000_0000000000_0000000000_0000000000,
     0b0000000000_0000000000_0000000000_0000000000_0000000000_0000000001,
     0b0000000000_0000000000_0000000000_0000000000_0000000001_0000000001,
@@ -36,18 +44,43 @@ const KEY_MASKS: [u64; 25] = [
     0b0000000111_0000000111_0000001111_0000001111_0000001111_0000001111,
     0b0000000111_0000001111_0000001111_0000001111_0000001111_0000001111,
     0b0000001111_0000001111_0000001111_0000001111_0000001111_0000001111,
+    0b0000001111_0000001111_0000001111_0000001111_0000001111_0000011111,
+    0b0000001111_0000001111_0000001111_0000001111_0000011111_0000011111,
+    0b0000001111_0000001111_0000001111_0000011111_0000011111_0000011111,
+    0b0000001111_0000001111_0000011111_0000011111_0000011111_0000011111,
+    0b0000001111_0000011111_0000011111_0000011111_0000011111_0000011111,
+    0b0000011111_0000011111_0000011111_0000011111_0000011111_0000011111,
+    0b0000011111_0000011111_0000011111_0000011111_0000011111_0000111111,
+    0b0000011111_0000011111_0000011111_0000011111_0000111111_0000111111,
+    0b0000011111_0000011111_0000011111_0000111111_0000111111_0000111111,
+    0b0000011111_0000011111_0000111111_0000111111_0000111111_0000111111,
+    0b0000011111_0000111111_0000111111_0000111111_0000111111_0000111111,
+    0b0000111111_0000111111_0000111111_0000111111_0000111111_0000111111,
+    0b0000111111_0000111111_0000111111_0000111111_0000111111_0001111111,
+    0b0000111111_0000111111_0000111111_0000111111_0001111111_0001111111,
+    0b0000111111_0000111111_0000111111_0001111111_0001111111_0001111111,
+    0b0000111111_0000111111_0001111111_0001111111_0001111111_0001111111,
+    0b0000111111_0001111111_0001111111_0001111111_0001111111_0001111111,
+    0b0001111111_0001111111_0001111111_0001111111_0001111111_0001111111,
+    0b0001111111_0001111111_0001111111_0001111111_0001111111_0011111111,
+    0b0001111111_0001111111_0001111111_0001111111_0011111111_0011111111,
+    0b0001111111_0001111111_0001111111_0011111111_0011111111_0011111111,
+    0b0001111111_0001111111_0011111111_0011111111_0011111111_0011111111,
+    0b0001111111_0011111111_0011111111_0011111111_0011111111_0011111111,
+    0b0011111111_0011111111_0011111111_0011111111_0011111111_0011111111,
 ];
 
 // y行上のブロックは対象に含まない
 // TODO(#7): Make a better type wrapper for Keys
-/// Crashes if y > 24
+/// Crashes if y > 48
 pub const fn get_mask_for_key_below_y(y: u8) -> u64 {
     KEY_MASKS[y as usize]
 }
 
 // y行上のブロックは対象に含む
+/// Crashes if y > 48
 pub const fn get_mask_for_key_above_y(y: u8) -> u64 {
-    repeat_rows(0b0000001111) - get_mask_for_key_below_y(y)
+    VALID_KEY_RANGE - get_mask_for_key_below_y(y)
 }
 
 #[cfg(test)]
@@ -62,7 +95,7 @@ pub const fn get_delete_bit_key(y: u8) -> u64 {
     get_bit_key(y)
 }
 
-const BIT_KEY_MASKS: [u64; 24] = [
+const BIT_KEY_MASKS: [u64; 48] = [
     0b0000000000_0000000000_0000000000_0000000000_0000000000_0000000001,
     0b0000000000_0000000000_0000000000_0000000000_0000000001_0000000000,
     0b0000000000_0000000000_0000000000_0000000001_0000000000_0000000000,
@@ -87,10 +120,34 @@ const BIT_KEY_MASKS: [u64; 24] = [
     0b0000000000_0000000000_0000001000_0000000000_0000000000_0000000000,
     0b0000000000_0000001000_0000000000_0000000000_0000000000_0000000000,
     0b0000001000_0000000000_0000000000_0000000000_0000000000_0000000000,
+    0b0000000000_0000000000_0000000000_0000000000_0000000000_0000010000,
+    0b0000000000_0000000000_0000000000_0000000000_0000010000_0000000000,
+    0b0000000000_0000000000_0000000000_0000010000_0000000000_0000000000,
+    0b0000000000_0000000000_0000010000_0000000000_0000000000_0000000000,
+    0b0000000000_0000010000_0000000000_0000000000_0000000000_0000000000,
+    0b0000010000_0000000000_0000000000_0000000000_0000000000_0000000000,
+    0b0000000000_0000000000_0000000000_0000000000_0000000000_0000100000,
+    0b0000000000_0000000000_0000000000_0000000000_0000100000_0000000000,
+    0b0000000000_0000000000_0000000000_0000100000_0000000000_0000000000,
+    0b0000000000_0000000000_0000100000_0000000000_0000000000_0000000000,
+    0b0000000000_0000100000_0000000000_0000000000_0000000000_0000000000,
+    0b0000100000_0000000000_0000000000_0000000000_0000000000_0000000000,
+    0b0000000000_0000000000_0000000000_0000000000_0000000000_0001000000,
+    0b0000000000_0000000000_0000000000_0000000000_0001000000_0000000000,
+    0b0000000000_0000000000_0000000000_0001000000_0000000000_0000000000,
+    0b0000000000_0000000000_0001000000_0000000000_0000000000_0000000000,
+    0b0000000000_0001000000_0000000000_0000000000_0000000000_0000000000,
+    0b0001000000_0000000000_0000000000_0000000000_0000000000_0000000000,
+    0b0000000000_0000000000_0000000000_0000000000_0000000000_0010000000,
+    0b0000000000_0000000000_0000000000_0000000000_0010000000_0000000000,
+    0b0000000000_0000000000_0000000000_0010000000_0000000000_0000000000,
+    0b0000000000_0000000000_0010000000_0000000000_0000000000_0000000000,
+    0b0000000000_0010000000_0000000000_0000000000_0000000000_0000000000,
+    0b0010000000_0000000000_0000000000_0000000000_0000000000_0000000000,
 ];
 
 // TODO (#4): check if using 1 << (y % 6 * 10 + y / 6) instead is good enough
-/// Panics if y > 23
+/// Panics if y > 47
 pub const fn get_bit_key(y: u8) -> u64 {
     BIT_KEY_MASKS[y as usize]
 }
@@ -121,22 +178,14 @@ pub const fn get_using_key(board: u64) -> u64 {
 
 // keyのうち1ビットがオンになっているとき、そのビットのy座標を返却
 pub fn bit_to_y_from_key(key: u64) -> u8 {
-    assert_eq!(
-        (key & repeat_rows(0b0000001111)).count_ones(),
-        1,
-        "{key:0b}"
-    );
-
-    if let low @ 1.. = key & repeat_rows(0b0000000001) {
-        bit_operators::get_lowest_y(low)
-    } else if let mid_low @ 1.. = key & repeat_rows(0b0000000010) {
-        bit_operators::get_lowest_y(mid_low >> 1) + BOARD_HEIGHT
-    } else if let mid_high @ 1.. = key & repeat_rows(0b0000000100) {
-        bit_operators::get_lowest_y(mid_high >> 2) + BOARD_HEIGHT * 2
-    } else {
-        let high = key & repeat_rows(0b0000001000);
-        bit_operators::get_lowest_y(high >> 3) + BOARD_HEIGHT * 3
-    }
+    let key = key & VALID_KEY_RANGE;
+    assert_eq!(key.count_ones(), 1, "{key:0b}");
+
+    // the column of the key is the board, and the row of the key is the row in the board
+    let column = (0..KEY_COLUMN_COUNT)
+        .find(|&column| key & repeat_rows(1 << column) != 0)
+        .unwrap();
+    bit_operators::get_lowest_y(key >> column) + BOARD_HEIGHT * column
 }
 
 // reused in Field implementations
@@ -148,26 +197,18 @@ pub(super) const fn get_lowest_bit(x: u64) -> u64 {
 
 // keyのうち、最も低い行のbitを取り出す
 pub fn extract_lower_bit(key: u64) -> u64 {
-    assert!(
-        (key & repeat_rows(0b0000001111)).count_ones() >= 1,
-        "{key:0b}"
-    );
-
-    // although the return signature looks similar, the different branches give different results because each masks a different range of bits.
-    if let low @ 1.. = key & repeat_rows(0b0000000001) {
-        get_lowest_bit(low)
-    } else if let mid_low @ 1.. = key & repeat_rows(0b0000000010) {
-        get_lowest_bit(mid_low)
-    } else if let mid_high @ 1.. = key & repeat_rows(0b0000000100) {
-        get_lowest_bit(mid_high)
-    } else {
-        let high = key & repeat_rows(0b0000001000);
-        get_lowest_bit(high)
-    }
+    assert!((key & VALID_KEY_RANGE).count_ones() >= 1, "{key:0b}");
+
+    // the lowest rows are in the lowest column that has a bit, not in the lowest bit of the key
+    (0..KEY_COLUMN_COUNT)
+        .map(|column| key & repeat_rows(1 << column))
+        .find(|&column_key| column_key != 0)
+        .map(get_lowest_bit)
+        .unwrap()
 }
 
 pub fn to_column_key(bit_key: u64) -> u64 {
-    (0..24)
+    (0..MAX_KEY_HEIGHT)
         .filter(|&y| bit_key & get_bit_key(y) != 0)
         .map(get_column_key)
         .fold(0, std::ops::BitOr::bitor)
@@ -178,7 +219,7 @@ pub const fn get_column_key(y: u8) -> u64 {
 }
 
 pub fn to_bit_key(column_key: u64) -> u64 {
-    (0..24)
+    (0..MAX_KEY_HEIGHT)
         .filter(|&y| column_key & get_column_key(y) != 0)
         .map(get_bit_key)
         .fold(0, std::ops::BitOr::bitor)
@@ -229,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_get_mask_for_key_below_y() {
-        for y in 0..=24 {
+        for y in 0..=48 {
             let mask = get_mask_for_key_below_y(y);
 
             // y行より下の行が含まれることを確認
@@ -238,7 +279,7 @@ mod tests {
             }
 
             // y行を含めた上の行が含まれないことを確認
-            for line in y..24 {
+            for line in y..48 {
                 assert_eq!(mask & bit_operators::get_x_mask(line / 6, line % 6), 0);
             }
         }
@@ -246,7 +287,7 @@ mod tests {
 
     #[test]
     fn test_get_mask_for_key_above_y() {
-        for y in 0..=24 {
+        for y in 0..=48 {
             let mask = get_mask_for_key_above_y(y);
             // println!("{mask:0b}");
 
@@ -256,7 +297,7 @@ mod tests {
             }
 
             // y行を含めた上の行が含まれることを確認
-            for line in y..24 {
+            for line in y..48 {
                 assert_ne!(mask & bit_operators::get_x_mask(line / 6, line % 6), 0);
             }
         }
@@ -264,7 +305,7 @@ mod tests {
 
     #[test]
     fn test_get_delete_bit_key() {
-        for y in 0..24 {
+        for y in 0..48 {
             assert_eq!(
                 get_delete_bit_key(y),
                 bit_operators::get_x_mask(y / 6, y % 6)
@@ -282,7 +323,7 @@ mod tests {
 
     #[test]
     fn test_bit_to_y_from_key() {
-        for y in 0..24 {
+        for y in 0..48 {
             let key = get_bit_key(y);
             assert_eq!(bit_to_y_from_key(key), y);
         }
@@ -292,11 +333,11 @@ mod tests {
     fn test_extract_lower_bit() {
         let mut rngs = thread_rng();
 
-        for y in 0..24 {
+        for y in 0..48 {
             let key = get_bit_key(y);
 
             let mut current = key;
-            for dy in y + 1..24 {
+            for dy in y + 1..48 {
                 if rngs.gen_bool(0.5) {
                     current |= get_bit_key(dy);
                 }
@@ -316,7 +357,7 @@ mod tests {
 
     #[test]
     fn test_to_column_key() {
-        for y in 0..24 {
+        for y in 0..48 {
             let bit_key = get_bit_key(y);
             assert_eq!(to_column_key(bit_key), get_column_key(y));
         }
@@ -324,7 +365,7 @@ mod tests {
 
     #[test]
     fn test_to_bit_key() {
-        for y in 0..24 {
+        for y in 0..48 {
             let column_key = get_column_key(y);
             assert_eq!(to_bit_key(column_key), get_bit_key(y));
         }
@@ -333,7 +374,7 @@ mod tests {
     #[test]
     fn masks_agree() {
         // Equivalence retrieved from common/generator/DeleteBitKeyGenerator.java
-        for y in 0..24 {
+        for y in 0..48 {
             let delete_key = get_delete_bit_key(y);
             assert_eq!(delete_key.count_ones(), 1);
             assert_eq!(
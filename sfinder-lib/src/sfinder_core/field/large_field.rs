@@ -5,6 +5,7 @@ use super::{
     key_operators, long_board_map,
     middle_field::MiddleField,
     small_field::SmallField,
+    zobrist,
 };
 use crate::sfinder_core::mino::mino::Mino;
 use std::fmt::Debug;
@@ -22,12 +23,19 @@ enum Position {
     High(u8),
 }
 
+// Porting note: the fifth value is the fingerprint of the boards
 #[derive(Clone)]
-pub struct LargeField(u64, u64, u64, u64);
+pub struct LargeField(u64, u64, u64, u64, u64);
 
 impl LargeField {
     pub fn from_parts(low: u64, mid_low: u64, mid_high: u64, high: u64) -> Self {
-        Self(low, mid_low, mid_high, high)
+        Self(
+            low,
+            mid_low,
+            mid_high,
+            high,
+            zobrist::get_boards_hash(&[low, mid_low, mid_high, high]),
+        )
     }
 
     pub fn get_x_board_low(&self) -> u64 {
@@ -46,6 +54,15 @@ impl LargeField {
         self.3
     }
 
+    fn get_boards(&self) -> [u64; 4] {
+        [self.0, self.1, self.2, self.3]
+    }
+
+    // Porting note: called at the end of the functions that change the boards
+    fn update_fingerprint(&mut self, before: [u64; 4]) {
+        self.4 ^= zobrist::get_changed_hash(&before, &self.get_boards());
+    }
+
     fn select(y: u8) -> Position {
         match y {
             FIELD_ROW_HIGH_BORDER_Y.. => Position::High(y - FIELD_ROW_HIGH_BORDER_Y),
@@ -60,6 +77,8 @@ impl LargeField {
     }
 
     fn delete_row(&mut self, key_low: u64, key_mid_low: u64, key_mid_high: u64, key_high: u64) {
+        let before = self.get_boards();
+
         let new_x_boards = [
             long_board_map::delete_row(self.0, key_low),
             long_board_map::delete_row(self.1, key_mid_low),
@@ -107,17 +126,25 @@ impl LargeField {
                     & VALID_BOARD_RANGE;
             self.3 = bit_operators::board_shr(boards[3], delete_row_bottom);
         }
+
+        self.update_fingerprint(before);
     }
 
     fn fill_all(&mut self) {
+        let before = self.get_boards();
+
         self.0 = VALID_BOARD_RANGE;
         self.1 = VALID_BOARD_RANGE;
         self.2 = VALID_BOARD_RANGE;
         self.3 = VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 
     // row_fill_fn is used to factor out the two calls of this function that differ only by this argument.
     fn insert_row_with_key(&mut self, delete_key: u64, row_fill_fn: fn(u64, u64) -> u64) {
+        let before = self.get_boards();
+
         let delete_keys: [_; 4] =
             std::array::from_fn(|index| <dyn Field>::extract_delete_key(delete_key, index as u8));
 
@@ -178,12 +205,14 @@ impl LargeField {
         self.1 = new_x_boards[1] & VALID_BOARD_RANGE;
         self.2 = new_x_boards[2] & VALID_BOARD_RANGE;
         self.3 = new_x_boards[3] & VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 }
 
 impl Field for LargeField {
     fn new() -> Self {
-        Self(0, 0, 0, 0)
+        Self(0, 0, 0, 0, 0)
     }
 
     fn get_max_field_height(&self) -> u8 {
@@ -195,6 +224,10 @@ impl Field for LargeField {
     }
 
     fn set_block(&mut self, x: u8, y: u8) {
+        if self.is_empty_block(x, y) {
+            self.4 ^= zobrist::get_block_hash(x, y);
+        }
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 |= bit_operators::get_x_mask(x, y_off),
             Position::MidLow(y_off) => self.1 |= bit_operators::get_x_mask(x, y_off),
@@ -204,6 +237,10 @@ impl Field for LargeField {
     }
 
     fn remove_block(&mut self, x: u8, y: u8) {
+        if !self.is_empty_block(x, y) {
+            self.4 ^= zobrist::get_block_hash(x, y);
+        }
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 &= !bit_operators::get_x_mask(x, y_off),
             Position::MidLow(y_off) => self.1 &= !bit_operators::get_x_mask(x, y_off),
@@ -217,8 +254,11 @@ impl Field for LargeField {
         self.1 = 0;
         self.2 = 0;
         self.3 = 0;
+        self.4 = 0;
     }
     fn put(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
         match Self::select(y) {
             Position::Low(y_off) => {
                 // no lower board
@@ -267,6 +307,8 @@ impl Field for LargeField {
                 // no higher field
             }
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_put(&self, mino: &Mino, x: u8, y: u8) -> bool {
@@ -319,6 +361,8 @@ impl Field for LargeField {
     }
 
     fn remove(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
         match Self::select(y) {
             Position::Low(y_off) => {
                 // no lower board
@@ -367,6 +411,8 @@ impl Field for LargeField {
                 // no higher board
             }
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_reach_on_harddrop(&self, mino: &Mino, x: u8, start_y: u8) -> bool {
@@ -590,12 +636,16 @@ impl Field for LargeField {
     }
 
     fn fill_row(&mut self, y: u8) {
+        let before = self.get_boards();
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 |= bit_operators::get_row_mask(y_off),
             Position::MidLow(y_off) => self.1 |= bit_operators::get_row_mask(y_off),
             Position::MidHigh(y_off) => self.2 |= bit_operators::get_row_mask(y_off),
             Position::High(y_off) => self.3 |= bit_operators::get_row_mask(y_off),
         }
+
+        self.update_fingerprint(before);
     }
 
     fn get_board(&self, index: u8) -> u64 {
@@ -617,19 +667,23 @@ impl Field for LargeField {
     }
 
     fn merge(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
         match other.get_board_count() {
             BoardCount::Small => self.0 |= other.get_board(0),
             BoardCount::Middle => {
                 self.0 |= other.get_board(0);
                 self.1 |= other.get_board(1);
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 self.0 |= other.get_board(0);
                 self.1 |= other.get_board(1);
                 self.2 |= other.get_board(2);
                 self.3 |= other.get_board(3);
             }
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_merge(&self, other: &dyn Field) -> bool {
@@ -638,7 +692,7 @@ impl Field for LargeField {
             BoardCount::Middle => {
                 self.0 & other.get_board(0) == 0 && self.1 & other.get_board(1) == 0
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 self.0 & other.get_board(0) == 0
                     && self.1 & other.get_board(1) == 0
                     && self.2 & other.get_board(2) == 0
@@ -648,19 +702,23 @@ impl Field for LargeField {
     }
 
     fn reduce(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
         match other.get_board_count() {
             BoardCount::Small => self.0 &= !other.get_board(0),
             BoardCount::Middle => {
                 self.0 &= !other.get_board(0);
                 self.1 &= !other.get_board(1);
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 self.0 &= !other.get_board(0);
                 self.1 &= !other.get_board(1);
                 self.2 &= !other.get_board(2);
                 self.3 &= !other.get_board(3);
             }
         }
+
+        self.update_fingerprint(before);
     }
 
     fn get_upper_y_with_4_blocks(&self) -> u8 {
@@ -700,22 +758,32 @@ impl Field for LargeField {
     }
 
     fn slide_left(&mut self, slide: u8) {
+        let before = self.get_boards();
+
         let mask = bit_operators::get_column_mask_right_of_row(slide);
         self.0 = (self.0 & mask) >> slide;
         self.1 = (self.1 & mask) >> slide;
         self.2 = (self.2 & mask) >> slide;
         self.3 = (self.3 & mask) >> slide;
+
+        self.update_fingerprint(before);
     }
 
     fn slide_right(&mut self, slide: u8) {
+        let before = self.get_boards();
+
         let mask = bit_operators::get_column_mask_left_of_row(FIELD_WIDTH - slide);
         self.0 = (self.0 & mask) << slide;
         self.1 = (self.1 & mask) << slide;
         self.2 = (self.2 & mask) << slide;
         self.3 = (self.3 & mask) << slide;
+
+        self.update_fingerprint(before);
     }
 
     fn slide_down_one(&mut self) {
+        let before = self.get_boards();
+
         self.0 = (bit_operators::board_shr(self.0, 1)
             | bit_operators::board_shl(self.1, BOARD_HEIGHT - 1))
             & VALID_BOARD_RANGE;
@@ -726,6 +794,8 @@ impl Field for LargeField {
             | bit_operators::board_shl(self.3, BOARD_HEIGHT - 1))
             & VALID_BOARD_RANGE;
         self.3 >>= FIELD_WIDTH;
+
+        self.update_fingerprint(before);
     }
 
     fn slide_down(&mut self, slide: u8) {
@@ -784,7 +854,7 @@ impl Field for LargeField {
                 self.0 & child_board_low == child_board_low
                     && self.1 & child_board_mid_low == child_board_mid_low
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 let child_board_low = child.get_board(0);
                 let child_board_mid_low = child.get_board(1);
                 let child_board_mid_high = child.get_board(2);
@@ -793,29 +863,46 @@ impl Field for LargeField {
                     && self.1 & child_board_mid_low == child_board_mid_low
                     && self.2 & child_board_mid_high == child_board_mid_high
                     && self.3 & child_board_high == child_board_high
+                    && (4..child.get_board_count() as u8).all(|index| child.get_board(index) == 0)
             }
         }
     }
 
     fn invert(&mut self) {
+        let before = self.get_boards();
+
         self.0 = !self.0 & VALID_BOARD_RANGE;
         self.1 = !self.1 & VALID_BOARD_RANGE;
         self.2 = !self.2 & VALID_BOARD_RANGE;
         self.3 = !self.3 & VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 
     fn mirror(&mut self) {
+        let before = self.get_boards();
+
         self.0 = key_operators::mirror(self.0);
         self.1 = key_operators::mirror(self.1);
         self.2 = key_operators::mirror(self.2);
         self.3 = key_operators::mirror(self.3);
+
+        self.update_fingerprint(before);
     }
 
     fn mask(&mut self, mask_field: &dyn Field) {
+        let before = self.get_boards();
+
         self.0 &= mask_field.get_board(0);
         self.1 &= mask_field.get_board(1);
         self.2 &= mask_field.get_board(2);
         self.3 &= mask_field.get_board(3);
+
+        self.update_fingerprint(before);
+    }
+
+    fn fingerprint(&self) -> u64 {
+        self.4
     }
 }
 
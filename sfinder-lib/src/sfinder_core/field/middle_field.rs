@@ -4,6 +4,7 @@ use super::{
     field_constants::{BoardCount, BOARD_HEIGHT, FIELD_WIDTH, VALID_BOARD_RANGE},
     key_operators, long_board_map,
     small_field::SmallField,
+    zobrist,
 };
 use crate::sfinder_core::mino::mino::Mino;
 use std::fmt::Debug;
@@ -16,12 +17,13 @@ enum Position {
     High(u8),
 }
 
+// Porting note: the third value is the fingerprint of the boards
 #[derive(Clone)]
-pub struct MiddleField(u64, u64);
+pub struct MiddleField(u64, u64, u64);
 
 impl MiddleField {
     pub fn from_parts(low: u64, high: u64) -> Self {
-        Self(low, high)
+        Self(low, high, zobrist::get_boards_hash(&[low, high]))
     }
 
     pub fn get_x_board_low(&self) -> u64 {
@@ -32,6 +34,15 @@ impl MiddleField {
         self.1
     }
 
+    fn get_boards(&self) -> [u64; 2] {
+        [self.0, self.1]
+    }
+
+    // Porting note: called at the end of the functions that change the boards
+    fn update_fingerprint(&mut self, before: [u64; 2]) {
+        self.2 ^= zobrist::get_changed_hash(&before, &self.get_boards());
+    }
+
     fn select(y: u8) -> Position {
         match y {
             FIELD_ROW_BORDER_Y.. => Position::High(y - FIELD_ROW_BORDER_Y),
@@ -44,6 +55,8 @@ impl MiddleField {
     }
 
     fn delete_row(&mut self, delete_key_low: u64, delete_key_high: u64) {
+        let before = self.get_boards();
+
         let new_x_board_low = long_board_map::delete_row(self.0, delete_key_low);
         let new_x_board_high = long_board_map::delete_row(self.1, delete_key_high);
 
@@ -53,15 +66,23 @@ impl MiddleField {
             | bit_operators::board_shl(new_x_board_high, BOARD_HEIGHT - delete_row_low))
             & VALID_BOARD_RANGE;
         self.1 = bit_operators::board_shr(new_x_board_high, delete_row_low);
+
+        self.update_fingerprint(before);
     }
 
     fn fill_all(&mut self) {
+        let before = self.get_boards();
+
         self.0 = VALID_BOARD_RANGE;
         self.1 = VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 
     // row_fill_fn is used to factor out the two calls of this function that differ only by this argument.
     fn insert_row_with_key(&mut self, delete_key: u64, row_fill_fn: fn(u64, u64) -> u64) {
+        let before = self.get_boards();
+
         let delete_keys = [
             <dyn Field>::extract_delete_key(delete_key, 0),
             <dyn Field>::extract_delete_key(delete_key, 1),
@@ -85,6 +106,8 @@ impl MiddleField {
 
         self.0 = new_x_boards[0];
         self.1 = new_x_boards[1] & VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 }
 
@@ -96,7 +119,7 @@ impl From<MiddleField> for (u64, u64) {
 
 impl Field for MiddleField {
     fn new() -> Self {
-        Self(0, 0)
+        Self(0, 0, 0)
     }
 
     fn get_max_field_height(&self) -> u8 {
@@ -108,6 +131,10 @@ impl Field for MiddleField {
     }
 
     fn set_block(&mut self, x: u8, y: u8) {
+        if self.is_empty_block(x, y) {
+            self.2 ^= zobrist::get_block_hash(x, y);
+        }
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 |= bit_operators::get_x_mask(x, y_off),
             Position::High(y_off) => self.1 |= bit_operators::get_x_mask(x, y_off),
@@ -115,6 +142,10 @@ impl Field for MiddleField {
     }
 
     fn remove_block(&mut self, x: u8, y: u8) {
+        if !self.is_empty_block(x, y) {
+            self.2 ^= zobrist::get_block_hash(x, y);
+        }
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 &= !bit_operators::get_x_mask(x, y_off),
             Position::High(y_off) => self.1 &= !bit_operators::get_x_mask(x, y_off),
@@ -124,9 +155,12 @@ impl Field for MiddleField {
     fn clear_all(&mut self) {
         self.0 = 0;
         self.1 = 0;
+        self.2 = 0;
     }
 
     fn put(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
         // Lowの更新が必要
         if y as i8 + mino.get_min_y() < FIELD_ROW_BORDER_Y as i8 {
             self.0 |= mino.get_mask(x, y as i8);
@@ -136,6 +170,8 @@ impl Field for MiddleField {
         if y as i8 + mino.get_max_y() >= FIELD_ROW_BORDER_Y as i8 {
             self.1 |= mino.get_mask(x, y as i8 - FIELD_ROW_BORDER_Y as i8);
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_put(&self, mino: &Mino, x: u8, y: u8) -> bool {
@@ -158,6 +194,8 @@ impl Field for MiddleField {
     }
 
     fn remove(&mut self, mino: &Mino, x: u8, y: u8) {
+        let before = self.get_boards();
+
         // Lowの更新が必要
         if y as i8 + mino.get_min_y() < FIELD_ROW_BORDER_Y as i8 {
             self.0 &= !mino.get_mask(x, y as i8);
@@ -167,6 +205,8 @@ impl Field for MiddleField {
         if y as i8 + mino.get_max_y() >= FIELD_ROW_BORDER_Y as i8 {
             self.1 &= !mino.get_mask(x, y as i8 - FIELD_ROW_BORDER_Y as i8);
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_reach_on_harddrop(&self, mino: &Mino, x: u8, start_y: u8) -> bool {
@@ -309,10 +349,14 @@ impl Field for MiddleField {
     }
 
     fn fill_row(&mut self, y: u8) {
+        let before = self.get_boards();
+
         match Self::select(y) {
             Position::Low(y_off) => self.0 |= bit_operators::get_row_mask(y_off),
             Position::High(y_off) => self.1 |= bit_operators::get_row_mask(y_off),
         }
+
+        self.update_fingerprint(before);
     }
 
     fn get_board(&self, index: u8) -> u64 {
@@ -332,6 +376,8 @@ impl Field for MiddleField {
     }
 
     fn merge(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
         debug_assert!(other.get_board_count() <= BoardCount::Large);
 
         self.0 |= other.get_board(0);
@@ -339,6 +385,8 @@ impl Field for MiddleField {
         if other.get_board_count() > BoardCount::Small {
             self.1 |= other.get_board(1);
         }
+
+        self.update_fingerprint(before);
     }
 
     fn can_merge(&self, other: &dyn Field) -> bool {
@@ -346,11 +394,13 @@ impl Field for MiddleField {
             && match other.get_board_count() {
                 BoardCount::Small => true,
                 BoardCount::Middle => self.1 & other.get_board(1) == 0,
-                BoardCount::Large => unreachable!(),
+                BoardCount::Large | BoardCount::ExtraLarge => unreachable!(),
             }
     }
 
     fn reduce(&mut self, other: &dyn Field) {
+        let before = self.get_boards();
+
         debug_assert!(other.get_board_count() <= BoardCount::Large);
 
         self.0 &= !other.get_board(0);
@@ -358,6 +408,8 @@ impl Field for MiddleField {
         if other.get_board_count() > BoardCount::Small {
             self.1 &= !other.get_board(1);
         }
+
+        self.update_fingerprint(before);
     }
 
     fn get_upper_y_with_4_blocks(&self) -> u8 {
@@ -387,24 +439,36 @@ impl Field for MiddleField {
     }
 
     fn slide_left(&mut self, slide: u8) {
+        let before = self.get_boards();
+
         let mask = bit_operators::get_column_mask_right_of_row(slide);
 
         self.0 = (self.0 & mask) >> slide;
         self.1 = (self.1 & mask) >> slide;
+
+        self.update_fingerprint(before);
     }
 
     fn slide_right(&mut self, slide: u8) {
+        let before = self.get_boards();
+
         let mask = bit_operators::get_column_mask_left_of_row(FIELD_WIDTH - slide);
 
         self.0 = (self.0 & mask) << slide;
         self.1 = (self.1 & mask) << slide;
+
+        self.update_fingerprint(before);
     }
 
     fn slide_down_one(&mut self) {
+        let before = self.get_boards();
+
         self.0 = (bit_operators::board_shr(self.0, 1)
             | bit_operators::board_shl(self.1, BOARD_HEIGHT - 1))
             & VALID_BOARD_RANGE;
         self.1 = bit_operators::board_shr(self.1, 1);
+
+        self.update_fingerprint(before);
     }
 
     fn slide_down(&mut self, slide: u8) {
@@ -449,31 +513,46 @@ impl Field for MiddleField {
                 self.0 & child_board_low == child_board_low
                     && self.1 & child_board_high == child_board_high
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 let child_board_low = child.get_board(0);
                 let child_board_high = child.get_board(1);
 
                 self.0 & child_board_low == child_board_low
                     && self.1 & child_board_high == child_board_high
-                    && child.get_board(2) == 0
-                    && child.get_board(3) == 0
+                    && (2..child.get_board_count() as u8).all(|index| child.get_board(index) == 0)
             }
         }
     }
 
     fn invert(&mut self) {
+        let before = self.get_boards();
+
         self.0 = !self.0 & VALID_BOARD_RANGE;
         self.1 = !self.1 & VALID_BOARD_RANGE;
+
+        self.update_fingerprint(before);
     }
 
     fn mirror(&mut self) {
+        let before = self.get_boards();
+
         self.0 = key_operators::mirror(self.0);
         self.1 = key_operators::mirror(self.1);
+
+        self.update_fingerprint(before);
     }
 
     fn mask(&mut self, mask_field: &dyn Field) {
+        let before = self.get_boards();
+
         self.0 &= mask_field.get_board(0);
         self.1 &= mask_field.get_board(1);
+
+        self.update_fingerprint(before);
+    }
+
+    fn fingerprint(&self) -> u64 {
+        self.2
     }
 }
 
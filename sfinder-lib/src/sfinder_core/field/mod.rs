@@ -1,5 +1,6 @@
 pub mod bit_operators;
 pub mod block_field_view;
+pub mod extra_large_field;
 pub mod field;
 pub mod field_constants;
 pub mod field_factory;
@@ -9,3 +10,5 @@ pub mod large_field;
 mod long_board_map;
 pub mod middle_field;
 pub mod small_field;
+pub mod variable_width_field;
+pub mod zobrist;
@@ -2,26 +2,33 @@ use super::{
     bit_operators,
     field::{Field, FieldHelper},
     field_constants::{BOARD_HEIGHT, FIELD_WIDTH, VALID_BOARD_RANGE},
-    key_operators, long_board_map,
+    key_operators, long_board_map, zobrist,
 };
 use crate::sfinder_core::{field::field_constants::BoardCount, mino::mino::Mino};
 use std::fmt::Debug;
 
 pub const MAX_FIELD_HEIGHT: u8 = BOARD_HEIGHT;
 
-/// Porting note: clone replaces copy constructor
+/// Porting note: clone replaces copy constructor.
+/// The second value is the fingerprint of the board.
 #[derive(Clone)]
-pub struct SmallField(u64);
+pub struct SmallField(u64, u64);
 
 impl SmallField {
     pub fn get_x_board(&self) -> u64 {
         self.0
     }
+
+    // Porting note: all changes to the board go through here to update the fingerprint
+    fn set_board(&mut self, board: u64) {
+        self.1 ^= zobrist::get_board_hash(self.0 ^ board, 0);
+        self.0 = board;
+    }
 }
 
 impl From<u64> for SmallField {
     fn from(value: u64) -> Self {
-        Self(value)
+        Self(value, zobrist::get_board_hash(value, 0))
     }
 }
 
@@ -33,7 +40,7 @@ impl From<SmallField> for u64 {
 
 impl Field for SmallField {
     fn new() -> Self {
-        Self(0)
+        Self(0, 0)
     }
 
     fn get_max_field_height(&self) -> u8 {
@@ -45,19 +52,20 @@ impl Field for SmallField {
     }
 
     fn set_block(&mut self, x: u8, y: u8) {
-        self.0 |= bit_operators::get_x_mask(x, y);
+        self.set_board(self.0 | bit_operators::get_x_mask(x, y));
     }
 
     fn remove_block(&mut self, x: u8, y: u8) {
-        self.0 &= !bit_operators::get_x_mask(x, y);
+        self.set_board(self.0 & !bit_operators::get_x_mask(x, y));
     }
 
     fn clear_all(&mut self) {
         self.0 = 0;
+        self.1 = 0;
     }
 
     fn put(&mut self, mino: &Mino, x: u8, y: u8) {
-        self.0 |= mino.get_mask(x, y as i8);
+        self.set_board(self.0 | mino.get_mask(x, y as i8));
     }
 
     fn can_put(&self, mino: &Mino, x: u8, y: u8) -> bool {
@@ -65,7 +73,7 @@ impl Field for SmallField {
     }
 
     fn remove(&mut self, mino: &Mino, x: u8, y: u8) {
-        self.0 &= !mino.get_mask(x, y as i8);
+        self.set_board(self.0 & !mino.get_mask(x, y as i8));
     }
 
     fn can_reach_on_harddrop(&self, mino: &Mino, x: u8, start_y: u8) -> bool {
@@ -130,19 +138,19 @@ impl Field for SmallField {
     }
 
     fn insert_filled_row_with_key(&mut self, delete_key: u64) {
-        self.0 = long_board_map::insert_filled_row(self.0, delete_key);
+        self.set_board(long_board_map::insert_filled_row(self.0, delete_key));
     }
 
     fn insert_blank_row_with_key(&mut self, delete_key: u64) {
-        self.0 = long_board_map::insert_blank_row(self.0, delete_key);
+        self.set_board(long_board_map::insert_blank_row(self.0, delete_key));
     }
 
     fn delete_rows_with_key(&mut self, delete_key: u64) {
-        self.0 = long_board_map::delete_row(self.0, delete_key);
+        self.set_board(long_board_map::delete_row(self.0, delete_key));
     }
 
     fn fill_row(&mut self, y: u8) {
-        self.0 |= bit_operators::get_row_mask(y);
+        self.set_board(self.0 | bit_operators::get_row_mask(y));
     }
 
     fn get_board(&self, index: u8) -> u64 {
@@ -158,7 +166,7 @@ impl Field for SmallField {
     }
 
     fn merge(&mut self, other: &dyn Field) {
-        self.0 |= other.get_board(0);
+        self.set_board(self.0 | other.get_board(0));
     }
 
     fn can_merge(&self, other: &dyn Field) -> bool {
@@ -166,7 +174,7 @@ impl Field for SmallField {
     }
 
     fn reduce(&mut self, other: &dyn Field) {
-        self.0 &= !other.get_board(0);
+        self.set_board(self.0 & !other.get_board(0));
     }
 
     fn get_upper_y_with_4_blocks(&self) -> u8 {
@@ -186,29 +194,29 @@ impl Field for SmallField {
 
     fn slide_left(&mut self, slide: u8) {
         let mask = bit_operators::get_column_mask_right_of_row(slide);
-        self.0 = (self.0 & mask) >> slide;
+        self.set_board((self.0 & mask) >> slide);
     }
 
     fn slide_right(&mut self, slide: u8) {
         let mask = bit_operators::get_column_mask_left_of_row(FIELD_WIDTH - slide);
-        self.0 = (self.0 & mask) << slide;
+        self.set_board((self.0 & mask) << slide);
     }
 
     fn slide_down_one(&mut self) {
-        self.0 >>= FIELD_WIDTH;
+        self.set_board(self.0 >> FIELD_WIDTH);
     }
 
     fn slide_down(&mut self, slide: u8) {
-        self.0 >>= slide * FIELD_WIDTH;
+        self.set_board(self.0 >> (slide * FIELD_WIDTH));
     }
 
     fn slide_up_with_filled_row(&mut self, slide: u8) {
         let count = slide * FIELD_WIDTH;
-        self.0 = (self.0 << count) | ((1 << count) - 1);
+        self.set_board((self.0 << count) | ((1 << count) - 1));
     }
 
     fn slide_up_with_empty_row(&mut self, slide: u8) {
-        self.0 <<= slide * FIELD_WIDTH;
+        self.set_board(self.0 << (slide * FIELD_WIDTH));
     }
 
     fn contains(&self, child: &dyn Field) -> bool {
@@ -222,25 +230,27 @@ impl Field for SmallField {
             BoardCount::Middle => {
                 self.0 & child_board_low == child_board_low && child.get_board(1) == 0
             }
-            BoardCount::Large => {
+            BoardCount::Large | BoardCount::ExtraLarge => {
                 self.0 & child_board_low == child_board_low
-                    && child.get_board(1) == 0
-                    && child.get_board(2) == 0
-                    && child.get_board(3) == 0
+                    && (1..child.get_board_count() as u8).all(|index| child.get_board(index) == 0)
             }
         }
     }
 
     fn invert(&mut self) {
-        self.0 = !self.0 & VALID_BOARD_RANGE;
+        self.set_board(!self.0 & VALID_BOARD_RANGE);
     }
 
     fn mirror(&mut self) {
-        self.0 = key_operators::mirror(self.0);
+        self.set_board(key_operators::mirror(self.0));
     }
 
     fn mask(&mut self, mask_field: &dyn Field) {
-        self.0 &= mask_field.get_board(0);
+        self.set_board(self.0 & mask_field.get_board(0));
+    }
+
+    fn fingerprint(&self) -> u64 {
+        self.1
     }
 }
 
@@ -0,0 +1,486 @@
+//! Porting note: a field whose width is set at creation, for boards other than FIELD_WIDTH wide.
+//! Each row is stored as a bitmask instead of the 60-bit boards, so it is slower than the other fields.
+//! The keys of the rows use the same layout as the other fields, so the height is limited to 24 rows.
+//! The boards returned by get_board only contain the columns below FIELD_WIDTH.
+
+use super::{
+    field::Field,
+    field_constants::{BoardCount, BOARD_HEIGHT, FIELD_WIDTH},
+    key_operators, zobrist,
+};
+use crate::sfinder_core::mino::mino::Mino;
+use std::fmt::Debug;
+
+pub const MAX_FIELD_HEIGHT: u8 = BOARD_HEIGHT * 4;
+pub const MAX_FIELD_WIDTH: u8 = 64;
+
+#[derive(Clone)]
+pub struct VariableWidthField {
+    width: u8,
+    rows: [u64; MAX_FIELD_HEIGHT as usize],
+}
+
+impl VariableWidthField {
+    pub fn with_width(width: u8) -> Self {
+        assert!(
+            (1..=MAX_FIELD_WIDTH).contains(&width),
+            "Field width should be 1 <= width <= {MAX_FIELD_WIDTH}: width={width}"
+        );
+
+        Self {
+            width,
+            rows: [0; MAX_FIELD_HEIGHT as usize],
+        }
+    }
+
+    fn get_row_mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    // The blocks of the row in the other field, with the columns that are outside of this field dropped
+    fn get_row_of(&self, other: &dyn Field, y: u8) -> u64 {
+        if other.get_max_field_height() <= y {
+            return 0;
+        }
+
+        (0..self.width.min(other.get_width()))
+            .filter(|&x| other.exists_block(x, y))
+            .fold(0, |row, x| row | 1 << x)
+    }
+
+    fn is_in_row(&self, x: i8, y: i8) -> bool {
+        0 <= x && x < self.width as i8 && 0 <= y
+    }
+
+    fn slide_up(&mut self, slide: u8, inserted_row: u64) {
+        let slide = slide.min(MAX_FIELD_HEIGHT) as usize;
+        self.rows
+            .copy_within(..MAX_FIELD_HEIGHT as usize - slide, slide);
+        self.rows[..slide].fill(inserted_row);
+    }
+
+    // Rebuilds the rows, where the rows in the key are the inserted row, and the others are taken in order
+    fn insert_rows_with_key(&mut self, delete_key: u64, inserted_row: u64) {
+        let mut rows = self.rows.into_iter();
+        self.rows = std::array::from_fn(|y| {
+            if delete_key & key_operators::get_bit_key(y as u8) != 0 {
+                inserted_row
+            } else {
+                rows.next().unwrap()
+            }
+        });
+    }
+}
+
+impl Field for VariableWidthField {
+    fn new() -> Self {
+        Self::with_width(FIELD_WIDTH)
+    }
+
+    fn get_max_field_height(&self) -> u8 {
+        MAX_FIELD_HEIGHT
+    }
+
+    fn get_width(&self) -> u8 {
+        self.width
+    }
+
+    fn get_board_count(&self) -> BoardCount {
+        BoardCount::Large
+    }
+
+    fn set_block(&mut self, x: u8, y: u8) {
+        self.rows[y as usize] |= 1 << x;
+    }
+
+    fn remove_block(&mut self, x: u8, y: u8) {
+        self.rows[y as usize] &= !(1 << x);
+    }
+
+    fn clear_all(&mut self) {
+        self.rows.fill(0);
+    }
+
+    fn put(&mut self, mino: &Mino, x: u8, y: u8) {
+        for position in mino.get_positions() {
+            let block_y = (y as i8 + position.y) as u8;
+            if block_y < MAX_FIELD_HEIGHT {
+                self.set_block((x as i8 + position.x) as u8, block_y);
+            }
+        }
+    }
+
+    fn can_put(&self, mino: &Mino, x: u8, y: u8) -> bool {
+        mino.get_positions().iter().all(|position| {
+            let block_x = x as i8 + position.x;
+            let block_y = y as i8 + position.y;
+
+            // フィールドより上は空白とみなす
+            self.is_in_row(block_x, block_y)
+                && (MAX_FIELD_HEIGHT as i8 <= block_y
+                    || self.is_empty_block(block_x as u8, block_y as u8))
+        })
+    }
+
+    fn remove(&mut self, mino: &Mino, x: u8, y: u8) {
+        for position in mino.get_positions() {
+            let block_y = (y as i8 + position.y) as u8;
+            if block_y < MAX_FIELD_HEIGHT {
+                self.remove_block((x as i8 + position.x) as u8, block_y);
+            }
+        }
+    }
+
+    fn can_reach_on_harddrop(&self, mino: &Mino, x: u8, start_y: u8) -> bool {
+        self._can_reach_on_harddrop(mino, x, start_y, MAX_FIELD_HEIGHT)
+    }
+
+    fn is_empty_block(&self, x: u8, y: u8) -> bool {
+        MAX_FIELD_HEIGHT <= y || self.rows[y as usize] & (1 << x) == 0
+    }
+
+    fn exists_above_row(&self, y: u8) -> bool {
+        y < MAX_FIELD_HEIGHT && self.rows[y as usize..].iter().any(|&row| row != 0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == 0)
+    }
+
+    fn is_filled_in_column(&self, x: u8, max_y: u8) -> bool {
+        self.rows[..max_y as usize]
+            .iter()
+            .all(|&row| row & (1 << x) != 0)
+    }
+
+    fn is_wall_between_left(&self, x: u8, max_y: u8) -> bool {
+        let mask = 0b11 << (x - 1);
+        self.rows[..max_y as usize]
+            .iter()
+            .all(|&row| row & mask != 0)
+    }
+
+    fn get_block_count_in_column(&self, x: u8, max_y: u8) -> u32 {
+        self.rows[..max_y as usize]
+            .iter()
+            .filter(|&&row| row & (1 << x) != 0)
+            .count() as u32
+    }
+
+    fn get_block_count_in_row(&self, y: u8) -> u32 {
+        self.rows[y as usize].count_ones()
+    }
+
+    fn exists_block_in_row(&self, y: u8) -> bool {
+        self.rows[y as usize] != 0
+    }
+
+    fn get_num_of_all_blocks(&self) -> u32 {
+        self.rows.iter().map(|row| row.count_ones()).sum()
+    }
+
+    fn clear_filled_rows_return_key(&mut self) -> u64 {
+        let delete_key = self.get_filled_rows_key();
+
+        self.delete_rows_with_key(delete_key);
+
+        delete_key
+    }
+
+    fn get_filled_rows_key(&self) -> u64 {
+        let row_mask = self.get_row_mask();
+        (0..MAX_FIELD_HEIGHT)
+            .filter(|&y| self.rows[y as usize] == row_mask)
+            .map(key_operators::get_bit_key)
+            .fold(0, std::ops::BitOr::bitor)
+    }
+
+    fn get_using_key(&self) -> u64 {
+        (0..MAX_FIELD_HEIGHT)
+            .filter(|&y| self.rows[y as usize] != 0)
+            .map(key_operators::get_bit_key)
+            .fold(0, std::ops::BitOr::bitor)
+    }
+
+    fn insert_filled_row_with_key(&mut self, delete_key: u64) {
+        self.insert_rows_with_key(delete_key, self.get_row_mask());
+    }
+
+    fn insert_blank_row_with_key(&mut self, delete_key: u64) {
+        self.insert_rows_with_key(delete_key, 0);
+    }
+
+    fn delete_rows_with_key(&mut self, delete_key: u64) {
+        let mut rows = (0..MAX_FIELD_HEIGHT)
+            .filter(|&y| delete_key & key_operators::get_bit_key(y) == 0)
+            .map(|y| self.rows[y as usize]);
+        self.rows = std::array::from_fn(|_| rows.next().unwrap_or(0));
+    }
+
+    fn fill_row(&mut self, y: u8) {
+        self.rows[y as usize] = self.get_row_mask();
+    }
+
+    fn get_board(&self, index: u8) -> u64 {
+        if BoardCount::Large as u8 <= index {
+            return 0;
+        }
+
+        let row_mask = (1 << FIELD_WIDTH) - 1;
+        (0..BOARD_HEIGHT).fold(0, |board, y| {
+            let row = self.rows[(index * BOARD_HEIGHT + y) as usize] & row_mask;
+            board | row << (y * FIELD_WIDTH)
+        })
+    }
+
+    fn prune(&self, _max_height: u8) -> Box<dyn Field> {
+        Box::new(self.clone())
+    }
+
+    fn merge(&mut self, other: &dyn Field) {
+        for y in 0..MAX_FIELD_HEIGHT {
+            self.rows[y as usize] |= self.get_row_of(other, y);
+        }
+    }
+
+    fn can_merge(&self, other: &dyn Field) -> bool {
+        (0..MAX_FIELD_HEIGHT).all(|y| self.rows[y as usize] & self.get_row_of(other, y) == 0)
+    }
+
+    fn reduce(&mut self, other: &dyn Field) {
+        for y in 0..MAX_FIELD_HEIGHT {
+            self.rows[y as usize] &= !self.get_row_of(other, y);
+        }
+    }
+
+    fn get_upper_y_with_4_blocks(&self) -> u8 {
+        assert_eq!(self.get_num_of_all_blocks(), 4);
+        self.rows.iter().rposition(|&row| row != 0).unwrap() as u8
+    }
+
+    fn get_min_x(&self) -> Option<u8> {
+        let columns = self.rows.iter().fold(0, |columns, row| columns | row);
+        (columns != 0).then(|| columns.trailing_zeros() as u8)
+    }
+
+    fn get_min_y(&self) -> Option<u8> {
+        self.rows.iter().position(|&row| row != 0).map(|y| y as u8)
+    }
+
+    fn slide_left(&mut self, slide: u8) {
+        for row in self.rows.iter_mut() {
+            *row >>= slide;
+        }
+    }
+
+    fn slide_right(&mut self, slide: u8) {
+        let row_mask = self.get_row_mask();
+        for row in self.rows.iter_mut() {
+            *row = (*row << slide) & row_mask;
+        }
+    }
+
+    fn slide_down_one(&mut self) {
+        self.slide_down(1);
+    }
+
+    fn slide_down(&mut self, slide: u8) {
+        let slide = slide.min(MAX_FIELD_HEIGHT) as usize;
+        self.rows.copy_within(slide.., 0);
+        self.rows[MAX_FIELD_HEIGHT as usize - slide..].fill(0);
+    }
+
+    fn slide_up_with_empty_row(&mut self, slide: u8) {
+        self.slide_up(slide, 0);
+    }
+
+    fn slide_up_with_filled_row(&mut self, slide: u8) {
+        self.slide_up(slide, self.get_row_mask());
+    }
+
+    fn contains(&self, child: &dyn Field) -> bool {
+        (0..child.get_max_field_height()).all(|y| {
+            (0..child.get_width())
+                .all(|x| child.is_empty_block(x, y) || (x < self.width && self.exists_block(x, y)))
+        })
+    }
+
+    fn invert(&mut self) {
+        let row_mask = self.get_row_mask();
+        for row in self.rows.iter_mut() {
+            *row = !*row & row_mask;
+        }
+    }
+
+    fn mirror(&mut self) {
+        for row in self.rows.iter_mut() {
+            *row = row.reverse_bits() >> (64 - self.width);
+        }
+    }
+
+    fn mask(&mut self, mask_field: &dyn Field) {
+        for y in 0..MAX_FIELD_HEIGHT {
+            self.rows[y as usize] &= self.get_row_of(mask_field, y);
+        }
+    }
+
+    // Porting note: recomputed from the rows, unlike the other fields that keep it up to date
+    fn fingerprint(&self) -> u64 {
+        (0..MAX_FIELD_HEIGHT)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.exists_block(x, y))
+            .fold(0, |hash, (x, y)| {
+                hash ^ if x < FIELD_WIDTH {
+                    zobrist::get_block_hash(x, y)
+                } else {
+                    zobrist::get_outer_block_hash(x, y)
+                }
+            })
+    }
+}
+
+impl Debug for VariableWidthField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let height = self
+            .rows
+            .iter()
+            .rposition(|&row| row != 0)
+            .map_or(0, |y| y + 1);
+        write!(f, "VariableWidthField {{ width: {}, rows: ", self.width)?;
+        f.debug_list()
+            .entries(
+                self.rows[..height]
+                    .iter()
+                    .map(|row| format!("{row:0width$b}", width = self.width as usize)),
+            )
+            .finish()?;
+        write!(f, " }}")
+    }
+}
+
+impl PartialEq for VariableWidthField {
+    fn eq(&self, other: &Self) -> bool {
+        self as &dyn Field == other as &_
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfinder_core::{
+        field::{field_factory, field_view},
+        mino::{mino_factory::MinoFactory, piece::Piece},
+        srs::rotate::Rotate,
+    };
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    #[test]
+    fn put_and_remove() {
+        let mino_factory = MinoFactory::new();
+        let mut field = VariableWidthField::with_width(4);
+        let mino = mino_factory.get(Piece::I, Rotate::Spawn);
+
+        assert!(field.can_put(mino, 1, 0));
+        // 右端をはみ出す
+        assert!(!field.can_put(mino, 2, 0));
+
+        field.put(mino, 1, 0);
+        assert_eq!(field.get_filled_rows_key(), key_operators::get_bit_key(0));
+        assert!(!field.can_put(mino, 1, 0));
+        assert!(field.can_reach_on_harddrop(mino, 1, 1));
+
+        field.remove(mino, 1, 0);
+        assert!(field.is_empty());
+    }
+
+    #[test]
+    fn clear_and_insert_rows() {
+        #[rustfmt::skip]
+        let mut field = field_factory::create_variable_width_field_with_marks(6,
+            String::new()
+                + "X_____"
+                + "XXXXXX"
+                + "__X___"
+                + "XXXXXX"
+        );
+        let expected = field.clone();
+
+        let delete_key = field.clear_filled_rows_return_key();
+        assert_eq!(
+            delete_key,
+            key_operators::get_bit_key(0) | key_operators::get_bit_key(2)
+        );
+        assert_eq!(field_view::to_reduced_string(&field), "X_____\n__X___");
+
+        field.insert_filled_row_with_key(delete_key);
+        assert_eq!(field, expected);
+
+        field.delete_rows_with_key(delete_key);
+        field.insert_blank_row_with_key(delete_key);
+        assert_eq!(
+            field_view::to_reduced_string(&field),
+            "X_____\n______\n__X___\n______"
+        );
+    }
+
+    #[test]
+    fn slide_and_mirror() {
+        #[rustfmt::skip]
+        let mut field = field_factory::create_variable_width_field_with_marks(12,
+            String::new()
+                + "X__________X"
+                + "XX__________"
+        );
+
+        field.mirror();
+        assert_eq!(
+            field_view::to_reduced_string(&field),
+            "X__________X\n__________XX"
+        );
+
+        field.slide_left(1);
+        field.slide_up_with_filled_row(1);
+        assert_eq!(
+            field_view::to_reduced_string(&field),
+            "__________X_\n_________XX_\nXXXXXXXXXXXX"
+        );
+        assert_eq!(field.get_min_x(), Some(0));
+        assert_eq!(field.get_block_count_in_column(10, 3), 3);
+
+        field.slide_down(1);
+        field.invert();
+        assert_eq!(field.get_num_of_all_blocks(), 12 * 24 - 3);
+    }
+
+    #[test]
+    fn compare_with_other_fields() {
+        let hash = |field: &dyn Field| {
+            let mut hasher = DefaultHasher::new();
+            field.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let marks = String::new() + "X_________" + "XX________";
+        let small = field_factory::create_small_field_with_marks(marks.clone());
+        let variable = field_factory::create_variable_width_field_with_marks(10, marks);
+        assert!(&small as &dyn Field == &variable as &dyn Field);
+        assert_eq!(hash(&small), hash(&variable));
+
+        // 幅が異なるフィールドは等しくない
+        let narrow = field_factory::create_variable_width_field_with_marks(
+            5,
+            String::new() + "X____" + "XX___",
+        );
+        assert!(&small as &dyn Field != &narrow as &dyn Field);
+
+        // FIELD_WIDTH以降の列も比較する
+        let wide = |marks: &str| {
+            field_factory::create_variable_width_field_with_marks(12, marks.to_owned())
+        };
+        assert_ne!(wide("X__________X"), wide("X___________"));
+        assert_ne!(hash(&wide("X__________X")), hash(&wide("X___________")));
+    }
+}
@@ -0,0 +1,144 @@
+//! Porting note: Zobrist keys for Field::fingerprint, which is not in the original.
+//! Each block of the field has a random key, and the fingerprint is the xor of the keys of all blocks.
+//! The keys of a board are tabulated per byte, so that the keys of all blocks in a board can be xored in 8 lookups.
+//! Since xor is its own inverse, the fingerprint is updated by xoring the hash of the changed blocks.
+
+use super::{
+    bit_operators,
+    field_constants::{BoardCount, BOARD_HEIGHT, FIELD_WIDTH, VALID_BOARD_RANGE},
+};
+
+const MAX_HEIGHT: u8 = BOARD_HEIGHT * BoardCount::ExtraLarge as u8;
+
+const SEED: u64 = 0x5f3759df_9e3779b9;
+
+const fn split_mix(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+// BYTE_KEYS[byte][value] is the xor of the keys of the bits that are set in the value of the byte-th byte
+const BYTE_KEYS: [[u64; 256]; 8] = {
+    let mut keys = [[0; 256]; 8];
+
+    let mut byte = 0;
+    while byte < 8 {
+        let mut value = 1;
+        while value < 256 {
+            // the lowest bit is added to the keys of the value without it
+            let bit = (value as u64).trailing_zeros() as usize;
+            keys[byte][value] =
+                keys[byte][value & (value - 1)] ^ split_mix(SEED ^ (byte * 8 + bit) as u64);
+            value += 1;
+        }
+        byte += 1;
+    }
+
+    keys
+};
+
+/// Returns the xor of the keys of the blocks in the board.
+/// The keys of each board index are rotated so that the same block in a different board has a different key.
+pub const fn get_board_hash(board: u64, index: u8) -> u64 {
+    let mut board = board & VALID_BOARD_RANGE;
+    let mut hash = 0;
+
+    // the upper bytes are skipped once they are empty
+    let mut byte = 0;
+    while board != 0 {
+        hash ^= BYTE_KEYS[byte][(board & 0xff) as usize];
+        board >>= 8;
+        byte += 1;
+    }
+
+    hash.rotate_left(index as u32 * 8)
+}
+
+pub fn get_boards_hash(boards: &[u64]) -> u64 {
+    let mut hash = 0;
+    for (index, &board) in boards.iter().enumerate() {
+        hash ^= get_board_hash(board, index as u8);
+    }
+    hash
+}
+
+/// Returns the hash of the blocks that changed between the boards, which is xored to the fingerprint to update it.
+pub fn get_changed_hash(before: &[u64], after: &[u64]) -> u64 {
+    let mut hash = 0;
+    for index in 0..before.len() {
+        let changed = before[index] ^ after[index];
+        if changed != 0 {
+            hash ^= get_board_hash(changed, index as u8);
+        }
+    }
+    hash
+}
+
+// BLOCK_KEYS[y][x] is the key of the block at (x, y), which is used when a single block changes
+const BLOCK_KEYS: [[u64; FIELD_WIDTH as usize]; MAX_HEIGHT as usize] = {
+    let mut keys = [[0; FIELD_WIDTH as usize]; MAX_HEIGHT as usize];
+
+    let mut y = 0;
+    while y < MAX_HEIGHT {
+        let mut x = 0;
+        while x < FIELD_WIDTH {
+            keys[y as usize][x as usize] = get_board_hash(
+                bit_operators::get_x_mask(x, y % BOARD_HEIGHT),
+                y / BOARD_HEIGHT,
+            );
+            x += 1;
+        }
+        y += 1;
+    }
+
+    keys
+};
+
+pub fn get_block_hash(x: u8, y: u8) -> u64 {
+    BLOCK_KEYS[y as usize][x as usize]
+}
+
+// Porting note: for the columns that are not in the boards, such as in VariableWidthField
+pub fn get_outer_block_hash(x: u8, y: u8) -> u64 {
+    split_mix(!SEED ^ ((y as u64) << 8 | x as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn block_hashes_are_unique() {
+        let hashes = (0..MAX_HEIGHT)
+            .flat_map(|y| (0..FIELD_WIDTH).map(move |x| get_block_hash(x, y)))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(hashes.len(), MAX_HEIGHT as usize * FIELD_WIDTH as usize);
+        assert!(!hashes.contains(&0));
+    }
+
+    #[test]
+    fn board_hash_is_xor_of_block_hashes() {
+        let board = 0b1000000001_0000000000_0110000000_0000000000_0000000000_0000010001;
+
+        for index in 0..8 {
+            let expected = (0..BOARD_HEIGHT)
+                .flat_map(|y| (0..FIELD_WIDTH).map(move |x| (x, y)))
+                .filter(|&(x, y)| board & bit_operators::get_x_mask(x, y) != 0)
+                .fold(0, |hash, (x, y)| {
+                    hash ^ get_block_hash(x, y + index * BOARD_HEIGHT)
+                });
+
+            assert_eq!(get_board_hash(board, index), expected);
+        }
+
+        // the unused bits are ignored
+        assert_eq!(
+            get_board_hash(board | 0b1111 << 60, 0),
+            get_board_hash(board, 0)
+        );
+    }
+}
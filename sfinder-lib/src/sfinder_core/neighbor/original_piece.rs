@@ -136,7 +136,7 @@ pub fn create_all_pieces<'a>(
             Rotate::value_list().iter().flat_map(move |rotate| {
                 let mino = mino_factory.get(*piece, *rotate);
 
-                let (x_range, y_range) = get_ranges(mino, field_height);
+                let (x_range, y_range) = get_ranges(mino, FIELD_WIDTH, field_height);
 
                 y_range.flat_map(move |y| {
                     x_range
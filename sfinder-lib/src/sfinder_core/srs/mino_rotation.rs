@@ -1,13 +1,11 @@
 use super::{pattern::Pattern, rotate::Rotate, rotate_direction::RotateDirection};
 use crate::{
     common::datastore::coordinate::Coordinate,
-    sfinder_core::{
-        field::{field::Field, field_constants::FIELD_WIDTH},
-        mino::mino::Mino,
-    },
+    sfinder_core::{field::field::Field, mino::mino::Mino},
 };
 
-pub trait MinoRotation {
+// Porting note: Sync, so that a rotation can be shared between the workers of a parallel search
+pub trait MinoRotation: Sync {
     // Porting note: refactors retrieval of map
     fn get_map(&self, direction: RotateDirection) -> &[Pattern];
 
@@ -59,7 +57,7 @@ fn _get_kicks(
     pattern: &Pattern,
 ) -> Option<Coordinate> {
     let min_x = -after.get_min_x();
-    let max_x = FIELD_WIDTH as i8 - after.get_max_x();
+    let max_x = field.get_width() as i8 - after.get_max_x();
     let min_y = -after.get_min_y();
 
     pattern
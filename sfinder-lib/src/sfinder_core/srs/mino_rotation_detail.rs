@@ -4,7 +4,7 @@ use super::{
 use crate::{
     common::datastore::coordinate::Coordinate,
     sfinder_core::{
-        field::{field::Field, field_constants::FIELD_WIDTH},
+        field::field::Field,
         mino::{mino::Mino, mino_factory::MinoFactory},
     },
 };
@@ -47,7 +47,7 @@ impl<'a> MinoRotationDetail<'a> {
 
         {
             let min_x = -after.get_min_x();
-            let max_x = FIELD_WIDTH as i8 - after.get_max_x();
+            let max_x = field.get_width() as i8 - after.get_max_x();
             let min_y = -after.get_min_y();
 
             offsets.get_checks().iter().enumerate().find_map(
@@ -1,20 +1,27 @@
-use crate::sfinder_core::{field::field_constants::FIELD_WIDTH, mino::mino::Mino};
+#[cfg(test)]
+use crate::sfinder_core::field::field_constants::FIELD_WIDTH;
+use crate::sfinder_core::mino::mino::Mino;
 
 // TODO: This does not use Coordinate because it uses i8
 #[cfg(test)]
 pub fn walk(mino: &'static Mino, max_y: u8) -> impl Iterator<Item = (u8, u8)> {
-    let (x_range, y_range) = get_ranges(mino, max_y);
+    let (x_range, y_range) = get_ranges(mino, FIELD_WIDTH, max_y);
     y_range.flat_map(move |y| x_range.clone().map(move |x| (x, y)))
 }
 
 // (x_range, y_range)
+// The ranges are empty if the mino does not fit in the field.
 #[inline]
-pub fn get_ranges(mino: &'static Mino, max_y: u8) -> (std::ops::Range<u8>, std::ops::Range<u8>) {
+pub fn get_ranges(
+    mino: &'static Mino,
+    field_width: u8,
+    max_y: u8,
+) -> (std::ops::Range<u8>, std::ops::Range<u8>) {
     (
         u8::try_from(-mino.get_min_x()).unwrap()
-            ..u8::try_from(FIELD_WIDTH as i8 - mino.get_max_x()).unwrap(),
+            ..u8::try_from(field_width as i8 - mino.get_max_x()).unwrap_or(0),
         u8::try_from(-mino.get_min_y()).unwrap()
-            ..u8::try_from(max_y as i8 - mino.get_max_y()).unwrap(),
+            ..u8::try_from(max_y as i8 - mino.get_max_y()).unwrap_or(0),
     )
 }
 
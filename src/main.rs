@@ -1,3 +1,12 @@
+use sfinder_lib::entry::entry_point_main;
+
 fn main() {
-    sfinder_lib::test_function();
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match entry_point_main::run(&args) {
+        Ok(output) => print!("{output}"),
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
 }